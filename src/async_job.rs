@@ -1,15 +1,27 @@
-use std::{fmt, future::Future, pin::Pin};
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{atomic::AtomicU32, Arc},
+};
 
 use chrono::{DateTime, Local, TimeZone};
 
 use crate::{
+    error::JobError,
     job::Job,
-    job_schedule::{JobSchedule, WithSchedule},
+    job_schedule::{record_outcome, record_success, JobSchedule, WithSchedule},
     timeprovider::{ChronoTimeProvider, TimeProvider},
     Interval,
 };
 
+/// The future for a single run of an [`AsyncJob`], e.g. as returned by [`AsyncScheduler::drain_pending`](crate::AsyncScheduler::drain_pending).
 pub type JobFuture = Box<dyn Future<Output = ()> + Send + 'static>;
+
+/// The future for a single run of an [`AsyncJob`], passing on the error if the run failed
+pub(crate) type FallibleJobFuture =
+    Pin<Box<dyn Future<Output = Result<(), JobError>> + Send + 'static>>;
+
 /// An asynchronous job to run on the scheduler.
 /// Create these by calling [`AsyncScheduler::every()`](crate::AsyncScheduler::every).
 ///
@@ -24,7 +36,7 @@ where
 }
 
 trait GiveMeAPinnedFuture {
-    fn get_pinned(&mut self) -> Pin<JobFuture>;
+    fn get_pinned(&mut self) -> FallibleJobFuture;
 }
 
 struct JobWrapper<F, T>
@@ -50,8 +62,57 @@ where
     F: FnMut() -> T,
     T: Future<Output = ()> + Send + 'static,
 {
-    fn get_pinned(&mut self) -> Pin<JobFuture> {
-        Box::pin((self.f)())
+    fn get_pinned(&mut self) -> FallibleJobFuture {
+        let future = (self.f)();
+        Box::pin(async move {
+            future.await;
+            Ok(())
+        })
+    }
+}
+
+/// Wraps a job whose futures are already boxed and pinned
+struct BoxedJobWrapper<F> {
+    f: F,
+}
+
+impl<F> GiveMeAPinnedFuture for BoxedJobWrapper<F>
+where
+    F: FnMut() -> Pin<JobFuture>,
+{
+    fn get_pinned(&mut self) -> FallibleJobFuture {
+        let future = (self.f)();
+        Box::pin(async move {
+            future.await;
+            Ok(())
+        })
+    }
+}
+
+/// Wraps a job whose futures return a `Result`, counting failures
+struct FallibleJobWrapper<F> {
+    f: F,
+    failures: Arc<AtomicU32>,
+}
+
+impl<F, T, E> GiveMeAPinnedFuture for FallibleJobWrapper<F>
+where
+    F: FnMut() -> T,
+    T: Future<Output = Result<(), E>> + Send + 'static,
+    E: Into<JobError>,
+{
+    fn get_pinned(&mut self) -> FallibleJobFuture {
+        let future = (self.f)();
+        let failures = self.failures.clone();
+        Box::pin(async move {
+            let result = future.await.map_err(Into::into);
+            #[cfg(feature = "tracing")]
+            if let Err(e) = &result {
+                tracing::warn!(error = %e, "job failed");
+            }
+            record_outcome(&failures, result.is_ok());
+            result
+        })
     }
 }
 
@@ -101,6 +162,10 @@ where
     /// Specify a task to run, and schedule its next run
     ///
     /// The function passed into this method should return a value implementing `Future<Output = ()>`.
+    ///
+    /// # Panics
+    /// Panics if a time given to [`Job::at`](crate::Job::at) couldn't be parsed. So do the other methods
+    /// that specify a task, such as [`AsyncJob::run_fallible`].
     pub fn run<F, T>(&mut self, f: F) -> &mut Self
     where
         F: 'static + FnMut() -> T + Send,
@@ -111,14 +176,134 @@ where
         self
     }
 
+    /// Like [`AsyncJob::run`], for a task that already returns a boxed and pinned future,
+    /// such as a function returning `Pin<Box<dyn Future<Output = ()> + Send>>`.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use std::pin::Pin;
+    /// # use std::sync::atomic::{AtomicU32, Ordering};
+    /// # use std::sync::Arc;
+    /// fn check_feeds(checked: Arc<AtomicU32>) -> Pin<JobFuture> {
+    ///     Box::pin(async move {
+    ///         checked.fetch_add(1, Ordering::SeqCst);
+    ///     })
+    /// }
+    ///
+    /// let checked = Arc::new(AtomicU32::new(0));
+    /// let mut scheduler = AsyncScheduler::new();
+    /// let counter = checked.clone();
+    /// scheduler
+    ///     .every(10.minutes())
+    ///     .run_immediately()
+    ///     .run_boxed(move || check_feeds(counter.clone()));
+    /// # tokio_test::block_on(async {
+    /// scheduler.run_pending().await;
+    /// assert_eq!(checked.load(Ordering::SeqCst), 1);
+    /// # });
+    /// ```
+    pub fn run_boxed<F>(&mut self, f: F) -> &mut Self
+    where
+        F: 'static + FnMut() -> Pin<JobFuture> + Send,
+    {
+        self.job = Some(Box::new(BoxedJobWrapper { f }));
+        self.schedule.start_schedule();
+        self
+    }
+
+    /// Specify a task that can fail, and schedule its next run. The function passed into this method
+    /// should return a value implementing `Future<Output = Result<(), E>>`. Failed runs are counted towards
+    /// [`Job::disable_after_failures`] once their future completes, and logged if the `tracing` feature is enabled.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # async fn fetch_feed() -> Result<(), std::io::Error> { Ok(()) }
+    /// let mut scheduler = AsyncScheduler::new();
+    /// scheduler
+    ///     .every(10.minutes())
+    ///     .disable_after_failures(5)
+    ///     .run_fallible(fetch_feed);
+    /// ```
+    pub fn run_fallible<F, T, E>(&mut self, f: F) -> &mut Self
+    where
+        F: 'static + FnMut() -> T + Send,
+        T: 'static + Future<Output = Result<(), E>> + Send,
+        E: 'static + Into<JobError>,
+    {
+        let failures = self.schedule.failures();
+        self.job = Some(Box::new(FallibleJobWrapper { f, failures }));
+        self.schedule.start_schedule();
+        self
+    }
+
     /// Run a task and re-schedule it. This is usually only called by
     /// [AsyncScheduler::run_pending()](crate::AsyncScheduler::run_pending).
     pub fn execute(&mut self, now: &DateTime<Tz>) -> Option<Pin<JobFuture>> {
+        let future = self.execute_fallible(now)?;
+        Some(Box::pin(async move {
+            // Failures have already been counted, and logged if tracing is enabled
+            let _ = future.await;
+        }))
+    }
+
+    /// Like [`AsyncJob::execute`], but the future gives the error from tasks started with
+    /// [`AsyncJob::run_fallible`] that fail.
+    pub(crate) fn execute_fallible(&mut self, now: &DateTime<Tz>) -> Option<FallibleJobFuture> {
+        // Runs that failed since the last time are only known about now
+        self.schedule.disable_if_failing();
         // Don't do anything if we're run out of runs
         if !self.schedule.can_run_again() {
             return None;
         }
+        if self.schedule.skip_if_late(now) {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(label = self.schedule.get_label(), "skipped late run");
+            return None;
+        }
+        if self.schedule.skip_if_over_daily_limit(now) {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                label = self.schedule.get_label(),
+                "skipped run over daily limit"
+            );
+            return None;
+        }
+        if self.schedule.skip_if_condition_unmet(now) {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                label = self.schedule.get_label(),
+                "skipped run as its condition didn't hold"
+            );
+            return None;
+        }
+        if self.schedule.skip_by_chance(now) {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(label = self.schedule.get_label(), "skipped run by chance");
+            return None;
+        }
         let rv = self.job.as_mut().map(|f| f.get_pinned());
+        let last_success = self.schedule.last_success_millis();
+        let started = now.timestamp_millis();
+        let rv = rv.map(|fut| {
+            let fut: FallibleJobFuture = Box::pin(async move {
+                let result = fut.await;
+                if result.is_ok() {
+                    record_success(&last_success, started);
+                }
+                result
+            });
+            fut
+        });
+        #[cfg(feature = "tracing")]
+        let rv = rv.map(|fut| {
+            use tracing::Instrument;
+            let span = tracing::info_span!("job", label = self.schedule.get_label());
+            if self.schedule.is_overtaken(now) {
+                span.in_scope(|| tracing::warn!("late run"));
+            }
+            let fut: FallibleJobFuture =
+                Box::pin(crate::tracing_support::WarnOnPanic(fut).instrument(span));
+            fut
+        });
+        self.schedule.call_on_nth();
         self.schedule.schedule_next(now);
         rv
     }
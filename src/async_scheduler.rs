@@ -1,11 +1,24 @@
-use std::{future::Future, marker::PhantomData, pin::Pin, task::Poll};
+use std::{
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{Poll, Waker},
+    thread,
+    time::Duration,
+};
 
 use crate::AsyncJob;
 use crate::Interval;
 use crate::{
-    async_job::JobFuture,
+    async_job::{FallibleJobFuture, JobFuture},
+    error::JobError,
+    job_schedule::{by_priority, for_each_in_group, remove_group, JobSchedule},
     timeprovider::{ChronoTimeProvider, TimeProvider},
-    Job,
+    Job, JobId,
 };
 
 /// An asynchronous job scheduler, for use with `Future`s.
@@ -82,7 +95,7 @@ use crate::{
 /// scheduler
 ///     .every(1.day())
 ///         .at("3:20 pm")
-///     .run(returns_pinned_boxed_future).once();
+///     .run_boxed(returns_pinned_boxed_future).once();
 /// # tokio_test::block_on(async move {
 /// // Manually run the scheduler forever
 /// loop {
@@ -108,6 +121,7 @@ where
 {
     jobs: Vec<AsyncJob<Tz, Tp>>,
     tz: Tz,
+    in_flight: Arc<Mutex<InFlight>>,
     _tp: PhantomData<Tp>,
 }
 
@@ -116,6 +130,7 @@ impl Default for AsyncScheduler {
         AsyncScheduler::<chrono::Local> {
             jobs: vec![],
             tz: chrono::Local,
+            in_flight: Default::default(),
             _tp: PhantomData,
         }
     }
@@ -132,6 +147,7 @@ impl AsyncScheduler {
         AsyncScheduler {
             jobs: vec![],
             tz,
+            in_flight: Default::default(),
             _tp: PhantomData,
         }
     }
@@ -145,6 +161,7 @@ impl AsyncScheduler {
         AsyncScheduler {
             jobs: vec![],
             tz,
+            in_flight: Default::default(),
             _tp: PhantomData,
         }
     }
@@ -169,6 +186,7 @@ where
     /// scheduler.every(1.day()).at("3:20 pm").run(|| some_async_fn());
     /// scheduler.every(Wednesday).at("14:20:17").run(|| Pin::from(returns_boxed_future()));
     /// scheduler.every(Weekday).run(|| returns_pinned_boxed_future());
+    /// scheduler.every(Weekday).at("9:00").run_boxed(returns_pinned_boxed_future);
     /// ```
     pub fn every(&mut self, ival: Interval) -> &mut AsyncJob<Tz, Tp> {
         let job = AsyncJob::<Tz, Tp>::new(ival, self.tz.clone());
@@ -177,6 +195,88 @@ where
         &mut self.jobs[last_index]
     }
 
+    /// Add a job that runs `f` once, the next time the scheduler is polled, e.g. for a startup task.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = AsyncScheduler::new();
+    /// scheduler.run_once_now(|| async { println!("Starting up") });
+    /// ```
+    /// This is the same as `scheduler.every(1.day()).run_immediately().once().run(f)`.
+    pub fn run_once_now<F, T>(&mut self, f: F) -> &mut AsyncJob<Tz, Tp>
+    where
+        F: 'static + FnMut() -> T + Send,
+        T: 'static + Future<Output = ()> + Send,
+    {
+        self.every(Interval::Days(1))
+            .run_immediately()
+            .once()
+            .run(f)
+    }
+
+    /// Add a new job that runs every other day, counting from today. See [`Job::every_other_day`].
+    pub fn every_other_day(&mut self) -> &mut AsyncJob<Tz, Tp> {
+        self.every(Interval::Days(2)).every_other_day()
+    }
+
+    /// Add a new job that runs on `day` every other week, counting from the next `day`.
+    /// See [`Job::every_other_week_on`].
+    pub fn every_other_week_on(&mut self, day: chrono::Weekday) -> &mut AsyncJob<Tz, Tp> {
+        self.every(Interval::Weeks(2)).every_other_week_on(day)
+    }
+
+    /// Add a new job that runs every `n` seconds, the same as `every(n.seconds())` without needing [`TimeUnits`](crate::TimeUnits).
+    pub fn every_seconds(&mut self, n: u32) -> &mut AsyncJob<Tz, Tp> {
+        self.every(Interval::Seconds(n))
+    }
+
+    /// Add a new job that runs every `n` minutes, the same as `every(n.minutes())`.
+    pub fn every_minutes(&mut self, n: u32) -> &mut AsyncJob<Tz, Tp> {
+        self.every(Interval::Minutes(n))
+    }
+
+    /// Add a new job that runs every `n` hours, the same as `every(n.hours())`.
+    pub fn every_hours(&mut self, n: u32) -> &mut AsyncJob<Tz, Tp> {
+        self.every(Interval::Hours(n))
+    }
+
+    /// Add a new job that runs every `n` days, the same as `every(n.days())`.
+    pub fn every_days(&mut self, n: u32) -> &mut AsyncJob<Tz, Tp> {
+        self.every(Interval::Days(n))
+    }
+
+    /// Add a new job that runs every `n` weeks, the same as `every(n.weeks())`.
+    pub fn every_weeks(&mut self, n: u32) -> &mut AsyncJob<Tz, Tp> {
+        self.every(Interval::Weeks(n))
+    }
+
+    /// Add a new job that runs every day at `time`, the same as `every(1.day()).at(time)`.
+    /// As with [`Job::at`], an unparseable time panics when the task is given, e.g. with [`AsyncJob::run`].
+    pub fn every_day_at(&mut self, time: &str) -> &mut AsyncJob<Tz, Tp> {
+        self.every(Interval::Days(1)).at(time)
+    }
+
+    /// Add a new job that runs on `ival` at `time`, the same as `every(ival).at_time(time)`.
+    pub fn every_at(&mut self, ival: Interval, time: chrono::NaiveTime) -> &mut AsyncJob<Tz, Tp> {
+        self.every(ival).at_time(time)
+    }
+
+    /// Pause every job in the group `name` (see [`Job::group`]), returning how many jobs were paused.
+    /// Paused jobs don't run until resumed with [`AsyncScheduler::resume_group`].
+    pub fn pause_group(&mut self, name: &str) -> usize {
+        for_each_in_group(&mut self.jobs, name, JobSchedule::pause)
+    }
+
+    /// Resume every job in the group `name`, returning how many jobs are in the group.
+    /// Runs that were missed while the jobs were paused are skipped, rather than run late.
+    pub fn resume_group(&mut self, name: &str) -> usize {
+        for_each_in_group(&mut self.jobs, name, JobSchedule::resume)
+    }
+
+    /// Remove every job in the group `name` from the scheduler, returning how many jobs were removed.
+    pub fn remove_group(&mut self, name: &str) -> usize {
+        remove_group(&mut self.jobs, name)
+    }
+
     /// Run all jobs that should run at this time.
     ///
     /// This method returns a future that will poll each of the tasks until they are completed.
@@ -216,42 +316,628 @@ where
     /// });
     /// ```
     pub fn run_pending(&mut self) -> AsyncSchedulerFuture {
+        let futures = self
+            .drain_pending()
+            .into_iter()
+            .map(|(_, future)| future)
+            .collect();
+        AsyncSchedulerFuture { futures }
+    }
+
+    /// Start all jobs that should run at this time, and return their futures without polling them,
+    /// along with the ID of the job each came from. This is an alternative to [`AsyncScheduler::run_pending`]
+    /// for when you want to run each future yourself, e.g. by spawning it on your own executor.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # tokio_test::block_on(async {
+    /// let mut scheduler = AsyncScheduler::new();
+    /// scheduler.every(1.minute()).run_immediately().run(|| async { println!("Periodic task") });
+    /// for (id, future) in scheduler.drain_pending() {
+    ///     println!("Starting job {:?}", id);
+    ///     tokio::spawn(future);
+    /// }
+    /// # });
+    /// ```
+    /// The jobs are considered to have run, and are rescheduled, even if the futures are never polled.
+    /// [`AsyncScheduler::shutdown`] waits for these futures in the same way as those from `run_pending`.
+    pub fn drain_pending(&mut self) -> Vec<(JobId, Pin<JobFuture>)> {
+        self.drain_pending_fallible()
+            .into_iter()
+            .map(|(id, future)| {
+                let future = Box::pin(async move {
+                    let _ = future.await;
+                }) as Pin<JobFuture>;
+                (id, future)
+            })
+            .collect()
+    }
+
+    /// Run all jobs that should run at this time, like [`AsyncScheduler::run_pending`], but the returned
+    /// future gives the errors from jobs started with [`AsyncJob::run_fallible`] that failed,
+    /// along with the ID of each job.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # async fn fetch_feed() -> Result<(), std::io::Error> { Ok(()) }
+    /// # tokio_test::block_on(async {
+    /// let mut scheduler = AsyncScheduler::new();
+    /// scheduler.every(10.minutes()).run_immediately().run_fallible(fetch_feed);
+    /// for (id, error) in scheduler.run_pending_results().await {
+    ///     eprintln!("Job {:?} failed: {}", id, error);
+    /// }
+    /// # });
+    /// ```
+    /// The errors are in the order the jobs finished. Failures are still counted towards
+    /// [`Job::disable_after_failures`], as with `run_pending`.
+    pub fn run_pending_results(&mut self) -> AsyncSchedulerResultsFuture {
+        AsyncSchedulerResultsFuture {
+            futures: self.drain_pending_fallible(),
+            errors: vec![],
+        }
+    }
+
+    /// Start all jobs that should run at this time, as for [`AsyncScheduler::drain_pending`],
+    /// keeping the result of each run.
+    fn drain_pending_fallible(&mut self) -> Vec<(JobId, FallibleJobFuture)> {
         let now = Tp::now(&self.tz);
         let mut futures = vec![];
-        for job in &mut self.jobs {
-            if job.is_pending(&now) {
-                if let Some(future) = job.execute(&now) {
-                    futures.push(Some(future.into()));
-                }
+        for job in by_priority(&mut self.jobs) {
+            // Jobs replaying missed runs with `CatchUp::RunAll` can be due again straight away
+            while job.is_pending(&now) {
+                let future = match job.execute_fallible(&now) {
+                    Some(future) => future,
+                    None => break,
+                };
+                let guard = InFlightGuard::new(&self.in_flight);
+                let future = Box::pin(async move {
+                    let result = future.await;
+                    drop(guard);
+                    result
+                }) as FallibleJobFuture;
+                futures.push((job.id(), future));
             }
         }
-        AsyncSchedulerFuture { futures }
+        futures
+    }
+
+    /// Shut down the scheduler, returning a future that completes once every job future
+    /// started by [`AsyncScheduler::run_pending`] has finished.
+    ///
+    /// This consumes the scheduler, so no new runs will be started. Futures returned by
+    /// `run_pending` still need to be driven to completion by whoever holds them (e.g. a task
+    /// they were spawned onto); shutting down only waits for them.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use std::time::Duration;
+    /// # let mut scheduler = AsyncScheduler::new();
+    /// # tokio_test::block_on(async move {
+    /// tokio::spawn(scheduler.run_pending());
+    /// scheduler.shutdown(Some(Duration::from_secs(30))).await;
+    /// # });
+    /// ```
+    /// If a job's future never completes, neither will the future returned by this method, unless a
+    /// `timeout` is given. In that case, it completes after at most `timeout`, even if some job
+    /// futures are still running. Those futures are not cancelled.
+    pub fn shutdown(self, timeout: Option<Duration>) -> ShutdownFuture {
+        ShutdownFuture {
+            in_flight: self.in_flight,
+            timeout,
+            timed_out: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct InFlight {
+    count: usize,
+    waiting: Vec<Waker>,
+}
+
+/// Counts a job future as in-flight for as long as it's alive.
+struct InFlightGuard(Arc<Mutex<InFlight>>);
+
+impl InFlightGuard {
+    fn new(in_flight: &Arc<Mutex<InFlight>>) -> Self {
+        in_flight.lock().unwrap().count += 1;
+        InFlightGuard(in_flight.clone())
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        let mut in_flight = self.0.lock().unwrap();
+        in_flight.count -= 1;
+        if in_flight.count == 0 {
+            for waker in in_flight.waiting.drain(..) {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// Future returned by [`AsyncScheduler::shutdown`]
+pub struct ShutdownFuture {
+    in_flight: Arc<Mutex<InFlight>>,
+    timeout: Option<Duration>,
+    timed_out: Arc<AtomicBool>,
+}
+
+impl Future for ShutdownFuture {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if this.timed_out.load(Ordering::SeqCst) {
+            return Poll::Ready(());
+        }
+        let mut in_flight = this.in_flight.lock().unwrap();
+        if in_flight.count == 0 {
+            return Poll::Ready(());
+        }
+        if !in_flight.waiting.iter().any(|w| w.will_wake(cx.waker())) {
+            in_flight.waiting.push(cx.waker().clone());
+        }
+        // We don't want to depend on a specific runtime's timer, so use a thread to time out
+        if let Some(timeout) = this.timeout.take() {
+            let in_flight = this.in_flight.clone();
+            let timed_out = this.timed_out.clone();
+            thread::spawn(move || {
+                thread::sleep(timeout);
+                timed_out.store(true, Ordering::SeqCst);
+                for waker in in_flight.lock().unwrap().waiting.drain(..) {
+                    waker.wake();
+                }
+            });
+        }
+        Poll::Pending
     }
 }
 
 pub struct AsyncSchedulerFuture {
-    futures: Vec<Option<Pin<JobFuture>>>,
+    futures: Vec<Pin<JobFuture>>,
 }
 
 impl Future for AsyncSchedulerFuture {
     type Output = ();
 
     fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
-        let mut all_done = true;
-
-        for future in &mut self.get_mut().futures {
-            if let Some(this_future) = future {
-                if this_future.as_mut().poll(cx) == Poll::Ready(()) {
-                    future.take();
-                } else {
-                    all_done = false;
-                }
+        let futures = &mut self.get_mut().futures;
+        // Drop each future as soon as it completes, rather than holding on to it until the whole batch is done
+        futures.retain_mut(|future| future.as_mut().poll(cx) == Poll::Pending);
+        if futures.is_empty() {
+            Poll::Ready(())
+        } else {
+            // Don't keep space for a large batch when only a few stragglers are left
+            if futures.len() * 4 < futures.capacity() {
+                futures.shrink_to_fit();
             }
+            Poll::Pending
         }
-        if all_done {
-            Poll::Ready(())
+    }
+}
+
+/// Future returned by [`AsyncScheduler::run_pending_results`]
+pub struct AsyncSchedulerResultsFuture {
+    futures: Vec<(JobId, FallibleJobFuture)>,
+    errors: Vec<(JobId, JobError)>,
+}
+
+impl Future for AsyncSchedulerResultsFuture {
+    type Output = Vec<(JobId, JobError)>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let errors = &mut this.errors;
+        this.futures
+            .retain_mut(|(id, future)| match future.as_mut().poll(cx) {
+                Poll::Ready(Ok(())) => false,
+                Poll::Ready(Err(e)) => {
+                    errors.push((*id, e));
+                    false
+                }
+                Poll::Pending => true,
+            });
+        if this.futures.is_empty() {
+            Poll::Ready(std::mem::take(&mut this.errors))
         } else {
             Poll::Pending
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::AsyncScheduler;
+    use crate::timeprovider::{lock_mock_clock, MockClock};
+    use crate::TimeUnits;
+    use chrono::{TimeZone, Utc};
+    use std::sync::{
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        Arc,
+    };
+    use std::task::Poll;
+    use std::time::Duration;
+
+    fn sleepy_scheduler(
+        sleep: Duration,
+        finished: Arc<AtomicU32>,
+    ) -> AsyncScheduler<Utc, MockClock> {
+        MockClock::set(Utc.with_ymd_and_hms(2021, 5, 12, 12, 0, 0).unwrap());
+        let mut scheduler = AsyncScheduler::with_tz_and_provider::<Utc, MockClock>(Utc);
+        scheduler.every(1.second()).run(move || {
+            let finished = finished.clone();
+            async move {
+                tokio::time::sleep(sleep).await;
+                finished.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+        MockClock::advance(Duration::from_secs(1));
+        scheduler
+    }
+
+    #[test]
+    fn test_shutdown_waits_for_futures() {
+        let _guard = lock_mock_clock();
+        let finished = Arc::new(AtomicU32::new(0));
+        let mut scheduler = sleepy_scheduler(Duration::from_millis(50), finished.clone());
+        tokio_test::block_on(async move {
+            tokio::spawn(scheduler.run_pending());
+            scheduler.shutdown(None).await;
+        });
+        assert_eq!(1, finished.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_shutdown_timeout() {
+        let _guard = lock_mock_clock();
+        let finished = Arc::new(AtomicU32::new(0));
+        let mut scheduler = sleepy_scheduler(Duration::from_secs(60), finished.clone());
+        tokio_test::block_on(async move {
+            tokio::spawn(scheduler.run_pending());
+            scheduler.shutdown(Some(Duration::from_millis(50))).await;
+        });
+        assert_eq!(0, finished.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_shutdown_when_idle() {
+        let _guard = lock_mock_clock();
+        let finished = Arc::new(AtomicU32::new(0));
+        let mut scheduler = sleepy_scheduler(Duration::from_millis(1), finished.clone());
+        tokio_test::block_on(async move {
+            scheduler.run_pending().await;
+            scheduler.shutdown(None).await;
+        });
+        assert_eq!(1, finished.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_on_finished() {
+        use crate::Job;
+        let _guard = lock_mock_clock();
+        MockClock::set(Utc.with_ymd_and_hms(2021, 5, 12, 12, 0, 0).unwrap());
+        let finished = Arc::new(AtomicU32::new(0));
+        let mut scheduler = AsyncScheduler::with_tz_and_provider::<Utc, MockClock>(Utc);
+        {
+            let finished = finished.clone();
+            scheduler
+                .every(1.second())
+                .once()
+                .on_finished(move || {
+                    finished.fetch_add(1, Ordering::SeqCst);
+                })
+                .run(|| async {});
+        }
+        MockClock::advance(Duration::from_secs(1));
+        tokio_test::block_on(scheduler.run_pending());
+        assert_eq!(1, finished.load(Ordering::SeqCst));
+        MockClock::advance(Duration::from_secs(1));
+        tokio_test::block_on(scheduler.run_pending());
+        assert_eq!(1, finished.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_on_nth() {
+        use crate::Job;
+        let _guard = lock_mock_clock();
+        MockClock::set(Utc.with_ymd_and_hms(2021, 5, 12, 12, 0, 0).unwrap());
+        let times_called = Arc::new(AtomicU32::new(0));
+        let extra_called = Arc::new(AtomicU32::new(0));
+        let mut scheduler = AsyncScheduler::with_tz_and_provider::<Utc, MockClock>(Utc);
+        {
+            let times_called = times_called.clone();
+            let extra_called = extra_called.clone();
+            scheduler
+                .every(1.minute())
+                .on_nth(3, move || {
+                    extra_called.fetch_add(1, Ordering::SeqCst);
+                })
+                .run(move || {
+                    let times_called = times_called.clone();
+                    async move {
+                        times_called.fetch_add(1, Ordering::SeqCst);
+                    }
+                });
+        }
+        for i in 1..=7 {
+            MockClock::advance(Duration::from_secs(60));
+            tokio_test::block_on(scheduler.run_pending());
+            assert_eq!(i, times_called.load(Ordering::SeqCst));
+            assert_eq!(i / 3, extra_called.load(Ordering::SeqCst));
+        }
+    }
+
+    #[test]
+    fn test_groups() {
+        use crate::Job;
+        let _guard = lock_mock_clock();
+        MockClock::set(Utc.with_ymd_and_hms(2021, 5, 12, 12, 0, 0).unwrap());
+        let reports = Arc::new(AtomicU32::new(0));
+        let other = Arc::new(AtomicU32::new(0));
+        let mut scheduler = AsyncScheduler::with_tz_and_provider::<Utc, MockClock>(Utc);
+        for _ in 0..2 {
+            let reports = reports.clone();
+            scheduler
+                .every(10.minutes())
+                .group("reporting")
+                .run(move || {
+                    let reports = reports.clone();
+                    async move {
+                        reports.fetch_add(1, Ordering::SeqCst);
+                    }
+                });
+        }
+        {
+            let other = other.clone();
+            scheduler.every(10.minutes()).run(move || {
+                let other = other.clone();
+                async move {
+                    other.fetch_add(1, Ordering::SeqCst);
+                }
+            });
+        }
+
+        assert_eq!(scheduler.pause_group("reporting"), 2);
+        assert_eq!(scheduler.pause_group("no such group"), 0);
+        MockClock::advance(Duration::from_secs(10 * 60));
+        tokio_test::block_on(scheduler.run_pending());
+        assert_eq!(0, reports.load(Ordering::SeqCst));
+        assert_eq!(1, other.load(Ordering::SeqCst));
+
+        // The 12:20 run was missed while paused, so isn't run on resuming
+        MockClock::advance(Duration::from_secs(5 * 60));
+        assert_eq!(scheduler.resume_group("reporting"), 2);
+        tokio_test::block_on(scheduler.run_pending());
+        assert_eq!(0, reports.load(Ordering::SeqCst));
+        MockClock::advance(Duration::from_secs(5 * 60));
+        tokio_test::block_on(scheduler.run_pending());
+        assert_eq!(2, reports.load(Ordering::SeqCst));
+
+        assert_eq!(scheduler.remove_group("reporting"), 2);
+        assert_eq!(scheduler.remove_group("reporting"), 0);
+        MockClock::advance(Duration::from_secs(10 * 60));
+        tokio_test::block_on(scheduler.run_pending());
+        assert_eq!(2, reports.load(Ordering::SeqCst));
+        assert_eq!(3, other.load(Ordering::SeqCst));
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_tracing() {
+        use crate::tracing_support::Recorder;
+        use crate::Job;
+        use std::panic::{self, AssertUnwindSafe};
+        let _guard = lock_mock_clock();
+        MockClock::set(Utc.with_ymd_and_hms(2021, 5, 12, 12, 0, 0).unwrap());
+        let recorder = Arc::new(Recorder::default());
+        let mut scheduler = AsyncScheduler::with_tz_and_provider::<Utc, MockClock>(Utc);
+        scheduler.every(1.minute()).label("tick").run(|| async {});
+        // The 12:01 run only starts after the 12:02 one was due
+        MockClock::advance(Duration::from_secs(150));
+        tracing::subscriber::with_default(recorder.clone(), || {
+            tokio_test::block_on(scheduler.run_pending());
+        });
+        assert_eq!(recorder.spans.lock().unwrap().len(), 1);
+        assert_eq!(*recorder.events.lock().unwrap(), vec!["WARN".to_string()]);
+
+        // Futures that panic are warned about, though they're only dropped after the panic
+        let mut scheduler = AsyncScheduler::with_tz_and_provider::<Utc, MockClock>(Utc);
+        scheduler
+            .every(1.minute())
+            .run(|| async { panic!("Oh no") });
+        MockClock::advance(Duration::from_secs(60));
+        tracing::subscriber::with_default(recorder.clone(), || {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                tokio_test::block_on(scheduler.run_pending());
+            }));
+            assert!(result.is_err());
+        });
+        assert_eq!(recorder.spans.lock().unwrap().len(), 2);
+        assert_eq!(recorder.events.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_run_once_now() {
+        let _guard = lock_mock_clock();
+        MockClock::set(Utc.with_ymd_and_hms(2021, 5, 12, 12, 0, 0).unwrap());
+        let times_called = Arc::new(AtomicU32::new(0));
+        let mut scheduler = AsyncScheduler::with_tz_and_provider::<Utc, MockClock>(Utc);
+        {
+            let times_called = times_called.clone();
+            scheduler.run_once_now(move || {
+                let times_called = times_called.clone();
+                async move {
+                    times_called.fetch_add(1, Ordering::SeqCst);
+                }
+            });
+        }
+        tokio_test::block_on(scheduler.run_pending());
+        assert_eq!(1, times_called.load(Ordering::SeqCst));
+        MockClock::advance(Duration::from_secs(86400));
+        tokio_test::block_on(scheduler.run_pending());
+        assert_eq!(1, times_called.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_drain_pending() {
+        use crate::Job;
+        let _guard = lock_mock_clock();
+        MockClock::set(Utc.with_ymd_and_hms(2021, 5, 12, 12, 0, 0).unwrap());
+        let finished = Arc::new(AtomicU32::new(0));
+        let mut scheduler = AsyncScheduler::with_tz_and_provider::<Utc, MockClock>(Utc);
+        let mut ids = vec![];
+        for interval in [1.second(), 1.hour(), 2.seconds()] {
+            let finished = finished.clone();
+            let id = scheduler
+                .every(interval)
+                .run(move || {
+                    let finished = finished.clone();
+                    async move {
+                        finished.fetch_add(1, Ordering::SeqCst);
+                    }
+                })
+                .id();
+            ids.push(id);
+        }
+        MockClock::advance(Duration::from_secs(2));
+        let pending = scheduler.drain_pending();
+        let pending_ids: Vec<_> = pending.iter().map(|(id, _)| *id).collect();
+        assert_eq!(pending_ids, vec![ids[0], ids[2]]);
+        // Nothing runs until the futures are polled
+        assert_eq!(0, finished.load(Ordering::SeqCst));
+        assert!(scheduler.drain_pending().is_empty());
+        tokio_test::block_on(async move {
+            for (_, future) in pending {
+                future.await;
+            }
+            scheduler.shutdown(None).await;
+        });
+        assert_eq!(2, finished.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_completed_futures_dropped() {
+        let _guard = lock_mock_clock();
+        MockClock::set(Utc.with_ymd_and_hms(2021, 5, 12, 12, 0, 0).unwrap());
+        let mut scheduler = AsyncScheduler::with_tz_and_provider::<Utc, MockClock>(Utc);
+        let release = Arc::new(AtomicBool::new(false));
+        {
+            let release = release.clone();
+            scheduler.every(1.second()).run(move || {
+                let release = release.clone();
+                std::future::poll_fn(move |cx| {
+                    if release.load(Ordering::SeqCst) {
+                        Poll::Ready(())
+                    } else {
+                        cx.waker().wake_by_ref();
+                        Poll::Pending
+                    }
+                })
+            });
+        }
+        for _ in 0..99 {
+            scheduler.every(1.second()).run(|| async {});
+        }
+        MockClock::advance(Duration::from_secs(1));
+        let mut future = tokio_test::task::spawn(scheduler.run_pending());
+        assert_eq!(future.futures.len(), 100);
+        assert!(future.poll().is_pending());
+        // Only the unfinished future is left
+        assert_eq!(future.futures.len(), 1);
+        assert!(future.futures.capacity() < 100);
+        release.store(true, Ordering::SeqCst);
+        assert!(future.poll().is_ready());
+        assert!(future.futures.is_empty());
+    }
+
+    #[test]
+    fn test_run_pending_results() {
+        use crate::Job;
+        let _guard = lock_mock_clock();
+        MockClock::set(Utc.with_ymd_and_hms(2021, 5, 12, 12, 0, 0).unwrap());
+        let mut scheduler = AsyncScheduler::with_tz_and_provider::<Utc, MockClock>(Utc);
+        let finished = Arc::new(AtomicU32::new(0));
+        {
+            let finished = finished.clone();
+            scheduler.every(1.second()).run(move || {
+                let finished = finished.clone();
+                async move {
+                    finished.fetch_add(1, Ordering::SeqCst);
+                }
+            });
+        }
+        let succeeds = scheduler
+            .every(1.second())
+            .run_fallible(|| async { Ok::<(), &str>(()) })
+            .id();
+        let fails = scheduler
+            .every(1.second())
+            .run_fallible(|| async {
+                tokio::task::yield_now().await;
+                Err::<(), _>("Oops")
+            })
+            .id();
+        let fails_twice = scheduler
+            .every(2.seconds())
+            .run_fallible(|| async { Err::<(), _>(std::io::Error::other("Broken pipe")) })
+            .id();
+
+        MockClock::advance(Duration::from_secs(1));
+        let errors = tokio_test::block_on(scheduler.run_pending_results());
+        let errors: Vec<_> = errors.iter().map(|(id, e)| (*id, e.to_string())).collect();
+        assert_eq!(errors, vec![(fails, "Oops".to_owned())]);
+        assert_eq!(1, finished.load(Ordering::SeqCst));
+
+        // Errors are listed in the order the jobs finished
+        MockClock::advance(Duration::from_secs(1));
+        let errors = tokio_test::block_on(scheduler.run_pending_results());
+        let errors: Vec<_> = errors.iter().map(|(id, e)| (*id, e.to_string())).collect();
+        assert_eq!(
+            errors,
+            vec![
+                (fails_twice, "Broken pipe".to_owned()),
+                (fails, "Oops".to_owned())
+            ]
+        );
+        assert!(!errors.iter().any(|(id, _)| *id == succeeds));
+        assert_eq!(2, finished.load(Ordering::SeqCst));
+
+        MockClock::advance(Duration::from_millis(500));
+        assert!(tokio_test::block_on(scheduler.run_pending_results()).is_empty());
+    }
+
+    #[test]
+    fn test_disable_after_failures() {
+        use crate::Job;
+        let _guard = lock_mock_clock();
+        MockClock::set(Utc.with_ymd_and_hms(2021, 5, 12, 12, 0, 0).unwrap());
+        let mut scheduler = AsyncScheduler::with_tz_and_provider::<Utc, MockClock>(Utc);
+        let times_called = Arc::new(AtomicU32::new(0));
+        let disabled = Arc::new(AtomicU32::new(0));
+        {
+            let times_called = times_called.clone();
+            let disabled = disabled.clone();
+            scheduler
+                .every(1.second())
+                .disable_after_failures(2)
+                .on_disabled(move || {
+                    disabled.fetch_add(1, Ordering::SeqCst);
+                })
+                .run_fallible(move || {
+                    let times_called = times_called.clone();
+                    async move {
+                        times_called.fetch_add(1, Ordering::SeqCst);
+                        Err::<(), _>("Oops")
+                    }
+                });
+        }
+        tokio_test::block_on(async {
+            for _ in 0..4 {
+                MockClock::advance(Duration::from_secs(1));
+                scheduler.run_pending().await;
+            }
+        });
+        // Noticed when the job was next due after the second failure
+        assert_eq!(2, times_called.load(Ordering::SeqCst));
+        assert_eq!(1, disabled.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,245 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::Interval;
+
+/// The error returned by [`Job::try_at`](crate::Job::try_at) when a time string could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AtError {
+    input: String,
+    source: chrono::ParseError,
+}
+
+impl AtError {
+    #[cfg(feature = "std")]
+    pub(crate) fn new(input: &str, source: chrono::ParseError) -> Self {
+        AtError {
+            input: input.into(),
+            source,
+        }
+    }
+
+    /// The string that could not be parsed as a time.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+}
+
+impl fmt::Display for AtError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Could not convert {:?} into a time: {}",
+            self.input, self.source
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for AtError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Something that stops a job's schedule from being built, listed by [`ScheduleError::errors`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BuildError {
+    /// A time passed to [`Job::at`](crate::Job::at) could not be parsed.
+    At(AtError),
+    /// The phase passed to [`Job::every_with_phase`](crate::Job::every_with_phase) isn't a fixed-length
+    /// interval shorter than the interval.
+    Phase {
+        /// The interval
+        interval: Interval,
+        /// The phase
+        phase: Interval,
+    },
+}
+
+impl From<AtError> for BuildError {
+    fn from(e: AtError) -> Self {
+        BuildError::At(e)
+    }
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::At(e) => e.fmt(f),
+            BuildError::Phase { interval, phase } => write!(
+                f,
+                "The phase must be a fixed-length interval shorter than the interval, not {:?} for {:?}",
+                phase, interval
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for BuildError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BuildError::At(e) => Some(e),
+            BuildError::Phase { .. } => None,
+        }
+    }
+}
+
+/// A potential problem with a job's schedule, as reported by [`Job::validate`](crate::Job::validate).
+///
+/// These describe schedules that are permitted, but probably don't do what was intended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ScheduleWarning {
+    /// A day-of-week interval (e.g. `Monday` or `Weekday`) was combined using
+    /// [`Job::and_every`](crate::Job::and_every) with an interval shorter than an hour.
+    /// The shorter interval will always run sooner, so the job effectively ignores the day of the week.
+    WeekdayWithSubHourInterval {
+        /// The day-of-week interval
+        weekday: Interval,
+        /// The sub-hour interval
+        other: Interval,
+    },
+}
+
+impl fmt::Display for ScheduleWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScheduleWarning::WeekdayWithSubHourInterval { weekday, other } => write!(
+                f,
+                "{:?} is combined with {:?}, which will always run first",
+                weekday, other
+            ),
+        }
+    }
+}
+
+/// The error returned when an [`Interval`] could not be constructed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum IntervalError {
+    /// A [`Rate`](crate::Rate) was zero, or doesn't evenly divide its period into whole seconds.
+    InvalidRate {
+        /// The number of times per period
+        times: u32,
+        /// The period, e.g. one day
+        per: Interval,
+    },
+    /// A string could not be parsed as an interval.
+    Unrecognized {
+        /// The string that could not be parsed
+        input: String,
+    },
+    /// The environment variable passed to [`Interval::from_env`] isn't set.
+    MissingVariable {
+        /// The variable's name
+        name: String,
+    },
+}
+
+impl fmt::Display for IntervalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IntervalError::InvalidRate { times, per } => write!(
+                f,
+                "{} times per {:?} is not a whole number of seconds apart",
+                times, per
+            ),
+            IntervalError::Unrecognized { input } => {
+                write!(f, "Could not convert {:?} into an interval", input)
+            }
+            IntervalError::MissingVariable { name } => {
+                write!(f, "The environment variable {} is not set", name)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for IntervalError {}
+
+/// The error returned by [`Scheduler::from_specs`](crate::Scheduler::from_specs) when the specs and closures don't match up,
+/// and by [`Scheduler::every_unique`](crate::Scheduler::every_unique).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SpecError {
+    /// There was no closure with the same label as a spec.
+    MissingClosure {
+        /// The spec's label
+        label: String,
+    },
+    /// More than one spec had the same label.
+    /// Also returned by [`Scheduler::every_unique`](crate::Scheduler::every_unique) if the label is already in use.
+    DuplicateLabel {
+        /// The repeated label
+        label: String,
+    },
+}
+
+impl fmt::Display for SpecError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SpecError::MissingClosure { label } => write!(f, "No closure for job {:?}", label),
+            SpecError::DuplicateLabel { label } => {
+                write!(f, "More than one job is labelled {:?}", label)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for SpecError {}
+
+/// The error returned by [`Job::try_build`](crate::Job::try_build), listing everything wrong with a job's schedule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleError {
+    errors: Vec<BuildError>,
+    warnings: Vec<ScheduleWarning>,
+}
+
+impl ScheduleError {
+    #[cfg(feature = "std")]
+    pub(crate) fn new(errors: Vec<BuildError>, warnings: Vec<ScheduleWarning>) -> Self {
+        ScheduleError { errors, warnings }
+    }
+
+    /// Times passed to [`Job::at`](crate::Job::at) that could not be parsed, and phases passed to
+    /// [`Job::every_with_phase`](crate::Job::every_with_phase) that don't fit their interval.
+    pub fn errors(&self) -> &[BuildError] {
+        &self.errors
+    }
+
+    /// Problems found by [`Job::validate`](crate::Job::validate).
+    pub fn warnings(&self) -> &[ScheduleWarning] {
+        &self.warnings
+    }
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid schedule")?;
+        let problems = self
+            .errors
+            .iter()
+            .map(|e| e as &dyn fmt::Display)
+            .chain(self.warnings.iter().map(|w| w as &dyn fmt::Display));
+        for (i, problem) in problems.enumerate() {
+            let separator = if i == 0 { ": " } else { "; " };
+            write!(f, "{}{}", separator, problem)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ScheduleError {}
+
+/// The error from a failed run of a job started with `run_fallible`, e.g. [`SyncJob::run_fallible`](crate::SyncJob::run_fallible).
+/// Tasks can return any error type that converts into this, such as [`std::io::Error`] or a `String`.
+#[cfg(feature = "std")]
+pub type JobError = alloc::boxed::Box<dyn Error + Send + Sync>;
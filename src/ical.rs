@@ -0,0 +1,109 @@
+//! Helpers for the `ical` feature.
+use std::fmt::Write;
+
+use chrono::{DateTime, Utc};
+
+/// A run to list in a calendar
+pub(crate) struct Event {
+    pub(crate) uid: String,
+    pub(crate) summary: String,
+    pub(crate) start: DateTime<Utc>,
+}
+
+/// Write `events` as an iCalendar (RFC 5545) file, stamped with the time `now`.
+pub(crate) fn calendar(events: &[Event], now: DateTime<Utc>) -> String {
+    let mut ics = String::new();
+    content_line(&mut ics, "BEGIN:VCALENDAR");
+    content_line(&mut ics, "VERSION:2.0");
+    content_line(&mut ics, "PRODID:-//clokwerk//clokwerk//EN");
+    for event in events {
+        content_line(&mut ics, "BEGIN:VEVENT");
+        content_line(&mut ics, &format!("UID:{}", escape(&event.uid)));
+        content_line(&mut ics, &format!("DTSTAMP:{}", timestamp(now)));
+        content_line(&mut ics, &format!("DTSTART:{}", timestamp(event.start)));
+        content_line(&mut ics, &format!("SUMMARY:{}", escape(&event.summary)));
+        content_line(&mut ics, "END:VEVENT");
+    }
+    content_line(&mut ics, "END:VCALENDAR");
+    ics
+}
+
+fn timestamp(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escape special characters in a text value
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            '\r' => (),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Append `line`, folding it so that no line is longer than 75 bytes
+fn content_line(ics: &mut String, line: &str) {
+    const MAX_LINE: usize = 75;
+    let mut limit = MAX_LINE;
+    let mut start = 0;
+    for (i, c) in line.char_indices() {
+        if i + c.len_utf8() - start > limit {
+            // Continuation lines start with a space, which counts towards their length
+            write!(ics, "{}\r\n ", &line[start..i]).unwrap();
+            start = i;
+            limit = MAX_LINE - 1;
+        }
+    }
+    write!(ics, "{}\r\n", &line[start..]).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{calendar, content_line, Event};
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_calendar() {
+        let events = [Event {
+            uid: "1-1571745600@clokwerk".to_owned(),
+            summary: "Back up; then, clean\nup".to_owned(),
+            start: Utc.with_ymd_and_hms(2019, 10, 22, 12, 0, 0).unwrap(),
+        }];
+        let now = Utc.with_ymd_and_hms(2019, 10, 22, 11, 30, 0).unwrap();
+        assert_eq!(
+            calendar(&events, now),
+            concat!(
+                "BEGIN:VCALENDAR\r\n",
+                "VERSION:2.0\r\n",
+                "PRODID:-//clokwerk//clokwerk//EN\r\n",
+                "BEGIN:VEVENT\r\n",
+                "UID:1-1571745600@clokwerk\r\n",
+                "DTSTAMP:20191022T113000Z\r\n",
+                "DTSTART:20191022T120000Z\r\n",
+                "SUMMARY:Back up\\; then\\, clean\\nup\r\n",
+                "END:VEVENT\r\n",
+                "END:VCALENDAR\r\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_folding() {
+        let mut ics = String::new();
+        let line = format!("SUMMARY:{}", "é".repeat(40));
+        content_line(&mut ics, &line);
+        let lines: Vec<_> = ics.split("\r\n").collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|line| line.len() <= 75));
+        assert_eq!(lines[2], "");
+        assert_eq!(lines[0].to_owned() + &lines[1][1..], line);
+    }
+}
@@ -1,18 +1,31 @@
 use chrono::prelude::*;
 use chrono::Duration;
+use chrono::LocalResult;
 use chrono::Weekday;
 
-#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::IntervalError;
+
+/// Intervals are ordered by length, with the fixed-length intervals (`Seconds` to `Weeks`) first,
+/// so `90.seconds() < 2.minutes()`. Equal lengths in different units are ordered by unit, so
+/// `60.seconds() < 1.minute()`. The day-of-week intervals come after all of the fixed-length intervals,
+/// in the order `Monday` to `Sunday`, then `Weekday`, then `DaysOfWeek`, then `MonthDayOrNextWeekday`, then `IsoWeeks`.
+#[derive(Eq, PartialEq, Hash, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Interval {
     /// The next multiple of `n` seconds since the start of the Unix epoch
     Seconds(u32),
-    /// The next multiple of `n` minutes since the start of the day
+    /// The next multiple of `n` minutes since the start of the day, e.g. `Minutes(30)` runs at :00 and :30
+    /// past each hour, whenever it's started
     Minutes(u32),
     /// The next multiple of `n` hours since the start of the day
     Hours(u32),
     /// The next multiple of `n` days since the start of the start of the era
     Days(u32),
-    /// The next multiple of `n` week since the start of the start of the era
+    /// The next multiple of `n` week since the start of the start of the era. See [`Interval::IsoWeeks`]
+    /// for weeks counted from the start of each year instead.
     Weeks(u32),
     /// Every Monday
     Monday,
@@ -30,6 +43,442 @@ pub enum Interval {
     Sunday,
     /// Every weekday (Monday through Friday)
     Weekday,
+    /// Every day in the set, e.g. Monday, Wednesday and Friday
+    DaysOfWeek(WeekdaySet),
+    /// Day `n` of every month, or the Monday after if that falls on a weekend, e.g. for payroll
+    /// that runs "on the 15th, or the next business day". In months shorter than `n` days, the last day of the month is used.
+    MonthDayOrNextWeekday(u32),
+    /// The start (Monday) of every `n`th ISO 8601 week, counting from week 1 of each ISO year, e.g. for
+    /// reports bucketed by ISO week. `IsoWeeks(2)` runs at the start of weeks 1, 3, 5 and so on.
+    ///
+    /// Unlike `Weeks(n)`, which counts weeks from a fixed date, this starts again at week 1 each year.
+    /// Years have either 52 or 53 ISO weeks, so the last run of one year can be fewer than `n` weeks before
+    /// the first run of the next, e.g. `IsoWeeks(2)` runs in both week 53 of 2020 and week 1 of 2021.
+    IsoWeeks(u32),
+}
+
+impl PartialOrd for Interval {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Interval {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+/// Describes the interval in English, e.g. "every 15 minutes" or "every Monday".
+/// ```rust
+/// # use clokwerk::{Interval, TimeUnits};
+/// assert_eq!(15.minutes().to_string(), "every 15 minutes");
+/// assert_eq!(1.hour().to_string(), "every 1 hour");
+/// assert_eq!(Interval::Monday.to_string(), "every Monday");
+/// ```
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (count, unit) = match *self {
+            Seconds(n) => (n, "second"),
+            Minutes(n) => (n, "minute"),
+            Hours(n) => (n, "hour"),
+            Days(n) => (n, "day"),
+            Weeks(n) => (n, "week"),
+            Weekday => return write!(f, "every weekday"),
+            MonthDayOrNextWeekday(n) => {
+                return write!(f, "every month on day {}, or the next weekday", n)
+            }
+            IsoWeeks(n) => {
+                let plural = if n == 1 { "" } else { "s" };
+                return write!(f, "every {} ISO week{}", n, plural);
+            }
+            DaysOfWeek(days) => {
+                let mut days = days.iter().map(weekday_name).peekable();
+                let first = match days.next() {
+                    Some(first) => first,
+                    None => return write!(f, "never"),
+                };
+                write!(f, "every {}", first)?;
+                while let Some(day) = days.next() {
+                    let separator = if days.peek().is_some() { ", " } else { " and " };
+                    write!(f, "{}{}", separator, day)?;
+                }
+                return Ok(());
+            }
+            Monday => return write!(f, "every Monday"),
+            Tuesday => return write!(f, "every Tuesday"),
+            Wednesday => return write!(f, "every Wednesday"),
+            Thursday => return write!(f, "every Thursday"),
+            Friday => return write!(f, "every Friday"),
+            Saturday => return write!(f, "every Saturday"),
+            Sunday => return write!(f, "every Sunday"),
+        };
+        let plural = if count == 1 { "" } else { "s" };
+        write!(f, "every {} {}{}", count, unit, plural)
+    }
+}
+
+fn weekday_name(day: chrono::Weekday) -> &'static str {
+    match day {
+        chrono::Weekday::Mon => "Monday",
+        chrono::Weekday::Tue => "Tuesday",
+        chrono::Weekday::Wed => "Wednesday",
+        chrono::Weekday::Thu => "Thursday",
+        chrono::Weekday::Fri => "Friday",
+        chrono::Weekday::Sat => "Saturday",
+        chrono::Weekday::Sun => "Sunday",
+    }
+}
+
+impl Interval {
+    /// Every day in `days`, at midnight.
+    /// ```rust
+    /// # use clokwerk::Interval;
+    /// # use chrono::Weekday;
+    /// let mwf = Interval::days_of_week(&[Weekday::Mon, Weekday::Wed, Weekday::Fri]);
+    /// ```
+    pub fn days_of_week(days: &[chrono::Weekday]) -> Interval {
+        DaysOfWeek(days.iter().copied().collect())
+    }
+
+    /// Every day of the week with the given index, counting from 0 for Monday to 6 for Sunday,
+    /// as with [`chrono::Weekday::num_days_from_monday`]. Returns `None` for any other index.
+    /// ```rust
+    /// # use clokwerk::Interval;
+    /// assert_eq!(Interval::from_weekday_index(0), Some(Interval::Monday));
+    /// assert_eq!(Interval::from_weekday_index(6), Some(Interval::Sunday));
+    /// assert_eq!(Interval::from_weekday_index(7), None);
+    /// ```
+    pub fn from_weekday_index(index: u8) -> Option<Interval> {
+        Some(match index {
+            0 => Monday,
+            1 => Tuesday,
+            2 => Wednesday,
+            3 => Thursday,
+            4 => Friday,
+            5 => Saturday,
+            6 => Sunday,
+            _ => return None,
+        })
+    }
+
+    /// Every `n` seconds, the same as `n.seconds()` with [`TimeUnits`]. The constructors for each unit
+    /// don't depend on trait methods, which can be easier to use from bindings to other languages.
+    pub fn seconds(n: u32) -> Interval {
+        Seconds(n)
+    }
+
+    /// Every `n` minutes, the same as `n.minutes()` with [`TimeUnits`].
+    pub fn minutes(n: u32) -> Interval {
+        Minutes(n)
+    }
+
+    /// Every `n` hours, the same as `n.hours()` with [`TimeUnits`].
+    pub fn hours(n: u32) -> Interval {
+        Hours(n)
+    }
+
+    /// Every `n` days, the same as `n.days()` with [`TimeUnits`].
+    pub fn days(n: u32) -> Interval {
+        Days(n)
+    }
+
+    /// Every `n` weeks, the same as `n.weeks()` with [`TimeUnits`].
+    pub fn weeks(n: u32) -> Interval {
+        Weeks(n)
+    }
+
+    /// Every `n` ISO weeks, the same as [`Interval::IsoWeeks`].
+    pub fn iso_weeks(n: u32) -> Interval {
+        IsoWeeks(n)
+    }
+
+    /// The same interval in the largest unit that divides it exactly, e.g. `Seconds(3600)` becomes `Hours(1)`
+    /// and `Minutes(120)` becomes `Hours(2)`. Use this before comparing or serializing intervals, so that
+    /// equivalent ones are written the same way.
+    /// ```rust
+    /// # use clokwerk::Interval::*;
+    /// assert_eq!(Seconds(3600).normalized(), Hours(1));
+    /// assert_eq!(Minutes(90).normalized(), Minutes(90));
+    /// assert_eq!(Seconds(86400).normalized(), Days(1));
+    /// ```
+    /// Promoting `Minutes` to `Hours` never changes when a job runs. Otherwise:
+    /// * `Seconds` intervals are counted from the Unix epoch, rather than from midnight. Promoting one only
+    ///   gives the same times if it divides a day evenly, and the time zone's UTC offset is a multiple of it,
+    ///   e.g. for any whole number of hours in UTC.
+    /// * Exactly one day of seconds, minutes or hours becomes `Days(1)`, which gives the same times except on days
+    ///   when daylight saving time starts or ends, where `Days(1)` stays at midnight.
+    ///
+    /// Longer intervals aren't promoted to `Days` or `Weeks`, since they're aligned differently: `Hours(48)` counts
+    /// from midnight today, `Days(2)` from the start of the era, and `Days(7)` and `Weeks(1)` start on different
+    /// days of the week. Intervals that aren't a fixed length, and zero-length intervals, are returned unchanged.
+    pub fn normalized(self) -> Interval {
+        let (count, unit) = match self.fixed_length() {
+            Some((count, unit)) if count > 0 && unit < 86400 => (count, unit),
+            _ => return self,
+        };
+        let total = u64::from(count) * u64::from(unit);
+        if total == 86400 {
+            return Days(1);
+        }
+        [3600, 60]
+            .iter()
+            .copied()
+            .find(|&larger| larger > unit && total % u64::from(larger) == 0)
+            .map(|larger| Interval::from_fixed_length((total / u64::from(larger)) as u32, larger))
+            .unwrap_or(self)
+    }
+
+    /// Does this interval next fire sooner after `from` than `other` does? This compares when they next run,
+    /// unlike comparing the intervals themselves, which orders them by length: `Saturday` comes after `Days(2)`,
+    /// but fires sooner from a Friday.
+    /// ```rust
+    /// # use clokwerk::Interval::*;
+    /// # use chrono::prelude::*;
+    /// // 14 May 2021 is a Friday
+    /// let friday = Utc.with_ymd_and_hms(2021, 5, 14, 10, 0, 0).unwrap();
+    /// assert!(Saturday.fires_before(&Days(2), &friday));
+    /// assert!(Days(2) < Saturday);
+    /// ```
+    /// If both fire at the same time, neither fires before the other.
+    pub fn fires_before<Tz: TimeZone>(&self, other: &Interval, from: &DateTime<Tz>) -> bool {
+        self.next(from) < other.next(from)
+    }
+}
+
+/// Parses an interval as written by its [`Display`](fmt::Display) implementation, e.g. "every 15 minutes"
+/// or "every Monday, Wednesday and Friday". The leading "every" is optional, as is the count for a single unit,
+/// and case is ignored, so "15 minutes", "hour" and "weekday" work too.
+/// Fixed-length intervals can also be abbreviated to a count and the first letter of the unit, e.g. "5s" or "2h".
+/// ```rust
+/// # use clokwerk::{Interval, TimeUnits};
+/// assert_eq!("every 15 minutes".parse(), Ok(15.minutes()));
+/// assert_eq!("15m".parse(), Ok(15.minutes()));
+/// assert_eq!("hour".parse(), Ok(1.hour()));
+/// assert_eq!("Tuesday".parse(), Ok(Interval::Tuesday));
+/// assert!("fortnightly".parse::<Interval>().is_err());
+/// ```
+impl core::str::FromStr for Interval {
+    type Err = IntervalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_interval(s).ok_or_else(|| IntervalError::Unrecognized { input: s.into() })
+    }
+}
+
+fn parse_interval(s: &str) -> Option<Interval> {
+    let s = s.trim().to_ascii_lowercase();
+    let s = s.strip_prefix("every ").map_or(s.as_str(), str::trim_start);
+    match s {
+        "weekday" => return Some(Weekday),
+        "never" => return Some(DaysOfWeek(WeekdaySet::empty())),
+        _ => (),
+    }
+    if let Some(day) = s
+        .strip_prefix("month on day ")
+        .and_then(|rest| rest.strip_suffix(", or the next weekday"))
+    {
+        return day.parse().ok().map(MonthDayOrNextWeekday);
+    }
+    if let Some(ival) = parse_abbreviated(s) {
+        return Some(ival);
+    }
+    if let Some(count) = s
+        .strip_suffix("iso weeks")
+        .or_else(|| s.strip_suffix("iso week"))
+    {
+        return match count.trim() {
+            "" => Some(IsoWeeks(1)),
+            count if count.bytes().all(|b| b.is_ascii_digit()) => count.parse().ok().map(IsoWeeks),
+            _ => None,
+        };
+    }
+    let mut words = s.split_whitespace();
+    let (count, unit) = match (words.next()?, words.next(), words.next()) {
+        (unit, None, _) if unit.parse::<chrono::Weekday>().is_err() => (1, unit),
+        (count, Some(unit), None) if count.bytes().all(|b| b.is_ascii_digit()) => {
+            (count.parse().ok()?, unit)
+        }
+        _ => return parse_days(s),
+    };
+    Some(match unit.strip_suffix('s').unwrap_or(unit) {
+        "second" => Seconds(count),
+        "minute" => Minutes(count),
+        "hour" => Hours(count),
+        "day" => Days(count),
+        "week" => Weeks(count),
+        _ => return None,
+    })
+}
+
+/// Parses an abbreviated fixed-length interval like "5s" or "2h"
+fn parse_abbreviated(s: &str) -> Option<Interval> {
+    let (count, unit) = s.split_at(s.find(|c: char| !c.is_ascii_digit())?);
+    let count = count.parse().ok()?;
+    Some(match unit {
+        "s" => Seconds(count),
+        "m" => Minutes(count),
+        "h" => Hours(count),
+        "d" => Days(count),
+        "w" => Weeks(count),
+        _ => return None,
+    })
+}
+
+/// Parses a list of days like "monday, wednesday and friday"
+fn parse_days(s: &str) -> Option<Interval> {
+    let days = s
+        .replace(" and ", ",")
+        .split(',')
+        .map(|day| day.trim().parse::<chrono::Weekday>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    match days[..] {
+        [day] => Some(day.into()),
+        _ => Some(DaysOfWeek(days.into_iter().collect())),
+    }
+}
+
+impl Interval {
+    /// Read an interval from the environment variable `var`, parsing it as for [`Interval::from_str`](core::str::FromStr),
+    /// so that a job's schedule can be changed without recompiling.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let interval = Interval::from_env("CLEANUP_INTERVAL").unwrap_or(1.hour());
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(interval).run(|| println!("Cleaning up"));
+    /// ```
+    /// Returns [`IntervalError::MissingVariable`] if the variable isn't set, and
+    /// [`IntervalError::Unrecognized`] if it isn't a valid interval.
+    #[cfg(feature = "std")]
+    pub fn from_env(var: &str) -> Result<Interval, IntervalError> {
+        match std::env::var(var) {
+            Ok(value) => value.parse(),
+            Err(std::env::VarError::NotPresent) => {
+                Err(IntervalError::MissingVariable { name: var.into() })
+            }
+            Err(std::env::VarError::NotUnicode(value)) => Err(IntervalError::Unrecognized {
+                input: value.to_string_lossy().into_owned(),
+            }),
+        }
+    }
+}
+
+/// Converts a day of the week to the matching interval, e.g. `Weekday::Mon` to `Interval::Monday`.
+/// ```rust
+/// # use clokwerk::Interval;
+/// # use chrono::Weekday;
+/// assert_eq!(Interval::from(Weekday::Mon), Interval::Monday);
+/// ```
+impl From<chrono::Weekday> for Interval {
+    fn from(day: chrono::Weekday) -> Self {
+        match day {
+            chrono::Weekday::Mon => Monday,
+            chrono::Weekday::Tue => Tuesday,
+            chrono::Weekday::Wed => Wednesday,
+            chrono::Weekday::Thu => Thursday,
+            chrono::Weekday::Fri => Friday,
+            chrono::Weekday::Sat => Saturday,
+            chrono::Weekday::Sun => Sunday,
+        }
+    }
+}
+
+/// A set of days of the week, for use with [`Interval::DaysOfWeek`].
+#[derive(Eq, PartialEq, Hash, PartialOrd, Ord, Copy, Clone, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "Vec<chrono::Weekday>", into = "Vec<chrono::Weekday>")
+)]
+pub struct WeekdaySet(u8);
+
+impl WeekdaySet {
+    /// A set containing no days. An `Interval::DaysOfWeek` with no days never advances.
+    pub fn empty() -> Self {
+        WeekdaySet(0)
+    }
+
+    pub fn insert(&mut self, day: chrono::Weekday) {
+        self.0 |= Self::bit(day);
+    }
+
+    pub fn contains(&self, day: chrono::Weekday) -> bool {
+        self.0 & Self::bit(day) != 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The days in the set, starting from Monday.
+    pub fn iter(&self) -> impl Iterator<Item = chrono::Weekday> + '_ {
+        let mut day = chrono::Weekday::Mon;
+        (0..7).filter_map(move |_| {
+            let current = day;
+            day = day.succ();
+            if self.contains(current) {
+                Some(current)
+            } else {
+                None
+            }
+        })
+    }
+
+    fn bit(day: chrono::Weekday) -> u8 {
+        1 << day.num_days_from_monday()
+    }
+
+    /// Days from `day` until the next day in the set, between 1 and 7.
+    fn days_after(&self, day: chrono::Weekday) -> i64 {
+        let mut candidate = day;
+        for shift in 1..=7 {
+            candidate = candidate.succ();
+            if self.contains(candidate) {
+                return shift;
+            }
+        }
+        unreachable!("Empty WeekdaySet has no next day")
+    }
+
+    /// Days from `day` back to the previous day in the set, between 1 and 7.
+    fn days_before(&self, day: chrono::Weekday) -> i64 {
+        let mut candidate = day;
+        for shift in 1..=7 {
+            candidate = candidate.pred();
+            if self.contains(candidate) {
+                return shift;
+            }
+        }
+        unreachable!("Empty WeekdaySet has no previous day")
+    }
+}
+
+impl core::iter::FromIterator<chrono::Weekday> for WeekdaySet {
+    fn from_iter<I: IntoIterator<Item = chrono::Weekday>>(iter: I) -> Self {
+        let mut set = WeekdaySet::empty();
+        for day in iter {
+            set.insert(day);
+        }
+        set
+    }
+}
+
+impl From<Vec<chrono::Weekday>> for WeekdaySet {
+    fn from(days: Vec<chrono::Weekday>) -> Self {
+        days.into_iter().collect()
+    }
+}
+
+impl From<WeekdaySet> for Vec<chrono::Weekday> {
+    fn from(days: WeekdaySet) -> Self {
+        days.iter().collect()
+    }
+}
+
+impl fmt::Debug for WeekdaySet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
 }
 
 pub trait NextTime {
@@ -37,6 +486,7 @@ pub trait NextTime {
     fn prev<Tz: TimeZone>(&self, from: &DateTime<Tz>) -> DateTime<Tz>;
 }
 
+#[cfg(feature = "std")]
 pub(crate) fn parse_time(s: &str) -> Result<NaiveTime, chrono::ParseError> {
     NaiveTime::parse_from_str(s, "%H:%M:%S")
         .or_else(|_| NaiveTime::parse_from_str(s, "%I:%M:%S %p"))
@@ -44,69 +494,227 @@ pub(crate) fn parse_time(s: &str) -> Result<NaiveTime, chrono::ParseError> {
         .or_else(|_| NaiveTime::parse_from_str(s, "%I:%M %p"))
 }
 
-#[derive(Debug)]
-enum Adjustment {
-    Intervals(Vec<Interval>),
-    Time(NaiveTime),
+/// How to choose when to run at a time of day that happens twice, or not at all, because of a daylight saving
+/// time change. Set with [`RunConfig::with_dst_policy`], or for every job on a scheduler with
+/// [`Scheduler::with_dst_policy`](crate::Scheduler::with_dst_policy).
+///
+/// The examples are for clocks going forward from 2 am to 3 am, so that 2:30 am is skipped, and going back from
+/// 2 am to 1 am, so that 1:30 am happens twice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DstPolicy {
+    /// Run at the first of a repeated time, before the clocks go back. A skipped time is moved forward by the
+    /// length of the change, so 2:30 am becomes 3:30 am. This is the default.
+    #[default]
+    Earliest,
+    /// Run at the second of a repeated time, after the clocks go back. A skipped time is moved forward as for
+    /// [`DstPolicy::Earliest`].
+    Latest,
+    /// Run at the first of a repeated time, and at the moment the clocks change for a skipped time,
+    /// so 2:30 am becomes 3:00 am.
+    SkipForward,
 }
 
-#[derive(Debug)]
-pub(crate) struct RunConfig {
-    base: Interval,
-    adjustment: Option<Adjustment>,
+#[cfg(feature = "serde")]
+fn is_default_dst_policy(policy: &DstPolicy) -> bool {
+    *policy == DstPolicy::default()
 }
 
-/// A RunConfig defines a schedule for a recurring event. It's composed of a base [`Interval`], and an additional adjustment.
-/// The adjustment is either a single day offset (e.g. "at 3 AM") for use in conjunction with a base interval like "every three days", or "every Tuesday",
-/// or it's a sequence of additional intervals, with the intended use of providing an additional offset for the scheduled task e.g.
+/// A RunConfig defines a schedule for a recurring event. It's composed of a base [`Interval`], and optional adjustments.
+/// The adjustments are a time of day (e.g. "at 3 AM") for use in conjunction with a base interval like "every three days", or "every Tuesday",
+/// and a sequence of additional intervals, with the intended use of providing an additional offset for the scheduled task e.g.
 /// "Every three hours, plus 30 minutes, plus 10 seconds".
+///
+/// If both are present, the time of day is applied first, and then each offset is added to it, so
+/// "every day at 09:00, plus 30 seconds" runs at 09:00:30.
+///
+/// This is what a [`Job`](crate::Job)'s schedule is built from, but can also be used on its own, for instance
+/// when driving jobs yourself without `std`:
+/// ```rust
+/// # use clokwerk::{Interval, NextTime, RunConfig, TimeUnits};
+/// # use chrono::{NaiveTime, TimeZone, Utc};
+/// let config = RunConfig::from_interval(Interval::Tuesday)
+///     .with_time(NaiveTime::from_hms_opt(9, 0, 0).unwrap())
+///     .with_subinterval(30.seconds());
+/// let now = Utc.with_ymd_and_hms(2021, 3, 4, 12, 0, 0).unwrap();
+/// assert_eq!(config.next(&now), Utc.with_ymd_and_hms(2021, 3, 9, 9, 0, 30).unwrap());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RunConfig {
+    base: Interval,
+    time: Option<NaiveTime>,
+    offsets: Vec<Interval>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    phase: Option<Interval>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "is_default_dst_policy")
+    )]
+    dst_policy: DstPolicy,
+}
+
 impl RunConfig {
+    /// A schedule running every `base`, with no adjustments.
     pub fn from_interval(base: Interval) -> Self {
         RunConfig {
             base,
-            adjustment: None,
+            time: None,
+            offsets: Vec::new(),
+            phase: None,
+            dst_policy: DstPolicy::default(),
         }
     }
 
+    /// The interval this schedule is based on.
+    pub fn base(&self) -> Interval {
+        self.base
+    }
+
+    /// The time of day this schedule runs at, if it was given one with [`RunConfig::with_time`].
+    pub fn time(&self) -> Option<NaiveTime> {
+        self.time
+    }
+
+    /// The offsets added with [`RunConfig::with_subinterval`], in the order they're applied.
+    pub fn offsets(&self) -> &[Interval] {
+        &self.offsets
+    }
+
+    /// The phase set with [`RunConfig::with_phase`], if any.
+    pub fn phase(&self) -> Option<Interval> {
+        self.phase
+    }
+
+    /// How times of day affected by daylight saving time changes are handled.
+    pub fn dst_policy(&self) -> DstPolicy {
+        self.dst_policy
+    }
+
+    /// Is this a fixed-length interval, not tied to the time of day or day of the week?
+    #[cfg(feature = "std")]
+    pub(crate) fn is_fixed_interval(&self) -> bool {
+        matches!(self.base, Seconds(_) | Minutes(_) | Hours(_)) && self.time.is_none()
+    }
+
+    /// A copy of this schedule running at the time of day `t`.
     pub fn with_time(&self, t: NaiveTime) -> Self {
         RunConfig {
-            adjustment: Some(Adjustment::Time(t)),
-            ..*self
+            time: Some(t),
+            ..self.clone()
         }
     }
 
+    /// A copy of this schedule with `ival` added as an additional offset.
     pub fn with_subinterval(&self, ival: Interval) -> Self {
-        let mut ival_queue = match self.adjustment {
-            None => vec![],
-            Some(Adjustment::Time(_)) => vec![],
-            Some(Adjustment::Intervals(ref ivals)) => ivals.clone(),
-        };
-        ival_queue.push(ival);
+        let mut offsets = self.offsets.clone();
+        offsets.push(ival);
+        RunConfig {
+            offsets,
+            ..self.clone()
+        }
+    }
+
+    /// A copy of this schedule that runs `phase` after each boundary of the base interval, e.g.
+    /// every 15 minutes with a phase of 5 minutes runs at :05, :20, :35 and :50.
+    ///
+    /// Unlike [`RunConfig::with_subinterval`], the phase is added exactly, rather than rounded up to a multiple of itself.
+    /// It's applied after the time of day and any other offsets. Replaces any previous phase.
+    /// ```rust
+    /// # use clokwerk::{Interval, NextTime, RunConfig, TimeUnits};
+    /// # use chrono::{TimeZone, Utc};
+    /// let config = RunConfig::from_interval(15.minutes()).with_phase(7.minutes());
+    /// let now = Utc.with_ymd_and_hms(2021, 3, 4, 12, 0, 0).unwrap();
+    /// assert_eq!(config.next(&now), Utc.with_ymd_and_hms(2021, 3, 4, 12, 7, 0).unwrap());
+    /// ```
+    pub fn with_phase(&self, phase: Interval) -> Self {
+        RunConfig {
+            phase: Some(phase),
+            ..self.clone()
+        }
+    }
+
+    /// A copy of this schedule that uses `policy` for a time of day that's repeated or skipped by a
+    /// daylight saving time change. This has no effect without a time of day.
+    /// ```rust
+    /// # use clokwerk::{DstPolicy, Interval, NextTime, RunConfig};
+    /// # use chrono::{DateTime, NaiveTime, TimeZone};
+    /// # use chrono_tz::America::New_York;
+    /// // Clocks go back from 2 am to 1 am on 3 November 2024 in New York
+    /// let config = RunConfig::from_interval(Interval::Sunday)
+    ///     .with_time(NaiveTime::from_hms_opt(1, 30, 0).unwrap())
+    ///     .with_dst_policy(DstPolicy::Latest);
+    /// let saturday = New_York.with_ymd_and_hms(2024, 11, 2, 12, 0, 0).unwrap();
+    /// let second = DateTime::parse_from_rfc3339("2024-11-03T01:30:00-05:00").unwrap();
+    /// assert_eq!(config.next(&saturday), second);
+    /// ```
+    pub fn with_dst_policy(&self, policy: DstPolicy) -> Self {
         RunConfig {
-            adjustment: Some(Adjustment::Intervals(ival_queue)),
-            ..*self
+            dst_policy: policy,
+            ..self.clone()
+        }
+    }
+
+    /// The time between runs, if the base interval and all of the offsets have fixed lengths.
+    /// This is how far [`RunConfig::next_from`] moves, ignoring the time of day.
+    #[cfg(feature = "std")]
+    pub(crate) fn fixed_period(&self) -> Option<Duration> {
+        let mut period = self.base.fixed_duration()?;
+        for ival in self.offsets.iter().chain(&self.phase) {
+            period = period.checked_add(&ival.fixed_duration()?)?;
+        }
+        Some(period)
+    }
+
+    /// The next run counting from `from`, rather than aligned to the base interval.
+    /// Offsets are added on, but the time of day is ignored.
+    #[cfg(feature = "std")]
+    pub(crate) fn next_from<Tz: TimeZone>(&self, from: &DateTime<Tz>) -> DateTime<Tz> {
+        let rv = self
+            .offsets
+            .iter()
+            .fold(self.base.next_from(from), |rv, ival| ival.next_from(&rv));
+        self.add_phase(rv)
+    }
+
+    fn add_phase<Tz: TimeZone>(&self, from: DateTime<Tz>) -> DateTime<Tz> {
+        match self.phase {
+            Some(phase) => phase.next_from(&from),
+            None => from,
         }
     }
 
     fn apply_adjustment<Tz: TimeZone>(&self, from: &DateTime<Tz>) -> DateTime<Tz> {
-        match self.adjustment {
-            None => from.clone(),
-            Some(Adjustment::Time(ref t)) => {
-                let from_time = from.time();
-                if *t >= from_time {
-                    from.date().and_time(t.clone()).unwrap()
-                } else {
-                    (from.date() + Duration::days(1))
-                        .and_time(t.clone())
-                        .unwrap()
-                }
-            }
-            Some(Adjustment::Intervals(ref ivals)) => {
+        match self.time {
+            None => {
                 let mut rv = from.clone();
-                for ival in ivals {
+                for ival in &self.offsets {
                     rv = ival.next(&rv);
                 }
-                rv
+                self.add_phase(rv)
+            }
+            Some(t) => {
+                // Compare the resolved times, rather than `t` with the local time of `from`: when a daylight
+                // saving time change skips midnight, the day's boundary is later than a time of 00:00 on it
+                let today = at_local(from, from.date_naive(), t, self.dst_policy);
+                let mut rv = if today >= *from {
+                    today
+                } else {
+                    at_local(
+                        from,
+                        from.date_naive() + Duration::days(1),
+                        t,
+                        self.dst_policy,
+                    )
+                };
+                // Offsets are relative to the time of day, rather than aligned to their own interval
+                for ival in &self.offsets {
+                    rv = ival.next_from(&rv);
+                }
+                self.add_phase(rv)
             }
         }
     }
@@ -115,14 +723,147 @@ impl RunConfig {
 impl NextTime for RunConfig {
     fn next<Tz: TimeZone>(&self, from: &DateTime<Tz>) -> DateTime<Tz> {
         let candidate = self.apply_adjustment(&self.base.prev(from));
+        if candidate > *from {
+            return candidate;
+        }
+        let next_boundary = self.base.next(from);
+        // If `from` is exactly on a boundary, `prev` skips over it, so check it too
+        let candidate = self.apply_adjustment(&self.base.prev(&next_boundary));
         if candidate > *from {
             candidate
         } else {
-            self.apply_adjustment(&self.base.next(from))
+            self.apply_adjustment(&next_boundary)
         }
     }
-    fn prev<Tz: TimeZone>(&self, _from: &DateTime<Tz>) -> DateTime<Tz> {
-        unimplemented!()
+    fn prev<Tz: TimeZone>(&self, from: &DateTime<Tz>) -> DateTime<Tz> {
+        // Adjustments can push a run past `from`, so walk back through the base interval until
+        // we find one that isn't.
+        let mut boundary = self.base.prev(from);
+        loop {
+            let candidate = self.apply_adjustment(&boundary);
+            if candidate < *from {
+                return candidate;
+            }
+            let earlier = self.base.prev(&boundary);
+            if earlier >= boundary {
+                // Zero-length intervals never move
+                return candidate;
+            }
+            boundary = earlier;
+        }
+    }
+}
+
+/// Combine a local date and time in the timezone of `from`.
+///
+/// Around daylight saving time changes, a local time can happen twice, or not at all. `policy` chooses which
+/// to use if it happens twice, and what to use instead if it's skipped; see [`DstPolicy`].
+fn at_local<Tz: TimeZone>(
+    from: &DateTime<Tz>,
+    date: NaiveDate,
+    time: NaiveTime,
+    policy: DstPolicy,
+) -> DateTime<Tz> {
+    let tz = from.timezone();
+    let local = date.and_time(time);
+    match (tz.from_local_datetime(&local), policy) {
+        (LocalResult::Single(dt), _) => dt,
+        (LocalResult::Ambiguous(_, latest), DstPolicy::Latest) => latest,
+        (LocalResult::Ambiguous(earliest, _), _) => earliest,
+        (LocalResult::None, DstPolicy::SkipForward) => {
+            // The change happened between the UTC times given by the offsets from after and before it
+            let mut before_change = tz.from_utc_datetime(&(local - utc_offset_near(&tz, local, 1)));
+            let mut after_change = tz.from_utc_datetime(&(local - utc_offset_near(&tz, local, -1)));
+            loop {
+                let span = (after_change.clone() - before_change.clone()).num_seconds();
+                if span <= 1 {
+                    break;
+                }
+                let mid = before_change.clone() + Duration::seconds(span / 2);
+                if mid.offset().fix() == before_change.offset().fix() {
+                    before_change = mid;
+                } else {
+                    after_change = mid;
+                }
+            }
+            after_change
+        }
+        (LocalResult::None, _) => {
+            // Use the offset from before the change
+            tz.from_utc_datetime(&(local - utc_offset_near(&tz, local, -1)))
+        }
+    }
+}
+
+/// The UTC offset `days` days from the local time `local`, e.g. to find the offset before or after a change.
+fn utc_offset_near<Tz: TimeZone>(tz: &Tz, local: NaiveDateTime, days: i64) -> Duration {
+    let offset = tz.offset_from_utc_datetime(&(local + Duration::days(days)));
+    Duration::seconds(i64::from(offset.fix().local_minus_utc()))
+}
+
+/// The start of the given local date in the timezone of `from`.
+fn midnight<Tz: TimeZone>(from: &DateTime<Tz>, date: NaiveDate) -> DateTime<Tz> {
+    at_local(
+        from,
+        date,
+        NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+        DstPolicy::default(),
+    )
+}
+
+/// The longest period we'll use for `Minutes` and `Hours`, in seconds. This is the same as the longest possible
+/// `Seconds` interval (a little over 136 years); anything longer could overflow when added to a date.
+const MAX_PERIOD_SECONDS: i64 = u32::MAX as i64;
+
+/// The length of `count` units of `unit_seconds` each, clamped to [`MAX_PERIOD_SECONDS`].
+fn period_seconds(count: u32, unit_seconds: i64) -> i64 {
+    (i64::from(count) * unit_seconds).min(MAX_PERIOD_SECONDS)
+}
+
+/// The run date for [`Interval::MonthDayOrNextWeekday`] in the month `shift` months from the month of `from`.
+fn month_day_or_next_weekday<Tz: TimeZone>(from: &DateTime<Tz>, shift: i32, day: u32) -> NaiveDate {
+    let months = from.year() * 12 + from.month0() as i32 + shift;
+    let first = NaiveDate::from_ymd_opt(months.div_euclid(12), months.rem_euclid(12) as u32 + 1, 1)
+        .unwrap();
+    let next_month = (first + Duration::days(31)).with_day(1).unwrap();
+    let days_in_month = (next_month - first).num_days() as u32;
+    let date = first + Duration::days(i64::from(day.min(days_in_month) - 1));
+    match date.weekday() {
+        Weekday::Sat => date + Duration::days(2),
+        Weekday::Sun => date + Duration::days(1),
+        _ => date,
+    }
+}
+
+/// Days since the start of the common era, which is negative for dates before 1 CE.
+fn day_of_era(date: NaiveDate) -> i64 {
+    i64::from(date.num_days_from_ce())
+}
+
+/// The Monday starting the ISO week that `date` is in.
+fn iso_week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(i64::from(date.weekday().num_days_from_monday()))
+}
+
+/// How many weeks the ISO week starting on `monday` is past the last start of an [`Interval::IsoWeeks`] run.
+fn iso_weeks_since_run(monday: NaiveDate, n: u32) -> u32 {
+    (monday.iso_week().week() - 1) % n
+}
+
+/// The first run of [`Interval::IsoWeeks`] that starts on or after `monday`, which must be a Monday.
+fn next_iso_week_run(monday: NaiveDate, n: u32) -> NaiveDate {
+    let weeks_since = iso_weeks_since_run(monday, n);
+    if weeks_since == 0 {
+        return monday;
+    }
+    // Runs start again at week 1 of the next ISO year
+    let next_year =
+        NaiveDate::from_isoywd_opt(monday.iso_week().year() + 1, 1, Weekday::Mon).unwrap();
+    let weeks_until = i64::from(n - weeks_since);
+    if weeks_until >= (next_year - monday).num_weeks() {
+        next_year
+    } else {
+        monday + Duration::weeks(weeks_until)
     }
 }
 
@@ -148,6 +889,8 @@ impl NextTime for Interval {
             Seconds(x) | Minutes(x) | Hours(x) | Days(x) | Weeks(x) if x == 0 => {
                 return from.clone()
             }
+            DaysOfWeek(days) if days.is_empty() => return from.clone(),
+            MonthDayOrNextWeekday(0) | IsoWeeks(0) => return from.clone(),
             _ => (),
         }
         match *self {
@@ -157,45 +900,69 @@ impl NextTime for Interval {
                 from.with_nanosecond(0).unwrap() + Duration::seconds(i64::from(next))
             }
             Minutes(m) => {
-                let s = from.num_seconds_from_midnight();
-                let modulus = s.checked_rem(m * 60).unwrap_or(0);
-                from.with_nanosecond(0).unwrap() + Duration::seconds(i64::from(m * 60 - modulus))
+                let period = period_seconds(m, 60);
+                let s = i64::from(from.num_seconds_from_midnight());
+                let modulus = s.checked_rem(period).unwrap_or(0);
+                from.with_nanosecond(0).unwrap() + Duration::seconds(period - modulus)
             }
             Hours(h) => {
-                let s = from.num_seconds_from_midnight();
-                let modulus = s.checked_rem(h * 3600).unwrap_or(0);
-                from.with_nanosecond(0).unwrap() + Duration::seconds(i64::from(h * 3600 - modulus))
+                let period = period_seconds(h, 3600);
+                let s = i64::from(from.num_seconds_from_midnight());
+                let modulus = s.checked_rem(period).unwrap_or(0);
+                from.with_nanosecond(0).unwrap() + Duration::seconds(period - modulus)
             }
             Days(d) => {
-                let day_of_era = from.num_days_from_ce() as u32;
-                let modulus = day_of_era.checked_rem(d).unwrap_or(0);
-                (from.date() + Duration::days(i64::from(d - modulus))).and_hms(0, 0, 0)
+                let modulus = day_of_era(from.date_naive()).rem_euclid(i64::from(d));
+                midnight(
+                    from,
+                    from.date_naive() + Duration::days(i64::from(d) - modulus),
+                )
             }
             Weeks(w) => {
-                let d = from.date();
+                let d = from.date_naive();
                 let dow = d.weekday().num_days_from_monday();
-                let start_of_week = d.clone() - Duration::days(i64::from(dow));
-                let days_since_ever = d.num_days_from_ce();
-                let week_num = (days_since_ever / 7) as u32;
-                let modulus = week_num.checked_rem(w).unwrap_or(0);
-                (start_of_week + Duration::weeks(i64::from(w - modulus))).and_hms(0, 0, 0)
+                let start_of_week = d - Duration::days(i64::from(dow));
+                let week_num = day_of_era(d).div_euclid(7);
+                let modulus = week_num.rem_euclid(i64::from(w));
+                midnight(
+                    from,
+                    start_of_week + Duration::weeks(i64::from(w) - modulus),
+                )
             }
             Monday | Tuesday | Wednesday | Thursday | Friday | Saturday | Sunday => {
-                let d = from.date();
+                let d = from.date_naive();
                 let dow = d.weekday().num_days_from_monday() as usize;
                 let i_dow = day_of_week(*self);
                 let to_shift = DAYS_TO_SHIFT[7 - i_dow + dow];
-                (from.date() + Duration::days(i64::from(to_shift))).and_hms(0, 0, 0)
+                midnight(
+                    from,
+                    from.date_naive() + Duration::days(i64::from(to_shift)),
+                )
             }
             Weekday => {
-                let d = from.date();
+                let d = from.date_naive();
                 let dow = d.weekday();
                 let days = match dow {
                     Weekday::Fri => 3,
                     Weekday::Sat => 2,
                     _ => 1,
                 };
-                (from.date() + Duration::days(days)).and_hms(0, 0, 0)
+                midnight(from, from.date_naive() + Duration::days(days))
+            }
+            DaysOfWeek(days) => {
+                let d = from.date_naive();
+                midnight(from, d + Duration::days(days.days_after(d.weekday())))
+            }
+            MonthDayOrNextWeekday(n) => {
+                // Last month's run can be pushed into this month, and this month's can already be past
+                (-1..=1)
+                    .map(|shift| midnight(from, month_day_or_next_weekday(from, shift, n)))
+                    .find(|candidate| candidate > from)
+                    .unwrap()
+            }
+            IsoWeeks(n) => {
+                let following = iso_week_start(from.date_naive()) + Duration::weeks(1);
+                midnight(from, next_iso_week_run(following, n))
             }
         }
     }
@@ -205,52 +972,54 @@ impl NextTime for Interval {
             Seconds(x) | Minutes(x) | Hours(x) | Days(x) | Weeks(x) if x == 0 => {
                 return from.clone()
             }
+            DaysOfWeek(days) if days.is_empty() => return from.clone(),
+            MonthDayOrNextWeekday(0) | IsoWeeks(0) => return from.clone(),
             _ => (),
         }
         match *self {
             Seconds(s) => {
                 let modulus = from.timestamp().checked_rem(i64::from(s)).unwrap_or(0);
                 let modulus = if modulus == 0 { i64::from(s) } else { modulus };
-                from.with_nanosecond(0).unwrap() - Duration::seconds(modulus as i64)
+                from.with_nanosecond(0).unwrap() - Duration::seconds(modulus)
             }
             Minutes(m) => {
-                let s = from.num_seconds_from_midnight();
-                let modulus = s.checked_rem(m * 60).unwrap_or(0);
-                let modulus = if modulus == 0 { m * 60 } else { modulus };
-                from.with_nanosecond(0).unwrap() - Duration::seconds(i64::from(modulus))
+                let period = period_seconds(m, 60);
+                let s = i64::from(from.num_seconds_from_midnight());
+                let modulus = s.checked_rem(period).unwrap_or(0);
+                let modulus = if modulus == 0 { period } else { modulus };
+                from.with_nanosecond(0).unwrap() - Duration::seconds(modulus)
             }
             Hours(h) => {
-                let s = from.num_seconds_from_midnight();
-                let modulus = s.checked_rem(h * 3600).unwrap_or(0);
-                let modulus = if modulus == 0 { h * 3600 } else { modulus };
-                from.with_nanosecond(0).unwrap() - Duration::seconds(i64::from(modulus))
+                let period = period_seconds(h, 3600);
+                let s = i64::from(from.num_seconds_from_midnight());
+                let modulus = s.checked_rem(period).unwrap_or(0);
+                let modulus = if modulus == 0 { period } else { modulus };
+                from.with_nanosecond(0).unwrap() - Duration::seconds(modulus)
             }
             Days(d) => {
-                let day_of_era = from.num_days_from_ce() as u32;
-                let modulus = day_of_era.checked_rem(d).unwrap_or(0);
+                let modulus = day_of_era(from.date_naive()).rem_euclid(i64::from(d));
                 let modulus = if modulus == 0 && from.num_seconds_from_midnight() == 0 {
-                    d
+                    i64::from(d)
                 } else {
                     modulus
                 };
-                (from.date() - Duration::days(i64::from(modulus))).and_hms(0, 0, 0)
+                midnight(from, from.date_naive() - Duration::days(modulus))
             }
             Weeks(w) => {
-                let d = from.date();
+                let d = from.date_naive();
                 let dow = d.weekday().num_days_from_monday();
-                let start_of_week = d.clone() - Duration::days(i64::from(dow));
-                let days_since_ever = d.num_days_from_ce();
-                let week_num = (days_since_ever / 7) as u32;
-                let modulus = week_num.checked_rem(w).unwrap_or(0);
+                let start_of_week = d - Duration::days(i64::from(dow));
+                let week_num = day_of_era(d).div_euclid(7);
+                let modulus = week_num.rem_euclid(i64::from(w));
                 let modulus = if modulus == 0 && from.num_seconds_from_midnight() == 0 {
-                    w
+                    i64::from(w)
                 } else {
                     modulus
                 };
-                (start_of_week - Duration::weeks(i64::from(modulus))).and_hms(0, 0, 0)
+                midnight(from, start_of_week - Duration::weeks(modulus))
             }
             Monday | Tuesday | Wednesday | Thursday | Friday | Saturday | Sunday => {
-                let d = from.date();
+                let d = from.date_naive();
                 let dow = d.weekday().num_days_from_monday() as i32;
                 let i_dow = day_of_week(*self) as i32;
                 let mut to_shift = if dow >= i_dow {
@@ -262,14 +1031,18 @@ impl NextTime for Interval {
                     to_shift = 7;
                 }
 
-                (from.date() - Duration::days(i64::from(to_shift))).and_hms(0, 0, 0)
+                midnight(
+                    from,
+                    from.date_naive() - Duration::days(i64::from(to_shift)),
+                )
             }
             Weekday => {
-                let d = from.date();
+                let d = from.date_naive();
                 let dow = d.weekday();
                 let days = match dow {
                     Weekday::Sat => 1,
                     Weekday::Sun => 2,
+                    Weekday::Mon if from.num_seconds_from_midnight() == 0 => 3,
                     _ => {
                         if from.num_seconds_from_midnight() == 0 {
                             1
@@ -278,30 +1051,130 @@ impl NextTime for Interval {
                         }
                     }
                 };
-                (from.date() - Duration::days(days)).and_hms(0, 0, 0)
+                midnight(from, from.date_naive() - Duration::days(days))
+            }
+            DaysOfWeek(days) => {
+                let d = from.date_naive();
+                let shift = if days.contains(d.weekday()) && from.num_seconds_from_midnight() != 0 {
+                    0
+                } else {
+                    days.days_before(d.weekday())
+                };
+                midnight(from, d - Duration::days(shift))
+            }
+            MonthDayOrNextWeekday(n) => (-2..=0)
+                .rev()
+                .map(|shift| midnight(from, month_day_or_next_weekday(from, shift, n)))
+                .find(|candidate| candidate < from)
+                .unwrap(),
+            IsoWeeks(n) => {
+                let mut monday = iso_week_start(from.date_naive());
+                if midnight(from, monday) >= *from {
+                    monday -= Duration::weeks(1);
+                }
+                let weeks_since = iso_weeks_since_run(monday, n);
+                midnight(from, monday - Duration::weeks(i64::from(weeks_since)))
             }
         }
     }
 }
 
 impl Interval {
+    /// The number of units in a fixed-length interval, and the length of a unit in seconds.
+    fn fixed_length(&self) -> Option<(u32, u32)> {
+        match *self {
+            Seconds(n) => Some((n, 1)),
+            Minutes(n) => Some((n, 60)),
+            Hours(n) => Some((n, 3600)),
+            Days(n) => Some((n, 86400)),
+            Weeks(n) => Some((n, 7 * 86400)),
+            _ => None,
+        }
+    }
+
+    /// The length of a fixed-length interval, or `None` for the day-of-week intervals
+    #[cfg(feature = "std")]
+    pub(crate) fn fixed_duration(&self) -> Option<Duration> {
+        self.fixed_length()
+            .map(|(count, unit)| Duration::seconds(i64::from(count) * i64::from(unit)))
+    }
+
+    /// A key giving the order described on [`Interval`]
+    fn sort_key(&self) -> (u8, u64, u32) {
+        match (*self, self.fixed_length()) {
+            (_, Some((count, unit))) => (0, u64::from(count) * u64::from(unit), unit),
+            (Weekday, _) => (2, 0, 0),
+            (DaysOfWeek(days), _) => (3, u64::from(days.0), 0),
+            (MonthDayOrNextWeekday(n), _) => (4, u64::from(n), 0),
+            (IsoWeeks(n), _) => (5, u64::from(n), 0),
+            _ => (1, day_of_week(*self) as u64, 0),
+        }
+    }
+
+    fn from_fixed_length(count: u32, unit: u32) -> Interval {
+        match unit {
+            1 => Seconds(count),
+            60 => Minutes(count),
+            3600 => Hours(count),
+            86400 => Days(count),
+            _ => Weeks(count),
+        }
+    }
+
+    /// Is this one of the day-of-week intervals, e.g. `Monday` or `Weekday`?
+    #[cfg(feature = "std")]
+    pub(crate) fn is_day_of_week(&self) -> bool {
+        matches!(
+            self,
+            Monday
+                | Tuesday
+                | Wednesday
+                | Thursday
+                | Friday
+                | Saturday
+                | Sunday
+                | Weekday
+                | DaysOfWeek(_)
+        )
+    }
+
+    /// Is this a fixed interval shorter than an hour?
+    #[cfg(feature = "std")]
+    pub(crate) fn is_sub_hour(&self) -> bool {
+        match *self {
+            Seconds(s) => s < 3600,
+            Minutes(m) => m < 60,
+            _ => false,
+        }
+    }
+
     pub(crate) fn next_from<Tz: TimeZone>(&self, from: &DateTime<Tz>) -> DateTime<Tz> {
         match *self {
             Seconds(x) | Minutes(x) | Hours(x) | Days(x) | Weeks(x) if x == 0 => {
                 return from.clone()
             }
+            DaysOfWeek(days) if days.is_empty() => return from.clone(),
+            MonthDayOrNextWeekday(0) | IsoWeeks(0) => return from.clone(),
             _ => (),
         }
 
         match *self {
             Seconds(s) => from.clone() + Duration::seconds(s as i64),
-            Minutes(m) => from.clone() + Duration::seconds(m as i64 * 60),
-            Hours(h) => from.clone() + Duration::seconds(h as i64 * 3600),
+            Minutes(m) => from.clone() + Duration::seconds(period_seconds(m, 60)),
+            Hours(h) => from.clone() + Duration::seconds(period_seconds(h, 3600)),
             Days(d) => from.clone() + Duration::days(d as i64),
             Weeks(w) => from.clone() + Duration::days(w as i64 * 7),
-            Monday | Tuesday | Wednesday | Thursday | Friday | Saturday | Sunday => self.next(from),
+            Monday
+            | Tuesday
+            | Wednesday
+            | Thursday
+            | Friday
+            | Saturday
+            | Sunday
+            | MonthDayOrNextWeekday(_)
+            | IsoWeeks(_) => self.next(from),
             Weekday => {
-                let d = from.date();
+                let d = from.date_naive();
                 let dow = d.weekday();
                 let days = match dow {
                     Weekday::Fri => 3,
@@ -310,10 +1183,51 @@ impl Interval {
                 };
                 from.clone() + Duration::days(days)
             }
+            DaysOfWeek(days) => from.clone() + Duration::days(days.days_after(from.weekday())),
         }
     }
 }
 
+/// Adding fixed-length intervals (seconds, minutes, hours, days and weeks) gives an interval of their combined length,
+/// in the smaller of the two units:
+/// ```rust
+/// # use clokwerk::{Interval, TimeUnits};
+/// assert_eq!(1.hour() + 30.minutes(), Interval::Minutes(90));
+/// assert_eq!(1.week() + 2.days(), Interval::Days(9));
+/// assert_eq!(2.hours() + 3.hours(), Interval::Hours(5));
+/// ```
+/// The result is aligned like any other interval of its unit, so e.g. `Minutes(90)` runs at 00:00, 01:30, 03:00, etc.
+/// To offset a job from an interval instead, see [`Job::plus`](crate::Job::plus).
+///
+/// # Panics
+/// Day-of-week intervals like `Monday` or `Weekday` have no fixed length, so adding them panics,
+/// as does a sum too long to represent.
+/// ```rust,should_panic
+/// # use clokwerk::{Interval, TimeUnits};
+/// let _ = Interval::Monday + 1.hour();
+/// ```
+impl core::ops::Add for Interval {
+    type Output = Interval;
+
+    fn add(self, rhs: Interval) -> Interval {
+        let (lhs_count, lhs_unit, rhs_count, rhs_unit) =
+            match (self.fixed_length(), rhs.fixed_length()) {
+                (Some((lc, lu)), Some((rc, ru))) => (lc, lu, rc, ru),
+                _ => panic!(
+                    "Can't add {:?} and {:?}: only fixed-length intervals can be added",
+                    self, rhs
+                ),
+            };
+        let unit = lhs_unit.min(rhs_unit);
+        lhs_count
+            .checked_mul(lhs_unit / unit)
+            .zip(rhs_count.checked_mul(rhs_unit / unit))
+            .and_then(|(l, r)| l.checked_add(r))
+            .map(|count| Interval::from_fixed_length(count, unit))
+            .unwrap_or_else(|| panic!("{:?} + {:?} is too long to represent", self, rhs))
+    }
+}
+
 /// A trait for easily expressing common intervals. Each method generates an appropriate [Interval].
 /// Plural and non-plural forms behave identically, but exist to make code more grammatical.
 /// ```rust
@@ -366,12 +1280,122 @@ impl TimeUnits for u32 {
     }
 }
 
+/// Build an [Interval] from a short phrase, e.g. `interval!(every 5 minutes)` or `interval!(monday)`.
+/// The `every` is optional. Misspelled units are a compile error, rather than a panic or a wrong schedule.
+/// ```rust
+/// # use clokwerk::{interval, Interval};
+/// assert_eq!(interval!(every 5 seconds), Interval::Seconds(5));
+/// assert_eq!(interval!(every 1 minute), Interval::Minutes(1));
+/// assert_eq!(interval!(2 hours), Interval::Hours(2));
+/// assert_eq!(interval!(every day), Interval::Days(1));
+/// assert_eq!(interval!(every monday), Interval::Monday);
+/// assert_eq!(interval!(weekday), Interval::Weekday);
+/// let n = 3;
+/// assert_eq!(interval!(every n weeks), Interval::Weeks(3));
+/// ```
+/// ```rust,compile_fail
+/// # use clokwerk::interval;
+/// let typo = interval!(every 5 secons);
+/// ```
+#[macro_export]
+macro_rules! interval {
+    (every $($rest:tt)+) => {
+        $crate::interval!($($rest)+)
+    };
+    (second) => { $crate::Interval::Seconds(1) };
+    (minute) => { $crate::Interval::Minutes(1) };
+    (hour) => { $crate::Interval::Hours(1) };
+    (day) => { $crate::Interval::Days(1) };
+    (week) => { $crate::Interval::Weeks(1) };
+    ($n:tt second) => { $crate::TimeUnits::seconds($n) };
+    ($n:tt seconds) => { $crate::TimeUnits::seconds($n) };
+    ($n:tt minute) => { $crate::TimeUnits::minutes($n) };
+    ($n:tt minutes) => { $crate::TimeUnits::minutes($n) };
+    ($n:tt hour) => { $crate::TimeUnits::hours($n) };
+    ($n:tt hours) => { $crate::TimeUnits::hours($n) };
+    ($n:tt day) => { $crate::TimeUnits::days($n) };
+    ($n:tt days) => { $crate::TimeUnits::days($n) };
+    ($n:tt week) => { $crate::TimeUnits::weeks($n) };
+    ($n:tt weeks) => { $crate::TimeUnits::weeks($n) };
+    (monday) => { $crate::Interval::Monday };
+    (tuesday) => { $crate::Interval::Tuesday };
+    (wednesday) => { $crate::Interval::Wednesday };
+    (thursday) => { $crate::Interval::Thursday };
+    (friday) => { $crate::Interval::Friday };
+    (saturday) => { $crate::Interval::Saturday };
+    (sunday) => { $crate::Interval::Sunday };
+    (weekday) => { $crate::Interval::Weekday };
+    ($($other:tt)*) => {
+        compile_error!(concat!(
+            "Unrecognized interval `",
+            stringify!($($other)*),
+            "`; expected e.g. `every 5 minutes` or `monday`"
+        ))
+    };
+}
+
+/// A trait for expressing intervals as rates, e.g. "four times a day" rather than "every six hours".
+/// Each method generates an appropriate [Interval], using the largest unit that represents it exactly.
+/// ```rust
+/// # use clokwerk::{Interval, Rate};
+/// assert_eq!(4.times_per_day(), Ok(Interval::Hours(6)));
+/// assert_eq!(96.times_per_day(), Ok(Interval::Minutes(15)));
+/// assert_eq!(12.times_per_hour(), Ok(Interval::Minutes(5)));
+/// assert_eq!(3600.times_per_hour(), Ok(Interval::Seconds(1)));
+/// ```
+///
+/// No rounding is done: if the period isn't evenly divisible into a whole number of seconds,
+/// an error is returned instead, since the runs couldn't be evenly spaced.
+/// The same applies if the rate is zero.
+/// ```rust
+/// # use clokwerk::{Interval, Rate};
+/// assert!(7.times_per_hour().is_err());
+/// assert!(0.times_per_day().is_err());
+/// ```
+pub trait Rate {
+    fn times_per_day(self) -> Result<Interval, IntervalError>;
+    fn times_per_hour(self) -> Result<Interval, IntervalError>;
+}
+
+impl Rate for u32 {
+    fn times_per_day(self) -> Result<Interval, IntervalError> {
+        interval_from_rate(self, 1.day())
+    }
+    fn times_per_hour(self) -> Result<Interval, IntervalError> {
+        interval_from_rate(self, 1.hour())
+    }
+}
+
+fn interval_from_rate(times: u32, per: Interval) -> Result<Interval, IntervalError> {
+    let period = match per {
+        Hours(h) => h * 3600,
+        Days(d) => d * 86400,
+        _ => unreachable!("Rates are only defined per hour or per day"),
+    };
+    if times == 0 || period % times != 0 {
+        return Err(IntervalError::InvalidRate { times, per });
+    }
+    let seconds = period / times;
+    Ok(if seconds.checked_rem(3600) == Some(0) {
+        Hours(seconds / 3600)
+    } else if seconds.checked_rem(60) == Some(0) {
+        Minutes(seconds / 60)
+    } else {
+        Seconds(seconds)
+    })
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::intervals::{NextTime, RunConfig};
+    use crate::intervals::{DstPolicy, NextTime, RunConfig, WeekdaySet};
     use crate::Interval::*;
-    use crate::TimeUnits;
+    use crate::{Interval, IntervalError, Rate, TimeUnits};
     use chrono::prelude::*;
+    use chrono::{Duration, Weekday};
+
+    fn hms(h: u32, m: u32, s: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, s).unwrap()
+    }
 
     #[test]
     fn basic_units() {
@@ -388,6 +1412,98 @@ mod tests {
         assert_eq!(Weeks(0), 0.weeks());
     }
 
+    #[test]
+    fn rates() {
+        assert_eq!(1.times_per_day(), Ok(Hours(24)));
+        assert_eq!(3.times_per_day(), Ok(Hours(8)));
+        assert_eq!(5.times_per_day(), Ok(Minutes(288)));
+        assert_eq!(86400.times_per_day(), Ok(Seconds(1)));
+        assert_eq!(
+            7.times_per_day(),
+            Err(IntervalError::InvalidRate {
+                times: 7,
+                per: Days(1)
+            })
+        );
+        assert!(86401.times_per_day().is_err());
+
+        assert_eq!(1.times_per_hour(), Ok(Hours(1)));
+        assert_eq!(4.times_per_hour(), Ok(Minutes(15)));
+        assert_eq!(16.times_per_hour(), Ok(Seconds(225)));
+        assert_eq!(
+            0.times_per_hour(),
+            Err(IntervalError::InvalidRate {
+                times: 0,
+                per: Hours(1)
+            })
+        );
+    }
+
+    #[test]
+    fn test_add() {
+        assert_eq!(1.hour() + 30.minutes(), Minutes(90));
+        assert_eq!(30.minutes() + 1.hour(), Minutes(90));
+        assert_eq!(1.day() + 1.second(), Seconds(86401));
+        assert_eq!(1.week() + 1.day(), Days(8));
+        assert_eq!(1.day() + 2.hours(), Hours(26));
+        assert_eq!(2.weeks() + 1.week(), Weeks(3));
+        assert_eq!(5.minutes() + 0.minutes(), Minutes(5));
+        // Units aren't combined into larger ones
+        assert_eq!(30.minutes() + 30.minutes(), Minutes(60));
+        assert_eq!(u32::MAX.minutes() + 0.minutes(), Minutes(u32::MAX));
+    }
+
+    #[test]
+    #[should_panic(expected = "only fixed-length intervals can be added")]
+    fn test_add_weekday() {
+        let _ = 1.hour() + Weekday;
+    }
+
+    #[test]
+    #[should_panic(expected = "too long to represent")]
+    fn test_add_overflow() {
+        let _ = u32::MAX.seconds() + 1.second();
+    }
+
+    #[test]
+    fn test_ord() {
+        let mut intervals = vec![
+            Interval::days_of_week(&[Weekday::Mon]),
+            Interval::Weekday,
+            Interval::Sunday,
+            Interval::Monday,
+            1.week(),
+            2.minutes(),
+            1.minute(),
+            90.seconds(),
+            60.seconds(),
+            u32::MAX.seconds(),
+        ];
+        intervals.sort();
+        assert_eq!(
+            intervals,
+            vec![
+                60.seconds(),
+                1.minute(),
+                90.seconds(),
+                2.minutes(),
+                1.week(),
+                u32::MAX.seconds(),
+                Interval::Monday,
+                Interval::Sunday,
+                Interval::Weekday,
+                Interval::days_of_week(&[Weekday::Mon]),
+            ]
+        );
+
+        let mut counts = std::collections::HashMap::new();
+        for ival in [1.hour(), 60.minutes(), 1.hour()] {
+            *counts.entry(ival).or_insert(0) += 1;
+        }
+        assert_eq!(counts[&1.hour()], 2);
+        assert_eq!(counts[&60.minutes()], 1);
+    }
+
     #[test]
     fn test_next_start() {
         // Set 999 ms to check that we remove any sub-second values
@@ -451,6 +1567,39 @@ mod tests {
         assert_eq!(next_dt, expected);
     }
 
+    #[test]
+    fn test_minutes_aligned_to_clock() {
+        let start = Utc.with_ymd_and_hms(2018, 9, 4, 14, 0, 0).unwrap();
+        let at = |m, s| start + Duration::minutes(m) + Duration::seconds(s);
+        // From every second of the hour, including exact boundaries, which move on to the next one
+        for s in 0..3600 {
+            let from = start + Duration::seconds(s);
+            let next = 30.minutes().next(&from);
+            assert!(
+                next > from && next - from <= Duration::minutes(30),
+                "{}",
+                from
+            );
+            assert_eq!(next.minute() % 30, 0, "{}", from);
+            assert_eq!(next.second(), 0, "{}", from);
+        }
+        assert_eq!(30.minutes().next(&at(17, 0)), at(30, 0));
+        assert_eq!(30.minutes().next(&at(29, 59)), at(30, 0));
+        assert_eq!(30.minutes().next(&at(30, 0)), at(60, 0));
+        let just_after = at(30, 0).with_nanosecond(1).unwrap();
+        assert_eq!(30.minutes().next(&just_after), at(60, 0));
+        let just_before = at(29, 59).with_nanosecond(999_999_999).unwrap();
+        assert_eq!(30.minutes().next(&just_before), at(30, 0));
+
+        // Alignment is to the local clock, even with an offset that isn't a whole number of hours
+        let kathmandu = FixedOffset::east_opt(5 * 3600 + 45 * 60).unwrap();
+        let from = kathmandu.with_ymd_and_hms(2018, 9, 4, 14, 17, 23).unwrap();
+        assert_eq!(
+            30.minutes().next(&from),
+            kathmandu.with_ymd_and_hms(2018, 9, 4, 14, 30, 0).unwrap()
+        );
+    }
+
     #[test]
     fn test_prev() {
         let dt = DateTime::parse_from_rfc3339("2018-09-04T14:22:13.999-00:00").unwrap();
@@ -533,33 +1682,209 @@ mod tests {
         assert_eq!(prev_dt, expected);
     }
 
-    use super::parse_time;
     #[test]
-    fn test_parse_time() {
-        assert_eq!(parse_time("14:52:13"), Ok(NaiveTime::from_hms(14, 52, 13)));
+    fn test_weekday_prev_from_monday_midnight() {
+        // 3 September 2018 is a Monday, so the weekday before its midnight is Friday, not Sunday
+        let monday = DateTime::parse_from_rfc3339("2018-09-03T00:00:00-00:00").unwrap();
+        let friday = DateTime::parse_from_rfc3339("2018-08-31T00:00:00-00:00").unwrap();
+        assert_eq!(Weekday.prev(&monday), friday);
+        // Later on Monday goes back to its start
+        let monday_morning = DateTime::parse_from_rfc3339("2018-09-03T09:00:00-00:00").unwrap();
+        assert_eq!(Weekday.prev(&monday_morning), monday);
+        // And Tuesday's midnight goes back to Monday's
+        let tuesday = DateTime::parse_from_rfc3339("2018-09-04T00:00:00-00:00").unwrap();
+        assert_eq!(Weekday.prev(&tuesday), monday);
+    }
+
+    #[test]
+    fn test_days_of_week() {
+        let mwf = Interval::days_of_week(&[Weekday::Mon, Weekday::Wed, Weekday::Fri]);
+        // A Tuesday
+        let dt = DateTime::parse_from_rfc3339("2018-09-04T14:22:13.999-00:00").unwrap();
+
+        let next_dt = mwf.next(&dt);
+        let expected = DateTime::parse_from_rfc3339("2018-09-05T00:00:00-00:00").unwrap();
+        assert_eq!(next_dt, expected);
+        let next_dt = mwf.next(&expected);
+        let expected = DateTime::parse_from_rfc3339("2018-09-07T00:00:00-00:00").unwrap();
+        assert_eq!(next_dt, expected);
+        // Friday wraps around to Monday
+        let next_dt = mwf.next(&expected);
+        let expected = DateTime::parse_from_rfc3339("2018-09-10T00:00:00-00:00").unwrap();
+        assert_eq!(next_dt, expected);
+
+        let prev_dt = mwf.prev(&dt);
+        let expected = DateTime::parse_from_rfc3339("2018-09-03T00:00:00-00:00").unwrap();
+        assert_eq!(prev_dt, expected);
+        // Monday wraps around to Friday
+        let prev_dt = mwf.prev(&expected);
+        let expected = DateTime::parse_from_rfc3339("2018-08-31T00:00:00-00:00").unwrap();
+        assert_eq!(prev_dt, expected);
+        // Partway through a day in the set goes back to its start
+        let friday = DateTime::parse_from_rfc3339("2018-09-07T10:00:00-00:00").unwrap();
+        let expected = DateTime::parse_from_rfc3339("2018-09-07T00:00:00-00:00").unwrap();
+        assert_eq!(mwf.prev(&friday), expected);
+
+        let rc = RunConfig::from_interval(mwf).with_time(hms(9, 0, 0));
+        let expected = DateTime::parse_from_rfc3339("2018-09-10T09:00:00-00:00").unwrap();
+        assert_eq!(rc.next(&friday), expected);
+
+        let empty = Interval::DaysOfWeek(WeekdaySet::empty());
+        assert_eq!(empty.next(&dt), dt);
+        assert_eq!(empty.prev(&dt), dt);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(1.second().to_string(), "every 1 second");
+        assert_eq!(30.seconds().to_string(), "every 30 seconds");
+        assert_eq!(0.seconds().to_string(), "every 0 seconds");
+        assert_eq!(1.minute().to_string(), "every 1 minute");
+        assert_eq!(15.minutes().to_string(), "every 15 minutes");
+        assert_eq!(1.hour().to_string(), "every 1 hour");
+        assert_eq!(6.hours().to_string(), "every 6 hours");
+        assert_eq!(1.day().to_string(), "every 1 day");
+        assert_eq!(2.days().to_string(), "every 2 days");
+        assert_eq!(1.week().to_string(), "every 1 week");
+        assert_eq!(3.weeks().to_string(), "every 3 weeks");
+        assert_eq!(Monday.to_string(), "every Monday");
+        assert_eq!(Tuesday.to_string(), "every Tuesday");
+        assert_eq!(Wednesday.to_string(), "every Wednesday");
+        assert_eq!(Thursday.to_string(), "every Thursday");
+        assert_eq!(Friday.to_string(), "every Friday");
+        assert_eq!(Saturday.to_string(), "every Saturday");
+        assert_eq!(Sunday.to_string(), "every Sunday");
+        assert_eq!(Interval::Weekday.to_string(), "every weekday");
+        assert_eq!(
+            Interval::days_of_week(&[Weekday::Fri, Weekday::Mon, Weekday::Wed]).to_string(),
+            "every Monday, Wednesday and Friday"
+        );
+        assert_eq!(
+            Interval::days_of_week(&[Weekday::Sat, Weekday::Sun]).to_string(),
+            "every Saturday and Sunday"
+        );
+        assert_eq!(
+            Interval::days_of_week(&[Weekday::Sun]).to_string(),
+            "every Sunday"
+        );
+        assert_eq!(
+            Interval::DaysOfWeek(WeekdaySet::empty()).to_string(),
+            "never"
+        );
+    }
+
+    #[test]
+    fn test_interval_macro() {
+        assert_eq!(interval!(every 30 seconds), 30.seconds());
+        assert_eq!(interval!(every second), 1.second());
+        assert_eq!(interval!(10 minutes), 10.minutes());
+        assert_eq!(interval!(every 1 hour), 1.hour());
+        assert_eq!(interval!(every 2 days), 2.days());
+        assert_eq!(interval!(every week), 1.week());
+        assert_eq!(interval!(every friday), Friday);
+        assert_eq!(interval!(sunday), Sunday);
+        assert_eq!(interval!(every weekday), Interval::Weekday);
+    }
+
+    #[test]
+    fn test_from_weekday() {
+        use super::day_of_week;
+        for day in [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ] {
+            let ival = Interval::from(day);
+            assert!(ival.is_day_of_week());
+            assert_eq!(day_of_week(ival), day.num_days_from_monday() as usize);
+        }
+        assert_eq!(Interval::from(Weekday::Wed), Wednesday);
+    }
+
+    #[test]
+    fn test_weekday_set() {
+        let set: WeekdaySet = vec![Weekday::Sun, Weekday::Mon, Weekday::Sun]
+            .into_iter()
+            .collect();
+        assert!(set.contains(Weekday::Mon));
+        assert!(!set.contains(Weekday::Tue));
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            vec![Weekday::Mon, Weekday::Sun]
+        );
+        assert_eq!(format!("{:?}", set), "{Mon, Sun}");
+    }
+
+    #[test]
+    fn test_extreme_intervals() {
+        let dt = DateTime::parse_from_rfc3339("2018-09-04T14:22:13.999-00:00").unwrap();
+        let midnight = DateTime::parse_from_rfc3339("2018-09-04T00:00:00-00:00").unwrap();
+        let longest = midnight + Duration::seconds(i64::from(u32::MAX));
+
+        // The largest number of minutes that fits in a u32 once converted to seconds
+        let expected = midnight + Duration::seconds(71_582_788 * 60);
+        assert_eq!(71_582_788.minutes().next(&dt), expected);
+        assert_eq!(71_582_789.minutes().next(&dt), longest);
+        assert_eq!(u32::MAX.minutes().next(&dt), longest);
+        assert_eq!(u32::MAX.hours().next(&dt), longest);
+
+        assert_eq!(u32::MAX.minutes().prev(&dt), midnight);
+        assert_eq!(u32::MAX.hours().prev(&dt), midnight);
         assert_eq!(
-            parse_time("2:52:13 pm"),
-            Ok(NaiveTime::from_hms(14, 52, 13))
+            u32::MAX.hours().prev(&midnight),
+            midnight - Duration::seconds(i64::from(u32::MAX))
         );
-        assert_eq!(parse_time("14:52"), Ok(NaiveTime::from_hms(14, 52, 0)));
-        assert_eq!(parse_time("2:52 PM"), Ok(NaiveTime::from_hms(14, 52, 0)));
+
+        assert_eq!(u32::MAX.hours().next_from(&midnight), longest);
+
+        for &n in &[
+            1,
+            59,
+            60,
+            1439,
+            1440,
+            71_582_788,
+            71_582_789,
+            u32::MAX / 2,
+            u32::MAX,
+        ] {
+            for ival in &[n.seconds(), n.minutes(), n.hours()] {
+                assert!(ival.next(&dt) > dt, "{:?}", ival);
+                assert!(ival.prev(&dt) < dt, "{:?}", ival);
+                assert!(ival.next_from(&dt) > dt, "{:?}", ival);
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parse_time() {
+        use super::parse_time;
+        assert_eq!(parse_time("14:52:13"), Ok(hms(14, 52, 13)));
+        assert_eq!(parse_time("2:52:13 pm"), Ok(hms(14, 52, 13)));
+        assert_eq!(parse_time("14:52"), Ok(hms(14, 52, 0)));
+        assert_eq!(parse_time("2:52 PM"), Ok(hms(14, 52, 0)));
     }
 
     #[test]
     fn test_run_config() {
-        let rc = RunConfig::from_interval(1.day()).with_time(NaiveTime::from_hms(15, 0, 0));
+        let rc = RunConfig::from_interval(1.day()).with_time(hms(15, 0, 0));
         let dt = DateTime::parse_from_rfc3339("2018-09-04T14:22:13-00:00").unwrap();
         let next_dt = rc.next(&dt);
         let expected = DateTime::parse_from_rfc3339("2018-09-04T15:00:00-00:00").unwrap();
         assert_eq!(next_dt, expected);
 
-        let rc = RunConfig::from_interval(Tuesday).with_time(NaiveTime::from_hms(15, 0, 0));
+        let rc = RunConfig::from_interval(Tuesday).with_time(hms(15, 0, 0));
         let dt = DateTime::parse_from_rfc3339("2018-09-04T14:22:13-00:00").unwrap();
         let next_dt = rc.next(&dt);
         let expected = DateTime::parse_from_rfc3339("2018-09-04T15:00:00-00:00").unwrap();
         assert_eq!(next_dt, expected);
 
-        let rc = RunConfig::from_interval(Tuesday).with_time(NaiveTime::from_hms(14, 0, 0));
+        let rc = RunConfig::from_interval(Tuesday).with_time(hms(14, 0, 0));
         let next_dt = rc.next(&dt);
         let expected = DateTime::parse_from_rfc3339("2018-09-11T14:00:00-00:00").unwrap();
         assert_eq!(next_dt, expected);
@@ -572,6 +1897,176 @@ mod tests {
         assert_eq!(next_dt, expected);
     }
 
+    #[test]
+    fn test_run_config_from_boundary() {
+        // Starting exactly on a boundary of the base interval runs later that day, rather than skipping a day
+        let rc = RunConfig::from_interval(1.day()).with_time(hms(8, 0, 0));
+        let dt = DateTime::parse_from_rfc3339("2018-09-04T00:00:00-00:00").unwrap();
+        let next_dt = rc.next(&dt);
+        let expected = DateTime::parse_from_rfc3339("2018-09-04T08:00:00-00:00").unwrap();
+        assert_eq!(next_dt, expected);
+
+        // 3 September 2018 is a Monday
+        let rc = RunConfig::from_interval(Monday).with_time(hms(9, 30, 0));
+        let dt = DateTime::parse_from_rfc3339("2018-09-03T00:00:00-00:00").unwrap();
+        let next_dt = rc.next(&dt);
+        let expected = DateTime::parse_from_rfc3339("2018-09-03T09:30:00-00:00").unwrap();
+        assert_eq!(next_dt, expected);
+
+        // Times after the boundary are unaffected
+        let dt = DateTime::parse_from_rfc3339("2018-09-03T09:30:00-00:00").unwrap();
+        let next_dt = rc.next(&dt);
+        let expected = DateTime::parse_from_rfc3339("2018-09-10T09:30:00-00:00").unwrap();
+        assert_eq!(next_dt, expected);
+    }
+
+    #[test]
+    fn test_run_config_prev() {
+        let dt = DateTime::parse_from_rfc3339("2018-09-04T14:22:13-00:00").unwrap();
+        let rc = RunConfig::from_interval(1.day()).with_time(hms(15, 0, 0));
+        let expected = DateTime::parse_from_rfc3339("2018-09-03T15:00:00-00:00").unwrap();
+        assert_eq!(rc.prev(&dt), expected);
+
+        let rc = RunConfig::from_interval(1.day()).with_time(hms(14, 0, 0));
+        let expected = DateTime::parse_from_rfc3339("2018-09-04T14:00:00-00:00").unwrap();
+        assert_eq!(rc.prev(&dt), expected);
+        let expected_prev = DateTime::parse_from_rfc3339("2018-09-03T14:00:00-00:00").unwrap();
+        assert_eq!(rc.prev(&expected), expected_prev);
+
+        let rc = RunConfig::from_interval(Tuesday)
+            .with_subinterval(6.hours())
+            .with_subinterval(5.minutes());
+        let expected = DateTime::parse_from_rfc3339("2018-09-04T06:05:00-00:00").unwrap();
+        assert_eq!(rc.prev(&dt), expected);
+
+        // Offset larger than the base interval
+        let rc = RunConfig::from_interval(1.hour()).with_subinterval(125.minutes());
+        let expected = DateTime::parse_from_rfc3339("2018-09-04T12:30:00-00:00").unwrap();
+        assert_eq!(rc.prev(&dt), expected);
+
+        assert_eq!(RunConfig::from_interval(0.seconds()).prev(&dt), dt);
+
+        // prev and next are consistent with each other
+        for rc in [
+            RunConfig::from_interval(1.day()).with_time(hms(15, 0, 0)),
+            RunConfig::from_interval(Weekday).with_time(hms(9, 30, 0)),
+            RunConfig::from_interval(10.minutes()).with_subinterval(3.minutes()),
+        ] {
+            let next = rc.next(&dt);
+            assert_eq!(rc.next(&rc.prev(&next)), next);
+        }
+    }
+
+    #[test]
+    fn test_run_config_time_plus_offset() {
+        let rc = RunConfig::from_interval(1.day())
+            .with_time(hms(9, 0, 0))
+            .with_subinterval(30.seconds());
+        let dt = DateTime::parse_from_rfc3339("2018-09-04T08:22:13-00:00").unwrap();
+        let expected = DateTime::parse_from_rfc3339("2018-09-04T09:00:30-00:00").unwrap();
+        assert_eq!(rc.next(&dt), expected);
+
+        // Between the time of day and the offset
+        let dt = DateTime::parse_from_rfc3339("2018-09-04T09:00:10-00:00").unwrap();
+        assert_eq!(rc.next(&dt), expected);
+
+        let dt = DateTime::parse_from_rfc3339("2018-09-04T09:00:30-00:00").unwrap();
+        let expected = DateTime::parse_from_rfc3339("2018-09-05T09:00:30-00:00").unwrap();
+        assert_eq!(rc.next(&dt), expected);
+
+        // Order of at and plus doesn't matter, and offsets accumulate
+        let rc = RunConfig::from_interval(1.day())
+            .with_subinterval(1.minute())
+            .with_time(hms(9, 0, 0))
+            .with_subinterval(45.seconds());
+        let dt = DateTime::parse_from_rfc3339("2018-09-04T08:22:13-00:00").unwrap();
+        let expected = DateTime::parse_from_rfc3339("2018-09-04T09:01:45-00:00").unwrap();
+        assert_eq!(rc.next(&dt), expected);
+    }
+
+    #[test]
+    fn test_dst_weekday_with_time() {
+        use chrono::TimeZone;
+        use chrono_tz::America::New_York;
+        let utc = |s: &str| DateTime::parse_from_rfc3339(s).unwrap();
+
+        // Clocks go forward from 2 am to 3 am on Sunday, 10 March 2024, so 2:30 am doesn't happen
+        let saturday = New_York.with_ymd_and_hms(2024, 3, 9, 12, 0, 0).unwrap();
+        let rc = RunConfig::from_interval(Sunday).with_time(hms(2, 30, 0));
+        assert_eq!(rc.next(&saturday), utc("2024-03-10T03:30:00-04:00"));
+        let rc = RunConfig::from_interval(1.day()).with_time(hms(2, 30, 0));
+        assert_eq!(rc.next(&saturday), utc("2024-03-10T03:30:00-04:00"));
+        // Times after the change keep their local time
+        let rc = RunConfig::from_interval(Monday).with_time(hms(0, 30, 0));
+        assert_eq!(rc.next(&saturday), utc("2024-03-11T00:30:00-04:00"));
+        let rc = RunConfig::from_interval(Sunday).with_time(hms(12, 0, 0));
+        assert_eq!(rc.next(&saturday), utc("2024-03-10T12:00:00-04:00"));
+
+        // Clocks go back from 2 am to 1 am on Sunday, 3 November 2024, so 1:30 am happens twice
+        let saturday = New_York.with_ymd_and_hms(2024, 11, 2, 12, 0, 0).unwrap();
+        let rc = RunConfig::from_interval(Sunday).with_time(hms(1, 30, 0));
+        let first = rc.next(&saturday);
+        assert_eq!(first, utc("2024-11-03T01:30:00-04:00"));
+        // ...but the job only runs the first time
+        assert_eq!(rc.next(&first), utc("2024-11-10T01:30:00-05:00"));
+        let rc = RunConfig::from_interval(Monday).with_time(hms(0, 30, 0));
+        assert_eq!(rc.next(&saturday), utc("2024-11-04T00:30:00-05:00"));
+    }
+
+    #[test]
+    fn test_dst_policy() {
+        use chrono::TimeZone;
+        use chrono_tz::America::New_York;
+        let utc = |s: &str| DateTime::parse_from_rfc3339(s).unwrap();
+        let sunday_at = |h, m, policy| {
+            RunConfig::from_interval(Sunday)
+                .with_time(hms(h, m, 0))
+                .with_dst_policy(policy)
+        };
+
+        // Clocks go forward from 2 am to 3 am on Sunday, 10 March 2024, so 2:30 am doesn't happen
+        let saturday = New_York.with_ymd_and_hms(2024, 3, 9, 12, 0, 0).unwrap();
+        let cases = [
+            (DstPolicy::Earliest, "2024-03-10T03:30:00-04:00"),
+            (DstPolicy::Latest, "2024-03-10T03:30:00-04:00"),
+            (DstPolicy::SkipForward, "2024-03-10T03:00:00-04:00"),
+        ];
+        for (policy, expected) in cases {
+            let rc = sunday_at(2, 30, policy);
+            assert_eq!(rc.next(&saturday), utc(expected), "{:?}", policy);
+            let run = utc(expected).with_timezone(&New_York);
+            assert_eq!(rc.prev(&run), utc("2024-03-03T02:30:00-05:00"));
+            // Times that aren't skipped are unaffected
+            let rc = sunday_at(12, 0, policy);
+            assert_eq!(rc.next(&saturday), utc("2024-03-10T12:00:00-04:00"));
+        }
+
+        // Clocks go back from 2 am to 1 am on Sunday, 3 November 2024, so 1:30 am happens twice
+        let saturday = New_York.with_ymd_and_hms(2024, 11, 2, 12, 0, 0).unwrap();
+        let cases = [
+            (DstPolicy::Earliest, "2024-11-03T01:30:00-04:00"),
+            (DstPolicy::Latest, "2024-11-03T01:30:00-05:00"),
+            (DstPolicy::SkipForward, "2024-11-03T01:30:00-04:00"),
+        ];
+        for (policy, expected) in cases {
+            let rc = sunday_at(1, 30, policy);
+            let first = rc.next(&saturday);
+            assert_eq!(first, utc(expected), "{:?}", policy);
+            // The job still runs only once that day
+            assert_eq!(rc.next(&first), utc("2024-11-10T01:30:00-05:00"));
+            let monday = New_York.with_ymd_and_hms(2024, 11, 4, 0, 0, 0).unwrap();
+            assert_eq!(rc.prev(&monday), first);
+        }
+
+        // Lord Howe Island's clocks go forward half an hour, from 2 am to 2:30 am on 6 October 2024
+        let lord_howe = chrono_tz::Australia::Lord_Howe;
+        let saturday = lord_howe.with_ymd_and_hms(2024, 10, 5, 12, 0, 0).unwrap();
+        let rc = sunday_at(2, 15, DstPolicy::SkipForward);
+        assert_eq!(rc.next(&saturday), utc("2024-10-06T02:30:00+11:00"));
+        let rc = sunday_at(2, 15, DstPolicy::Earliest);
+        assert_eq!(rc.next(&saturday), utc("2024-10-06T02:45:00+11:00"));
+    }
+
     #[test]
     fn test_division_by_zero() {
         let dt = DateTime::parse_from_rfc3339("2018-09-04T14:22:13-00:00").unwrap();
@@ -585,15 +2080,359 @@ mod tests {
         assert_eq!(0.days().prev(&dt), dt, "prev 0 days");
         assert_eq!(0.weeks().next(&dt), dt, "next 0 weeks");
         assert_eq!(0.weeks().prev(&dt), dt, "prev 0 weeks");
+        assert_eq!(IsoWeeks(0).next(&dt), dt, "next 0 ISO weeks");
+        assert_eq!(IsoWeeks(0).prev(&dt), dt, "prev 0 ISO weeks");
     }
 
     #[test]
     fn test_daily_interval_plus_time_of_midnight() {
         // See https://github.com/mdsherry/clokwerk/issues/22
         let dt = DateTime::parse_from_rfc3339("2018-09-04T14:22:13-00:00").unwrap();
-        let rc = RunConfig::from_interval(Tuesday).with_time(NaiveTime::from_hms(0, 0, 0));
+        let rc = RunConfig::from_interval(Tuesday).with_time(hms(0, 0, 0));
         let next_dt = rc.next(&dt);
         let expected = DateTime::parse_from_rfc3339("2018-09-11T00:00:00-00:00").unwrap();
         assert_eq!(next_dt, expected);
     }
+
+    #[test]
+    fn test_time_of_midnight() {
+        use chrono_tz::America::New_York;
+        // 4 September 2018 is a Tuesday
+        let at = |d, h, m, s| New_York.with_ymd_and_hms(2018, 9, d, h, m, s).unwrap();
+        let daily = RunConfig::from_interval(1.day()).with_time(hms(0, 0, 0));
+        let weekday = RunConfig::from_interval(Weekday).with_time(hms(0, 0, 0));
+        let cases = [
+            // (from, next daily run, next weekday run)
+            (at(4, 14, 22, 13), at(5, 0, 0, 0), at(5, 0, 0, 0)),
+            (at(4, 23, 59, 59), at(5, 0, 0, 0), at(5, 0, 0, 0)),
+            // Exactly at midnight, the next run is the following midnight
+            (at(5, 0, 0, 0), at(6, 0, 0, 0), at(6, 0, 0, 0)),
+            (at(5, 0, 0, 1), at(6, 0, 0, 0), at(6, 0, 0, 0)),
+            (
+                at(4, 0, 0, 0) - Duration::nanoseconds(1),
+                at(4, 0, 0, 0),
+                at(4, 0, 0, 0),
+            ),
+            // Friday evening and the weekend
+            (at(7, 23, 59, 59), at(8, 0, 0, 0), at(10, 0, 0, 0)),
+            (at(8, 0, 0, 0), at(9, 0, 0, 0), at(10, 0, 0, 0)),
+            (at(9, 23, 59, 59), at(10, 0, 0, 0), at(10, 0, 0, 0)),
+            (at(10, 0, 0, 0), at(11, 0, 0, 0), at(11, 0, 0, 0)),
+        ];
+        for (from, next_daily, next_weekday) in cases {
+            assert_eq!(daily.next(&from), next_daily, "daily from {}", from);
+            assert_eq!(weekday.next(&from), next_weekday, "weekday from {}", from);
+        }
+        assert_eq!(daily.prev(&at(5, 0, 0, 0)), at(4, 0, 0, 0));
+        assert_eq!(daily.prev(&at(5, 0, 0, 1)), at(5, 0, 0, 0));
+        assert_eq!(weekday.prev(&at(10, 0, 0, 0)), at(7, 0, 0, 0));
+        assert_eq!(weekday.prev(&at(10, 0, 0, 1)), at(10, 0, 0, 0));
+
+        // An offset from midnight stays on the same day
+        let rc = daily.with_subinterval(30.minutes());
+        assert_eq!(rc.next(&at(4, 23, 59, 59)), at(5, 0, 30, 0));
+        assert_eq!(rc.next(&at(5, 0, 0, 0)), at(5, 0, 30, 0));
+        assert_eq!(rc.next(&at(5, 0, 30, 0)), at(6, 0, 30, 0));
+        // ...as does a time of day late in the evening
+        let rc = RunConfig::from_interval(1.day()).with_time(hms(23, 30, 0));
+        assert_eq!(rc.next(&at(5, 0, 0, 0)), at(5, 23, 30, 0));
+        assert_eq!(rc.next(&at(5, 23, 30, 0)), at(6, 23, 30, 0));
+        let rc = rc.with_subinterval(30.minutes());
+        assert_eq!(rc.next(&at(5, 0, 0, 0)), at(6, 0, 0, 0));
+        assert_eq!(rc.next(&at(5, 23, 59, 59)), at(6, 0, 0, 0));
+        assert_eq!(rc.next(&at(6, 0, 0, 0)), at(7, 0, 0, 0));
+
+        // Clocks in São Paulo went forward from midnight to 1 am on 4 November 2018, so there was no midnight
+        let sao_paulo = chrono_tz::America::Sao_Paulo;
+        let at = |d, h| sao_paulo.with_ymd_and_hms(2018, 11, d, h, 0, 0).unwrap();
+        assert_eq!(daily.next(&at(3, 12)), at(4, 1));
+        assert_eq!(daily.next(&at(4, 1)), at(5, 0));
+        assert_eq!(weekday.next(&at(2, 12)), at(5, 0));
+        assert_eq!(daily.prev(&at(4, 12)), at(4, 1));
+    }
+
+    #[test]
+    fn test_month_day_or_next_weekday() {
+        let dt = |s: &str| DateTime::parse_from_rfc3339(s).unwrap();
+        let payday = MonthDayOrNextWeekday(15);
+        // 15 April 2023 is a Saturday, so payday moves to Monday the 17th
+        assert_eq!(
+            payday.next(&dt("2023-04-01T12:00:00-00:00")),
+            dt("2023-04-17T00:00:00-00:00")
+        );
+        // 15 May 2023 is a Monday
+        assert_eq!(
+            payday.next(&dt("2023-04-17T00:00:00-00:00")),
+            dt("2023-05-15T00:00:00-00:00")
+        );
+        assert_eq!(
+            payday.prev(&dt("2023-05-15T00:00:00-00:00")),
+            dt("2023-04-17T00:00:00-00:00")
+        );
+        assert_eq!(
+            payday.prev(&dt("2023-05-15T00:00:01-00:00")),
+            dt("2023-05-15T00:00:00-00:00")
+        );
+
+        let rc = RunConfig::from_interval(payday).with_time(hms(9, 0, 0));
+        assert_eq!(
+            rc.next(&dt("2023-04-15T12:00:00-00:00")),
+            dt("2023-04-17T09:00:00-00:00")
+        );
+        assert_eq!(
+            rc.next(&dt("2023-04-17T09:00:00-00:00")),
+            dt("2023-05-15T09:00:00-00:00")
+        );
+
+        // Short months use their last day; 30 September 2023 is a Saturday
+        let month_end = MonthDayOrNextWeekday(31);
+        assert_eq!(
+            month_end.next(&dt("2023-02-01T00:00:00-00:00")),
+            dt("2023-02-28T00:00:00-00:00")
+        );
+        assert_eq!(
+            month_end.next(&dt("2023-09-29T00:00:00-00:00")),
+            dt("2023-10-02T00:00:00-00:00")
+        );
+        assert_eq!(
+            month_end.next(&dt("2023-10-02T00:00:00-00:00")),
+            dt("2023-10-31T00:00:00-00:00")
+        );
+        assert_eq!(
+            month_end.prev(&dt("2023-10-31T00:00:00-00:00")),
+            dt("2023-10-02T00:00:00-00:00")
+        );
+        assert_eq!(
+            payday.to_string(),
+            "every month on day 15, or the next weekday"
+        );
+    }
+
+    #[test]
+    fn test_from_str() {
+        let intervals = [
+            15.minutes(),
+            1.second(),
+            36.hours(),
+            2.days(),
+            1.week(),
+            Wednesday,
+            Interval::Weekday,
+            Interval::days_of_week(&[Weekday::Mon, Weekday::Fri]),
+            Interval::days_of_week(&[Weekday::Mon, Weekday::Wed, Weekday::Fri]),
+            Interval::days_of_week(&[]),
+            MonthDayOrNextWeekday(15),
+            IsoWeeks(1),
+            IsoWeeks(2),
+        ];
+        for interval in intervals {
+            assert_eq!(interval.to_string().parse(), Ok(interval));
+        }
+        assert_eq!("  Every  5 Seconds ".parse(), Ok(5.seconds()));
+        assert_eq!("day".parse(), Ok(1.day()));
+        assert_eq!("mon".parse(), Ok(Monday));
+
+        for input in [
+            "",
+            "every",
+            "5",
+            "five minutes",
+            "5 fortnights",
+            "-5 minutes",
+            "monday and",
+        ] {
+            assert_eq!(
+                input.parse::<Interval>(),
+                Err(IntervalError::Unrecognized {
+                    input: input.into()
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_env() {
+        std::env::set_var("CLOKWERK_TEST_INTERVAL", "every 10 minutes");
+        assert_eq!(
+            Interval::from_env("CLOKWERK_TEST_INTERVAL"),
+            Ok(10.minutes())
+        );
+        std::env::set_var("CLOKWERK_TEST_INTERVAL", "sometimes");
+        assert_eq!(
+            Interval::from_env("CLOKWERK_TEST_INTERVAL"),
+            Err(IntervalError::Unrecognized {
+                input: "sometimes".into()
+            })
+        );
+        std::env::remove_var("CLOKWERK_TEST_INTERVAL");
+        assert_eq!(
+            Interval::from_env("CLOKWERK_TEST_INTERVAL"),
+            Err(IntervalError::MissingVariable {
+                name: "CLOKWERK_TEST_INTERVAL".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_days_and_weeks_outside_common_era() {
+        let dates = [
+            Utc.with_ymd_and_hms(-1, 6, 15, 12, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(0, 12, 30, 12, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(1, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(1, 1, 2, 12, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(200_000, 3, 4, 12, 0, 0).unwrap(),
+        ];
+        for ival in [3.days(), 7.days(), 1.week(), 2.weeks(), 5.weeks()] {
+            let period = ival.fixed_duration().unwrap();
+            for from in dates {
+                let next = ival.next(&from);
+                assert!(
+                    next > from && next - from <= period,
+                    "{:?} from {}",
+                    ival,
+                    from
+                );
+                assert_eq!(ival.next(&next) - next, period, "{:?} from {}", ival, from);
+                let prev = ival.prev(&next);
+                assert_eq!(next - prev, period, "{:?} from {}", ival, from);
+                let before = ival.prev(&from);
+                assert!(
+                    before < from && ival.next(&before) >= from,
+                    "{:?} from {}",
+                    ival,
+                    from
+                );
+            }
+        }
+
+        // Day 0 of the era is 31 December 1 BCE
+        let from = Utc.with_ymd_and_hms(0, 12, 30, 12, 0, 0).unwrap();
+        assert_eq!(
+            3.days().next(&from),
+            Utc.with_ymd_and_hms(0, 12, 31, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            3.days().prev(&from),
+            Utc.with_ymd_and_hms(0, 12, 28, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_constructors() {
+        assert_eq!(Interval::seconds(5), 5.seconds());
+        assert_eq!(Interval::minutes(10), 10.minutes());
+        assert_eq!(Interval::hours(2), 2.hours());
+        assert_eq!(Interval::days(3), 3.days());
+        assert_eq!(Interval::weeks(1), 1.week());
+        assert_eq!(Interval::iso_weeks(2), IsoWeeks(2));
+
+        let mut weekday = chrono::Weekday::Mon;
+        for day in 0..7 {
+            assert_eq!(weekday.num_days_from_monday(), u32::from(day));
+            assert_eq!(Interval::from_weekday_index(day), Some(weekday.into()));
+            weekday = weekday.succ();
+        }
+        for day in [7, 8, 100, u8::MAX] {
+            assert_eq!(Interval::from_weekday_index(day), None);
+        }
+    }
+
+    #[test]
+    fn test_iso_weeks() {
+        let at = |y, m, d, h| Utc.with_ymd_and_hms(y, m, d, h, 0, 0).unwrap();
+        // 2020 has 53 ISO weeks; week 53 starts on 28 December, and week 1 of 2021 on 4 January
+        let fortnightly = IsoWeeks(2);
+        assert_eq!(fortnightly.next(&at(2020, 12, 20, 12)), at(2020, 12, 28, 0));
+        assert_eq!(fortnightly.next(&at(2020, 12, 28, 0)), at(2021, 1, 4, 0));
+        assert_eq!(fortnightly.next(&at(2021, 1, 1, 12)), at(2021, 1, 4, 0));
+        assert_eq!(fortnightly.next(&at(2021, 1, 4, 0)), at(2021, 1, 18, 0));
+        assert_eq!(fortnightly.prev(&at(2021, 1, 4, 0)), at(2020, 12, 28, 0));
+        assert_eq!(fortnightly.prev(&at(2021, 1, 10, 12)), at(2021, 1, 4, 0));
+        assert_eq!(fortnightly.prev(&at(2021, 1, 18, 12)), at(2021, 1, 18, 0));
+        // `Weeks` carries on counting across the year
+        assert_eq!(2.weeks().next(&at(2020, 12, 28, 0)), at(2021, 1, 11, 0));
+
+        // Week 1 of 2025 starts on 30 December 2024, in the previous calendar year
+        let every_third = IsoWeeks(3);
+        assert_eq!(every_third.next(&at(2024, 12, 20, 12)), at(2024, 12, 23, 0));
+        assert_eq!(every_third.next(&at(2024, 12, 23, 0)), at(2024, 12, 30, 0));
+        assert_eq!(every_third.next(&at(2024, 12, 30, 0)), at(2025, 1, 20, 0));
+        assert_eq!(every_third.prev(&at(2025, 1, 1, 0)), at(2024, 12, 30, 0));
+        assert_eq!(every_third.prev(&at(2024, 12, 30, 0)), at(2024, 12, 23, 0));
+
+        // Only week 1 of each year is a multiple of a very long interval
+        assert_eq!(IsoWeeks(100).next(&at(2021, 1, 4, 0)), at(2022, 1, 3, 0));
+        assert_eq!(IsoWeeks(100).prev(&at(2021, 1, 4, 0)), at(2019, 12, 30, 0));
+
+        let rc = RunConfig::from_interval(fortnightly).with_time(hms(9, 0, 0));
+        assert_eq!(rc.next(&at(2020, 12, 28, 10)), at(2021, 1, 4, 9));
+        assert_eq!(rc.prev(&at(2021, 1, 4, 8)), at(2020, 12, 28, 9));
+
+        assert_eq!(IsoWeeks(1).to_string(), "every 1 ISO week");
+        assert_eq!(fortnightly.to_string(), "every 2 ISO weeks");
+        assert_eq!("iso week".parse(), Ok(IsoWeeks(1)));
+        assert!(MonthDayOrNextWeekday(31) < IsoWeeks(1));
+    }
+
+    #[test]
+    fn test_fires_before() {
+        // 12 May 2021 is a Wednesday
+        let at = |d, h| Utc.with_ymd_and_hms(2021, 5, d, h, 0, 0).unwrap();
+        let wednesday = at(12, 10);
+        assert!(Hours(1).fires_before(&Thursday, &wednesday));
+        assert!(!Thursday.fires_before(&Hours(1), &wednesday));
+        assert!(Thursday.fires_before(&Weeks(1), &wednesday));
+        assert!(Weekday.fires_before(&Friday, &wednesday));
+        // Saturday fires sooner from a Friday, despite sorting after `Days(2)`
+        assert!(Saturday.fires_before(&Days(2), &at(14, 10)));
+        assert!(Days(2) < Saturday);
+        assert!(!Saturday.fires_before(&Days(3), &at(14, 10)));
+        // Both next fire at midnight on Thursday
+        assert!(!Days(1).fires_before(&Thursday, &wednesday));
+        assert!(!Thursday.fires_before(&Days(1), &wednesday));
+        assert!(!Minutes(5).fires_before(&Minutes(5), &wednesday));
+    }
+
+    #[test]
+    fn test_normalized() {
+        assert_eq!(Seconds(3600).normalized(), Hours(1));
+        assert_eq!(Seconds(120).normalized(), Minutes(2));
+        assert_eq!(Seconds(5400).normalized(), Minutes(90));
+        assert_eq!(Seconds(90).normalized(), Seconds(90));
+        assert_eq!(Minutes(120).normalized(), Hours(2));
+        assert_eq!(Minutes(90).normalized(), Minutes(90));
+        assert_eq!(Hours(5).normalized(), Hours(5));
+
+        // Exactly one day becomes `Days(1)`, but longer intervals keep their alignment
+        assert_eq!(Seconds(86400).normalized(), Days(1));
+        assert_eq!(Minutes(1440).normalized(), Days(1));
+        assert_eq!(Hours(24).normalized(), Days(1));
+        assert_eq!(Hours(48).normalized(), Hours(48));
+        assert_eq!(Seconds(2 * 86400).normalized(), Hours(48));
+        assert_eq!(Days(7).normalized(), Days(7));
+        assert_eq!(Weeks(2).normalized(), Weeks(2));
+
+        assert_eq!(Seconds(0).normalized(), Seconds(0));
+        assert_eq!(Minutes(0).normalized(), Minutes(0));
+        assert_eq!(Monday.normalized(), Monday);
+        assert_eq!(IsoWeeks(2).normalized(), IsoWeeks(2));
+
+        // The promoted intervals run at the same times
+        let at = |d, h, m, s| Utc.with_ymd_and_hms(2021, 5, d, h, m, s).unwrap();
+        for ival in [Seconds(3600), Seconds(120), Minutes(120), Hours(24)].iter() {
+            for from in [at(12, 0, 0, 0), at(12, 10, 59, 30), at(12, 23, 59, 59)].iter() {
+                assert_eq!(ival.next(from), ival.normalized().next(from), "{:?}", ival);
+                assert_eq!(ival.prev(from), ival.normalized().prev(from), "{:?}", ival);
+            }
+        }
+        // Which is why these aren't promoted
+        assert_ne!(
+            Hours(48).next(&at(13, 10, 0, 0)),
+            Days(2).next(&at(13, 10, 0, 0))
+        );
+        assert_ne!(
+            Days(7).next(&at(12, 10, 0, 0)),
+            Weeks(1).next(&at(12, 10, 0, 0))
+        );
+    }
 }
@@ -1,7 +1,10 @@
-use crate::job_schedule::{Repeating, WithSchedule};
+use crate::job_schedule::{CatchUp, FireTimes, JobId, Repeating, ScheduleConfig, WithSchedule};
 
-use crate::{timeprovider::TimeProvider, Interval};
+use crate::{
+    timeprovider::TimeProvider, AtError, Interval, RunConfig, ScheduleError, ScheduleWarning,
+};
 use chrono::prelude::*;
+use std::collections::BTreeMap;
 
 /// This trait provides an abstraction over [`SyncJob`](crate::SyncJob) and [`AsyncJob`](crate::AsyncJob), covering all the methods relating to scheduling, rather than execution.
 pub trait Job<Tz, Tp>: WithSchedule<Tz, Tp> + Sized
@@ -20,28 +23,30 @@ where
     /// ```
     /// Times can be specified using strings, with or without seconds, and in either 24-hour or 12-hour time.
     /// They can also be any other type that implements `TryInto<ClokwerkTime>`, which includes [`chrono::NaiveTime`].
-    /// This method will panic if TryInto fails, e.g. because the time string could not be parsed.
-    /// If the value comes from an untrusted source, e.g. user input, [`Job::try_at`] will return a result instead.
+    /// If the time string can't be parsed, this doesn't panic straight away: giving the job its task
+    /// (e.g. with [`SyncJob::run`](crate::SyncJob::run)) will. The panic message includes the string that could not be parsed.
+    /// If the value comes from an untrusted source, e.g. user input, [`Job::try_at`] will return a result instead,
+    /// or [`Job::try_build`] can check the whole job before it's run.
     ///
-    /// This method is mutually exclusive with [`Job::plus()`].
+    /// This can be combined with [`Job::plus()`]; see there for details.
     fn at(&mut self, time: &str) -> &mut Self {
-        self.schedule_mut()
-            .try_at(time)
-            .expect("Could not convert value into a time");
+        self.schedule_mut().at(time);
         self
     }
 
-    /// Identical to [`Job::at`] except that it returns a Result instead of panicking if the conversion failed.
+    /// Identical to [`Job::at`] except that it returns a Result straight away if the conversion failed,
+    /// rather than panicking once the job is given its task.
+    /// The returned [`AtError`](crate::AtError) carries the string that could not be parsed.
     /// ```rust
     /// # use clokwerk::*;
     /// # use clokwerk::Interval::*;
     /// let mut scheduler = Scheduler::new();
     /// scheduler.every(1.day()).try_at("14:32")?.run(|| println!("Tea time!"));
-    /// # Ok::<(), chrono::ParseError>(())
+    /// # Ok::<(), AtError>(())
     /// ```
     /// Times can be specified with or without seconds, and in either 24-hour or 12-hour time.
-    /// Mutually exclusive with [`Job::plus()`].
-    fn try_at(&mut self, time: &str) -> Result<&mut Self, chrono::ParseError> {
+    /// Can be combined with [`Job::plus()`].
+    fn try_at(&mut self, time: &str) -> Result<&mut Self, AtError> {
         self.schedule_mut().try_at(time)?;
         Ok(self)
     }
@@ -53,13 +58,142 @@ where
     /// # use clokwerk::Interval::*;
     /// # use chrono::NaiveTime;
     /// let mut scheduler = Scheduler::new();
-    /// scheduler.every(Weekday).at_time(NaiveTime::from_hms(23, 42, 16)).run(|| println!("Also works with NaiveTime"));
+    /// scheduler.every(Weekday).at_time(NaiveTime::from_hms_opt(23, 42, 16).unwrap()).run(|| println!("Also works with NaiveTime"));
     /// ```
-
     fn at_time(&mut self, time: NaiveTime) -> &mut Self {
         self.schedule_mut().at_time(time);
         self
     }
+    /// Run the task at each of several times of day, e.g. at 9 AM, noon and 5 PM on weekdays.
+    /// This is the same as calling [`Job::and_every`] with the same interval for each time after the first.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use clokwerk::Interval::*;
+    /// # use chrono::NaiveTime;
+    /// let times = ["09:00", "12:00", "17:00"].map(|t| NaiveTime::parse_from_str(t, "%H:%M").unwrap());
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(Weekday).at_times(&times).run(|| println!("Stand up and stretch"));
+    /// ```
+    /// Offsets added with [`Job::plus()`] beforehand apply to every time, while those added afterwards only apply to the last one.
+    /// An empty list of times leaves the job unchanged.
+    fn at_times(&mut self, times: &[NaiveTime]) -> &mut Self {
+        self.schedule_mut().at_times(times);
+        self
+    }
+
+    /// Run the task at a random time between `start` and `end`, rather than at a fixed time.
+    /// A new time is picked for each run, which helps spread out load, e.g. from many machines
+    /// running the same job. The window can cross midnight, e.g. from 11 pm to 1 am.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use chrono::NaiveTime;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler
+    ///     .every(1.day())
+    ///     .at_random_between(
+    ///         NaiveTime::from_hms_opt(2, 0, 0).unwrap(),
+    ///         NaiveTime::from_hms_opt(4, 0, 0).unwrap(),
+    ///     )
+    ///     .run(|| println!("Some time between 2 and 4 am"));
+    /// ```
+    /// If the job is scheduled while its window is open, it first runs in that window if the time
+    /// picked is still to come, and otherwise in the next one.
+    ///
+    /// The random delay applies to the job's whole schedule, so any frequencies added with
+    /// [`Job::and_every()`] are also delayed from their usual times by the same random amount.
+    fn at_random_between(&mut self, start: NaiveTime, end: NaiveTime) -> &mut Self {
+        self.schedule_mut().at_random_between(start, end);
+        self
+    }
+
+    /// Run the task every `step` from `start` until `end` (inclusive), in each period given by the
+    /// job's interval. For example, this runs at 8 am, 10 am, and so on up to 8 pm, every day:
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use chrono::NaiveTime;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler
+    ///     .every(1.day())
+    ///     .every_step_in_window(
+    ///         2.hours(),
+    ///         NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+    ///         NaiveTime::from_hms_opt(20, 0, 0).unwrap(),
+    ///     )
+    ///     .run(|| println!("Every two hours during the day"));
+    /// ```
+    /// The window can cross midnight, e.g. from 10 pm to 2 am.
+    ///
+    /// # Panics
+    /// Panics if `step` is zero, or isn't a fixed-length interval (i.e. is a day of the week).
+    fn every_step_in_window(
+        &mut self,
+        step: Interval,
+        start: NaiveTime,
+        end: NaiveTime,
+    ) -> &mut Self {
+        self.schedule_mut().every_step_in_window(step, start, end);
+        self
+    }
+
+    /// Only run the job on weekdays between `open` and `close`, in the time zone `tz`, which can be different
+    /// from the scheduler's. Runs that would fall outside these hours are skipped, and the job next runs at its
+    /// first scheduled time after the hours open again.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use chrono::{FixedOffset, NaiveTime};
+    /// let mut scheduler = Scheduler::with_tz(chrono::Utc);
+    /// let new_york = FixedOffset::west_opt(5 * 3600).unwrap();
+    /// scheduler
+    ///     .every(15.minutes())
+    ///     .business_hours(
+    ///         new_york,
+    ///         NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+    ///         NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+    ///     )
+    ///     .run(|| println!("Checking the support queue"));
+    /// ```
+    /// The job can run at `open`, but not at `close`. The hours can cross midnight, e.g. from 10 pm to 6 am,
+    /// in which case they belong to the day they open on: Friday night's hours run into Saturday morning.
+    /// If `open` and `close` are the same, the job can run at any time on a weekday.
+    ///
+    /// This applies to the job's regular schedule, rather than to repeats from [`Job::repeating_every`],
+    /// missed runs replayed with [`CatchUp::RunAll`](crate::CatchUp::RunAll), or [`Job::run_immediately`].
+    fn business_hours<BTz>(&mut self, tz: BTz, open: NaiveTime, close: NaiveTime) -> &mut Self
+    where
+        BTz: chrono::TimeZone + Send + Sync + 'static,
+    {
+        self.schedule_mut().business_hours(tz, open, close);
+        self
+    }
+
+    /// Don't run the job on any of `days`. Runs that would fall on one of them move to the job's first
+    /// scheduled time on a day that isn't excluded. For example, this runs at 5 pm from Monday to Thursday:
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use clokwerk::Interval::*;
+    /// # use chrono::Weekday::Fri;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler
+    ///     .every(Weekday)
+    ///     .at("17:00")
+    ///     .exclude_weekdays(&[Fri])
+    ///     .run(|| println!("Daily wrap-up"));
+    /// ```
+    /// Days are in the scheduler's time zone. Calling this again excludes more days, rather than replacing them.
+    /// Like [`Job::business_hours`], this applies to the job's regular schedule, but not to repeats,
+    /// replayed missed runs, or [`Job::run_immediately`].
+    fn exclude_weekdays(&mut self, days: &[chrono::Weekday]) -> &mut Self {
+        self.schedule_mut().exclude_weekdays(days);
+        self
+    }
+
+    /// Seed the random number generator used by [`Job::at_random_between`] and [`Job::with_probability`],
+    /// so that the job runs at the same sequence of times each time the program is run. Call this before [`SyncJob::run`](crate::SyncJob::run).
+    fn random_seed(&mut self, seed: u64) -> &mut Self {
+        self.schedule_mut().random_seed(seed);
+        self
+    }
+
     /// Specifies an offset to when a task should run, e.g.
     /// ```rust
     /// # use clokwerk::*;
@@ -70,7 +204,17 @@ where
     ///     .plus(13.minutes())
     ///   .run(|| println!("Time to wake up!"));
     /// ```
-    /// Mutually exclusive with [`Job::at()`].
+    /// If combined with [`Job::at()`], the offset is added to the time of day, regardless of the order
+    /// in which the two are called. For instance,
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.day())
+    ///     .at("09:00")
+    ///     .plus(30.seconds())
+    ///   .run(|| println!("Staggered start"));
+    /// ```
+    /// will run at 09:00:30 every day.
     ///
     /// Note that this normally won't change the frequency with which a task runs, merely its timing.
     /// For instance,
@@ -117,13 +261,159 @@ where
         self
     }
 
+    /// Run the job every `interval`, `phase` after each boundary of the interval. This replaces the
+    /// interval the job was created with, along with any time of day or offsets given for it.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.hour())
+    ///   .every_with_phase(15.minutes(), 5.minutes())
+    ///   .run(|| println!("It's 5, 20, 35 or 50 minutes past the hour"));
+    /// ```
+    /// Unlike [`Job::plus`], the phase is added exactly: with a phase of 7 minutes, the job runs at :07, :22, :37 and :52.
+    ///
+    /// Both `interval` and `phase` must be fixed-length, with `phase` shorter than `interval`. If they aren't, the job
+    /// keeps its schedule, and giving it its task (e.g. with [`SyncJob::run`](crate::SyncJob::run)) panics;
+    /// [`Job::try_build`] reports the problem instead.
+    fn every_with_phase(&mut self, interval: Interval, phase: Interval) -> &mut Self {
+        self.schedule_mut().every_with_phase(interval, phase);
+        self
+    }
+
+    /// Run the job every other day, counting from today. This replaces the interval the job was created with,
+    /// along with any time of day or offsets given for it.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.day()).every_other_day().at("18:00").run(|| println!("Water the plants"));
+    /// ```
+    /// Unlike `every(2.days())`, which runs on alternate days counted from a fixed date, this runs on today's
+    /// date, then two days later, and so on. The run today only happens if its time of day, given with
+    /// [`Job::at`], is still to come; without one the job runs at midnight, so the first run is the day after tomorrow.
+    /// [`Scheduler::every_other_day`](crate::Scheduler::every_other_day) is a shorthand for this.
+    fn every_other_day(&mut self) -> &mut Self {
+        self.schedule_mut().every_other_day();
+        self
+    }
+
+    /// Run the job on `day` every other week, counting from the next `day` (or today, if it's `day`).
+    /// This replaces the interval the job was created with, along with any time of day or offsets given for it.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use chrono::Weekday;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.day()).every_other_week_on(Weekday::Tue).at("10:00").run(|| println!("Sprint planning"));
+    /// ```
+    /// Unlike `every(2.weeks())`, which runs on the Monday of alternate weeks counted from a fixed date,
+    /// this runs this Tuesday, then two weeks later, and so on. If today is `day`, the run today only happens if
+    /// its time of day is still to come; without [`Job::at`] the first run is two weeks from today.
+    /// [`Scheduler::every_other_week_on`](crate::Scheduler::every_other_week_on) is a shorthand for this.
+    fn every_other_week_on(&mut self, day: Weekday) -> &mut Self {
+        self.schedule_mut().every_other_week_on(day);
+        self
+    }
+
     /// Add an additional scheduling to the task. All schedules will be considered when determining
     /// when the task should next run.
+    ///
+    /// The job runs at whichever schedule comes soonest. This means that combining a day of the week with
+    /// a short interval probably won't do what you want:
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use clokwerk::Interval::*;
+    /// let mut scheduler = Scheduler::new();
+    /// let job = scheduler.every(Monday).and_every(15.minutes());
+    /// assert!(!job.validate().is_empty());
+    /// ```
+    /// runs every 15 minutes, every day of the week. [`Job::validate`] reports combinations like this.
     fn and_every(&mut self, ival: Interval) -> &mut Self {
         self.schedule_mut().and_every(ival);
         self
     }
 
+    /// Check the job for mistakes before running it: times passed to [`Job::at`] that couldn't be parsed,
+    /// phases passed to [`Job::every_with_phase`] that don't fit their interval, and any problems found by [`Job::validate`]. The error lists all of them, so that a schedule built from
+    /// configuration can be rejected with a complete explanation.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// let job = scheduler.every(1.day()).at("25:00");
+    /// let err = job.try_build().unwrap_err();
+    /// assert!(matches!(&err.errors()[0], BuildError::At(e) if e.input() == "25:00"));
+    ///
+    /// let job = scheduler.every(1.day()).at("3:00 pm");
+    /// job.try_build()?;
+    /// job.run(|| println!("Tea time"));
+    /// # Ok::<(), ScheduleError>(())
+    /// ```
+    fn try_build(&self) -> Result<(), ScheduleError> {
+        self.schedule().try_build()
+    }
+
+    /// Check the job's schedule for combinations that are permitted, but probably don't do what was intended.
+    /// See [`ScheduleWarning`](crate::ScheduleWarning) for the possible problems.
+    fn validate(&self) -> Vec<ScheduleWarning> {
+        self.schedule().validate()
+    }
+
+    /// Describe in English what the job's schedule does, taking into account all of the ways it has been
+    /// changed since it was created, e.g. to check that a complicated schedule does what was intended.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use clokwerk::Interval::*;
+    /// let mut scheduler = Scheduler::new();
+    /// let job = scheduler
+    ///     .every(Weekday)
+    ///     .at("09:00")
+    ///     .repeating_every(10.minutes())
+    ///     .times(5)
+    ///     .max_per_day(3);
+    /// assert_eq!(
+    ///     job.explain(),
+    ///     "Every weekday at 09:00, repeating every 10 minutes 5 times, at most 3 times a day."
+    /// );
+    /// ```
+    /// Only settings that differ from the defaults are included. Callbacks, labels and other metadata aren't.
+    fn explain(&self) -> String {
+        self.schedule().explain()
+    }
+
+    /// The most recent time before `from` at which the job was scheduled to run, whether or not it actually ran then.
+    /// Returns `None` if the job has no more runs left.
+    ///
+    /// Comparing this with the time the job last ran can be used to detect runs that were missed, e.g. during downtime.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use clokwerk::Interval::*;
+    /// # use chrono::prelude::*;
+    /// let mut scheduler = Scheduler::with_tz(Utc);
+    /// let job = scheduler.every(1.day()).at("09:00");
+    /// let now = Utc.with_ymd_and_hms(2021, 5, 12, 8, 0, 0).unwrap();
+    /// assert_eq!(job.scheduled_prev(now), Some(Utc.with_ymd_and_hms(2021, 5, 11, 9, 0, 0).unwrap()));
+    /// ```
+    fn scheduled_prev(&self, from: DateTime<Tz>) -> Option<DateTime<Tz>> {
+        self.schedule().scheduled_prev(from)
+    }
+
+    /// The times at which the job is scheduled to run after `from`, assuming that every run happens on time.
+    /// This takes into account [`Job::count`] and [`Job::repeating_every`], and ends after the job's last run.
+    /// For jobs that run forever, the iterator never ends, so limit it with e.g. [`Iterator::take`].
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use chrono::prelude::*;
+    /// let mut scheduler = Scheduler::with_tz(Utc);
+    /// let job = scheduler.every(1.day()).at("09:00");
+    /// let from = Utc.with_ymd_and_hms(2021, 5, 12, 10, 0, 0).unwrap();
+    /// let upcoming: Vec<_> = job.fire_times(from).take(2).collect();
+    /// assert_eq!(upcoming, vec![
+    ///     Utc.with_ymd_and_hms(2021, 5, 13, 9, 0, 0).unwrap(),
+    ///     Utc.with_ymd_and_hms(2021, 5, 14, 9, 0, 0).unwrap(),
+    /// ]);
+    /// ```
+    fn fire_times(&self, from: DateTime<Tz>) -> FireTimes<Tz> {
+        self.schedule().fire_times(from)
+    }
+
     /// Execute the job only once. Equivalent to `_.count(1)`.
     fn once(&mut self) -> &mut Self {
         self.schedule_mut().once();
@@ -142,6 +432,542 @@ where
         self
     }
 
+    /// Start the job's schedule again, as if it had just been created, e.g. to run a job limited with
+    /// [`Job::count`] again after it's finished. The job keeps its label and the rest of its configuration.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// let job = scheduler.every(1.hour()).count(3).run(|| println!("Reminder"));
+    /// // ...after the job has run three times
+    /// job.reset();
+    /// assert_eq!(job.runs_remaining(), Some(3));
+    /// ```
+    /// This also re-enables a job stopped by [`Job::disable_after_failures`]. The callbacks given to
+    /// [`Job::on_finished`] and [`Job::on_disabled`] are only ever called once, so won't be called again.
+    fn reset(&mut self) -> &mut Self {
+        self.schedule_mut().reset();
+        self
+    }
+
+    /// How many more times the job will run, or `None` if it runs forever.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// let job = scheduler.every(1.hour()).count(3);
+    /// assert_eq!(job.runs_remaining(), Some(3));
+    /// ```
+    fn runs_remaining(&self) -> Option<usize> {
+        self.schedule().runs_remaining()
+    }
+
+    /// This job's unique ID.
+    fn id(&self) -> JobId {
+        self.schedule().id()
+    }
+
+    /// The schedules this job runs on: one for the interval passed to `every`, and one more for each
+    /// call to [`Job::and_every`]. Useful for checking that a job was built as intended.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use chrono::NaiveTime;
+    /// let mut scheduler = Scheduler::new();
+    /// let job = scheduler
+    ///     .every(1.day())
+    ///     .at("9:00 am")
+    ///     .and_every(Interval::Saturday)
+    ///     .plus(30.minutes());
+    /// let frequencies = job.frequencies();
+    /// assert_eq!(frequencies.len(), 2);
+    /// assert_eq!(frequencies[0].base(), 1.day());
+    /// assert_eq!(frequencies[0].time(), NaiveTime::from_hms_opt(9, 0, 0));
+    /// assert_eq!(frequencies[1].base(), Interval::Saturday);
+    /// assert_eq!(frequencies[1].offsets(), &[30.minutes()]);
+    /// ```
+    fn frequencies<'a>(&'a self) -> &'a [RunConfig]
+    where
+        Tz: 'a,
+        Tp: 'a,
+    {
+        self.schedule().frequencies()
+    }
+
+    /// Get a copy of this job's schedule, to create similar jobs with [`Scheduler::every_like`](crate::Scheduler::every_like).
+    /// This includes the job's intervals, times, repeats and remaining runs, but not its label, group, description or tags.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// let config = scheduler
+    ///     .every(1.day())
+    ///     .at("9:00 am")
+    ///     .repeating_every(30.minutes())
+    ///     .times(4)
+    ///     .run(|| println!("Check the mail"))
+    ///     .schedule_config();
+    /// scheduler.every_like(&config, || println!("Check the news"));
+    /// ```
+    fn schedule_config(&self) -> ScheduleConfig {
+        self.schedule().schedule_config()
+    }
+
+    /// Run the job as soon as the scheduler is next polled, rather than waiting for the first interval boundary.
+    /// Subsequent runs follow the schedule as usual, so this job runs now, and then at the start of every hour:
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.hour())
+    ///   .run_immediately()
+    ///   .run(|| println!("Warming the cache"));
+    /// ```
+    /// Adjustments like [`Job::at`] and [`Job::plus`] only apply to the later runs: the first one is still immediate.
+    /// The immediate run counts towards any limit set with [`Job::count`].
+    fn run_immediately(&mut self) -> &mut Self {
+        self.schedule_mut().run_immediately();
+        self
+    }
+
+    /// Count each run from when the previous one happened, rather than aligning runs to the interval.
+    ///
+    /// Normally, runs line up with the interval: a job added at 10:20 with
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.hour()).run(|| println!("On the hour"));
+    /// ```
+    /// first runs at 11:00, then 12:00, 13:00, etc. With this option,
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.hour()).relative_to_start().run(|| println!("An hour after the last run"));
+    /// ```
+    /// first runs at 11:20, an hour after it was added, and then an hour after each run. Since each run is
+    /// timed from when it actually happened, runs will drift later by however late the scheduler is in running them.
+    ///
+    /// Offsets from [`Job::plus`] are added on to each run as usual, but any time set with [`Job::at`] is ignored.
+    /// Day-of-week intervals like `Monday` still run at midnight.
+    fn relative_to_start(&mut self) -> &mut Self {
+        self.schedule_mut().relative_to_start();
+        self
+    }
+
+    /// Like [`Job::relative_to_start`], but count each run from the start of the last run that succeeded,
+    /// rather than the last run. For jobs started with `run_fallible`, this keeps failed runs from
+    /// shifting the job's cadence: after a failure, the job runs next when it would have run had that run not happened.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # fn sync_mailbox() -> Result<(), std::io::Error> { Ok(()) }
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.hour())
+    ///   .cadence_from_success()
+    ///   .run_fallible(sync_mailbox);
+    /// ```
+    /// Until a run succeeds, runs are counted from the start of the previous run.
+    /// A run of an [`AsyncJob`](crate::AsyncJob) only counts as successful once its future completes,
+    /// which is after the job has been rescheduled, so each run is timed from an earlier success.
+    fn cadence_from_success(&mut self) -> &mut Self {
+        self.schedule_mut().cadence_from_success();
+        self
+    }
+
+    /// When the last successful run of the job started, if any run has succeeded. Every run of a job that can't
+    /// fail is successful. See [`Job::cadence_from_success`].
+    fn last_success(&self) -> Option<DateTime<Tz>> {
+        self.schedule().last_success()
+    }
+
+    /// Run at whole multiples of the interval after the job was added, rather than aligning runs to the interval.
+    ///
+    /// Normally, a job added at 10:20 with `every(1.hour())` runs at 11:00, 12:00, 13:00, etc. With this option,
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.hour()).from_registration().run(|| println!("At 20 past"));
+    /// ```
+    /// runs at 11:20, 12:20, 13:20, etc. Unlike [`Job::relative_to_start`], runs are timed from when the job was added,
+    /// rather than from the previous run, so they don't drift later if the scheduler is late in running them.
+    ///
+    /// As with [`Job::relative_to_start`], offsets from [`Job::plus`] are added to the interval, any time set
+    /// with [`Job::at`] is ignored, and day-of-week intervals like `Monday` still run at midnight.
+    #[allow(clippy::wrong_self_convention)]
+    fn from_registration(&mut self) -> &mut Self {
+        self.schedule_mut().from_registration();
+        self
+    }
+
+    /// Give the job a label, to identify it. Labels don't need to be unique.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// let job = scheduler.every(1.day()).at("2:00").label("backup");
+    /// assert_eq!(job.get_label(), Some("backup"));
+    /// ```
+    fn label(&mut self, label: &str) -> &mut Self {
+        self.schedule_mut().label(label);
+        self
+    }
+
+    /// The job's label, if it has one. See [`Job::label`].
+    fn get_label<'a>(&'a self) -> Option<&'a str>
+    where
+        Tz: 'a,
+        Tp: 'a,
+    {
+        self.schedule().get_label()
+    }
+
+    /// Describe what the job does, e.g. for an admin page listing the scheduled jobs.
+    /// The description is included when the job's schedule is serialized with the `serde` feature.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// let job = scheduler.every(1.day()).at("2:00").describe("Copies the database to S3");
+    /// assert_eq!(job.get_description(), Some("Copies the database to S3"));
+    /// ```
+    fn describe(&mut self, description: &str) -> &mut Self {
+        self.schedule_mut().describe(description);
+        self
+    }
+
+    /// The job's description, if it has one. See [`Job::describe`].
+    fn get_description<'a>(&'a self) -> Option<&'a str>
+    where
+        Tz: 'a,
+        Tp: 'a,
+    {
+        self.schedule().get_description()
+    }
+
+    /// Tag the job with `key` set to `value`, e.g. to filter jobs by owner in admin tooling.
+    /// Tagging the job with the same key again replaces the value.
+    /// Tags are included when the job's schedule is serialized with the `serde` feature.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// let job = scheduler.every(1.hour()).tag("team", "search").tag("tier", "critical");
+    /// assert_eq!(job.get_tag("team"), Some("search"));
+    /// assert_eq!(job.tags().len(), 2);
+    /// ```
+    fn tag(&mut self, key: &str, value: &str) -> &mut Self {
+        self.schedule_mut().tag(key, value);
+        self
+    }
+
+    /// The value of the job's tag `key`, if it has one. See [`Job::tag`].
+    fn get_tag<'a>(&'a self, key: &str) -> Option<&'a str>
+    where
+        Tz: 'a,
+        Tp: 'a,
+    {
+        self.schedule().get_tag(key)
+    }
+
+    /// All of the job's tags, ordered by key. See [`Job::tag`].
+    fn tags<'a>(&'a self) -> &'a BTreeMap<String, String>
+    where
+        Tz: 'a,
+        Tp: 'a,
+    {
+        self.schedule().tags()
+    }
+
+    /// Set the job's priority. When several jobs are due at the same time, jobs with a higher priority
+    /// run first, e.g. so that a critical job gets to a shared resource before the others.
+    /// Jobs with the same priority run in the order they were added. The default priority is 0.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.hour()).run(|| println!("Runs second"));
+    /// scheduler.every(1.hour()).priority(10).run(|| println!("Runs first"));
+    /// ```
+    fn priority(&mut self, priority: i32) -> &mut Self {
+        self.schedule_mut().priority(priority);
+        self
+    }
+
+    /// Add the job to a named group, so that it can be paused, resumed or removed along with the other
+    /// jobs in the group, e.g. with [`Scheduler::pause_group`](crate::Scheduler::pause_group).
+    /// A job can be in at most one group; calling this again moves it to another group.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.day()).at("2:00").group("maintenance").run(|| println!("Vacuuming"));
+    /// scheduler.every(1.week()).group("maintenance").run(|| println!("Rotating logs"));
+    /// scheduler.pause_group("maintenance");
+    /// ```
+    fn group(&mut self, name: &str) -> &mut Self {
+        self.schedule_mut().group(name);
+        self
+    }
+
+    /// Don't run the job until the scheduler has been running for `uptime`.
+    /// The job first runs at its first scheduled time after that, or as soon as `uptime` has passed if
+    /// [`Job::run_immediately`] is also used.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use std::time::Duration;
+    /// let mut scheduler = Scheduler::new();
+    /// // Five minutes after starting, then every hour on the hour
+    /// scheduler.every(1.hour())
+    ///   .after_uptime(Duration::from_secs(5 * 60))
+    ///   .run_immediately()
+    ///   .run(|| println!("Diagnostics"));
+    /// ```
+    /// Uptime is measured from when the scheduler was created, or the instant given to
+    /// [`Scheduler::with_start_instant`](crate::Scheduler::with_start_instant).
+    /// For an [`AsyncScheduler`](crate::AsyncScheduler), it's measured from when the job was created.
+    /// Uptime goes by the same monotonic clock as
+    /// [`Scheduler::use_monotonic_for_intervals`](crate::Scheduler::use_monotonic_for_intervals), so
+    /// [`Scheduler::advance`](crate::Scheduler::advance) counts towards it.
+    fn after_uptime(&mut self, uptime: std::time::Duration) -> &mut Self {
+        self.schedule_mut().after_uptime(uptime);
+        self
+    }
+
+    /// First run the job `delay` after it's added, rather than at its first scheduled time. After that,
+    /// it follows its schedule as usual.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use std::time::Duration;
+    /// let mut scheduler = Scheduler::new();
+    /// // 90 seconds after being added, then every 5 minutes on the 5 minutes
+    /// scheduler.every(5.minutes())
+    ///   .initial_delay(Duration::from_secs(90))
+    ///   .run(|| println!("Syncing"));
+    /// ```
+    /// The delay is counted from when the job's task is given, e.g. with [`SyncJob::run`](crate::SyncJob::run),
+    /// or from when it's reset with [`Job::reset`]. Unlike [`Job::after_uptime`], this doesn't depend on
+    /// how long the scheduler has been running, although a first run scheduled before the uptime has passed
+    /// waits for it. This takes the place of [`Job::run_immediately`].
+    fn initial_delay(&mut self, delay: std::time::Duration) -> &mut Self {
+        self.schedule_mut().initial_delay(delay);
+        self
+    }
+
+    /// Skip a run entirely, rather than running it late, if it's more than `threshold` overdue.
+    /// This can happen if the scheduler wasn't polled for a while, e.g. because the process was blocked or suspended.
+    /// The job is instead scheduled for its next regular time after now.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use std::time::Duration;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.day())
+    ///   .at("03:00")
+    ///   .skip_if_late_by(Duration::from_secs(30 * 60))
+    ///   .run(|| println!("Nightly maintenance"));
+    /// ```
+    fn skip_if_late_by(&mut self, threshold: std::time::Duration) -> &mut Self {
+        self.schedule_mut().skip_if_late_by(threshold);
+        self
+    }
+
+    /// Never run the job twice within `gap`. If the next run is scheduled sooner than `gap` after the last one,
+    /// it's delayed until the gap is over. This is a safety net for when the system clock jumps, or anything
+    /// else that could make a job run twice in quick succession.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use std::time::Duration;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.hour())
+    ///   .min_gap(Duration::from_secs(30 * 60))
+    ///   .run(|| println!("Sending hourly report"));
+    /// ```
+    /// If the clock goes backwards, the job doesn't run again until the clock is `gap` past the last run.
+    /// To space out the runs of every job, see [`Scheduler::set_minimum_interval`](crate::Scheduler::set_minimum_interval).
+    fn min_gap(&mut self, gap: std::time::Duration) -> &mut Self {
+        self.schedule_mut().min_gap(gap);
+        self
+    }
+
+    /// Only run the task when `predicate` returns `true`, e.g. to check a feature flag. It's called each time
+    /// the job is due, and can be toggled between runs.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use std::sync::atomic::{AtomicBool, Ordering};
+    /// # use std::sync::Arc;
+    /// let enabled = Arc::new(AtomicBool::new(true));
+    /// let flag = enabled.clone();
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(5.minutes())
+    ///   .run_if(move || flag.load(Ordering::SeqCst))
+    ///   .run(|| println!("Syncing with the new backend"));
+    /// // Later, to switch the job off without removing it
+    /// enabled.store(false, Ordering::SeqCst);
+    /// ```
+    /// As with [`Job::with_probability`], a skipped run is passed over rather than made up later. It doesn't count
+    /// towards [`Job::count`] or [`Job::max_per_day`], and no callbacks are called for it.
+    /// [`Scheduler::dry_run`](crate::Scheduler::dry_run) doesn't call `predicate`, and assumes that every run goes ahead.
+    fn run_if<F>(&mut self, predicate: F) -> &mut Self
+    where
+        F: 'static + Fn() -> bool + Send,
+    {
+        self.schedule_mut().run_if(predicate);
+        self
+    }
+
+    /// Only run the task for each due run with probability `p`, e.g. for chaos testing or sampling.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.minute())
+    ///   .with_probability(0.1)
+    ///   .run(|| println!("Injecting a fault"));
+    /// ```
+    /// A skipped run is passed over, and the job waits for its next scheduled run. Skipped runs don't count
+    /// towards [`Job::count`] or [`Job::max_per_day`], and neither the task nor any callbacks for the run
+    /// (such as [`Job::on_reschedule`]) are called. Use [`Job::random_seed`] for the same sequence of runs
+    /// and skips each time. [`Scheduler::dry_run`](crate::Scheduler::dry_run) assumes that every run goes ahead.
+    ///
+    /// # Panics
+    /// Panics if `p` isn't between 0 and 1.
+    fn with_probability(&mut self, p: f64) -> &mut Self {
+        self.schedule_mut().with_probability(p);
+        self
+    }
+
+    /// Run the job at most `limit` times on any one day, skipping scheduled runs once it has reached the limit.
+    /// Days are counted in the scheduler's timezone, starting at midnight. Every run counts towards the limit,
+    /// including ones from [`Job::run_immediately`] and [`Scheduler::run_now`](crate::Scheduler::run_now). Once the limit is reached, the job's next run is its first
+    /// one the following day, and it isn't pending (e.g. for [`Scheduler::has_pending`](crate::Scheduler::has_pending)) until then.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.hour())
+    ///   .max_per_day(3)
+    ///   .run(|| println!("Rate-limited API call"));
+    /// ```
+    fn max_per_day(&mut self, limit: u32) -> &mut Self {
+        self.schedule_mut().max_per_day(limit);
+        self
+    }
+
+    /// Choose what to do about missed runs, e.g. if the scheduler wasn't polled for a while
+    /// because the process was busy or suspended. See [`CatchUp`] for the choices; the default is
+    /// [`CatchUp::RunOnce`].
+    ///
+    /// [`CatchUp::RunAll`] suits jobs where every run counts, like sending a report for each hour,
+    /// but after a long gap it may run the job many times over. [`CatchUp::Skip`] and
+    /// [`CatchUp::RunOnce`] keep the job from piling up, at the cost of losing the missed runs.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.hour())
+    ///   .catch_up(CatchUp::RunAll)
+    ///   .run(|| println!("Hourly report"));
+    /// ```
+    /// Jobs that repeat (see [`Job::repeating_every`]) catch up as with [`CatchUp::RunOnce`].
+    fn catch_up(&mut self, policy: CatchUp) -> &mut Self {
+        self.schedule_mut().catch_up(policy);
+        self
+    }
+
+    /// With [`CatchUp::RunAll`], wait `spacing` between replaying missed runs, rather than replaying
+    /// them back to back. The replayed runs are then spread over several calls to `run_pending`, with at
+    /// most one per call, so a large backlog doesn't hold up other jobs. The tradeoff is that the job
+    /// takes longer to catch up, and its next regular run may be delayed until it has.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use std::time::Duration;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.hour())
+    ///   .catch_up(CatchUp::RunAll)
+    ///   .catch_up_spacing(Duration::from_secs(5))
+    ///   .run(|| println!("Hourly report"));
+    /// ```
+    fn catch_up_spacing(&mut self, spacing: std::time::Duration) -> &mut Self {
+        self.schedule_mut().catch_up_spacing(spacing);
+        self
+    }
+
+    /// Call `f` once the job has run for the last time, e.g. after the final run of a job
+    /// limited with [`Job::once`] or [`Job::count`]. It's called at most once, and never for jobs that run forever.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.hour())
+    ///   .count(3)
+    ///   .on_finished(|| println!("All done"))
+    ///   .run(|| println!("Hello"));
+    /// ```
+    fn on_finished<F>(&mut self, f: F) -> &mut Self
+    where
+        F: 'static + FnOnce() + Send,
+    {
+        self.schedule_mut().on_finished(f);
+        self
+    }
+
+    /// Also run `f` on every `n`th run of the job. This is useful for doing extra work less often than
+    /// the job runs, e.g. a heavy cleanup on every tenth run:
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler
+    ///     .every(1.minute())
+    ///     .on_nth(10, || println!("Heavy cleanup"))
+    ///     .run(|| println!("Light cleanup"));
+    /// ```
+    /// For a [`SyncJob`](crate::SyncJob), `f` is called after the job's main task. For an
+    /// [`AsyncJob`](crate::AsyncJob), it's called when the run starts, before the task's future is polled.
+    ///
+    /// # Panics
+    /// Panics if `n` is zero.
+    fn on_nth<F>(&mut self, n: u64, f: F) -> &mut Self
+    where
+        F: 'static + FnMut() + Send,
+    {
+        self.schedule_mut().on_nth(n, f);
+        self
+    }
+
+    /// Stop running the job for good after `n` runs in a row have failed, e.g. so that a job that's broken
+    /// doesn't keep retrying forever. Only jobs started with `run_fallible` can fail, and a successful run
+    /// resets the count.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # fn check_disk() -> Result<(), std::io::Error> { Ok(()) }
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.minute())
+    ///   .disable_after_failures(3)
+    ///   .on_disabled(|| eprintln!("Disk check keeps failing; giving up"))
+    ///   .run_fallible(check_disk);
+    /// ```
+    /// Async jobs are only disabled when they're next due after the failing future completes.
+    ///
+    /// # Panics
+    /// Panics if `n` is zero.
+    fn disable_after_failures(&mut self, n: u32) -> &mut Self {
+        self.schedule_mut().disable_after_failures(n);
+        self
+    }
+
+    /// Call `f` if the job is stopped by [`Job::disable_after_failures`].
+    fn on_disabled<F>(&mut self, f: F) -> &mut Self
+    where
+        F: 'static + FnOnce() + Send,
+    {
+        self.schedule_mut().on_disabled(f);
+        self
+    }
+
+    /// Call `f` with the job's new next run time each time it's rescheduled after running, e.g. to keep a display
+    /// of upcoming runs up to date without polling. It's called with `None` once the job won't run again.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.hour())
+    ///   .on_reschedule(|next_run| match next_run {
+    ///       Some(next_run) => println!("Next run at {}", next_run),
+    ///       None => println!("Finished"),
+    ///   })
+    ///   .run(|| println!("Hello"));
+    /// ```
+    fn on_reschedule<F>(&mut self, f: F) -> &mut Self
+    where
+        F: 'static + FnMut(Option<&DateTime<Tz>>) + Send,
+    {
+        self.schedule_mut().on_reschedule(f);
+        self
+    }
+
     /// After running once, run again with the specified interval.
     ///
     /// ```rust
@@ -188,7 +1014,7 @@ where
     ///   .run(|| println!("Hello"));
     /// ```
     /// If this is scheduled to run at 6 AM, it will print `Hello` at 6:00, 6:45, and 7:30, and then again at 8:00, 8:45, 9:30, etc.
-    fn repeating_every(&mut self, interval: Interval) -> Repeating<Self, Tz, Tp> {
+    fn repeating_every(&mut self, interval: Interval) -> Repeating<'_, Self, Tz, Tp> {
         Repeating::new(self, interval)
     }
 
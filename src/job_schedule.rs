@@ -1,13 +1,59 @@
-use std::{fmt, marker::PhantomData};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicI64, AtomicU32, AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 
-use chrono::{DateTime, Local, NaiveTime, TimeZone};
+use chrono::{
+    DateTime, Datelike, Local, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Timelike,
+    Utc, Weekday,
+};
 
 use crate::{
-    intervals::{parse_time, RunConfig},
-    timeprovider::{ChronoTimeProvider, TimeProvider},
-    Interval, NextTime,
+    error::{AtError, BuildError, ScheduleError, ScheduleWarning},
+    intervals::{parse_time, DstPolicy, RunConfig},
+    rng::Rng,
+    timeprovider::{monotonic_now, ChronoTimeProvider, SharedClock, TimeProvider},
+    Interval, NextTime, WeekdaySet,
 };
 
+/// Identifies a job, e.g. in the results of [`Scheduler::overdue`](crate::Scheduler::overdue).
+/// Every job gets a different ID, even jobs on different schedulers. Get a job's ID with [`Job::id`](crate::Job::id).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JobId(u64);
+
+impl JobId {
+    fn next() -> JobId {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        JobId(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+
+    #[cfg(feature = "ical")]
+    pub(crate) fn as_u64(self) -> u64 {
+        self.0
+    }
+}
+
+/// What a job does about runs it missed, e.g. because the scheduler wasn't polled for a while.
+/// Set with [`Job::catch_up`](crate::Job::catch_up).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CatchUp {
+    /// Run once, however many runs were missed, then carry on with the schedule. This is the default.
+    #[default]
+    RunOnce,
+    /// Don't run at all if more than one run was missed, and wait for the next scheduled run instead.
+    /// A run that's late, but hasn't been overtaken by a later one, still goes ahead.
+    Skip,
+    /// Run once for every missed run. By default the missed runs are replayed back to back in the same
+    /// call to `run_pending`; use [`Job::catch_up_spacing`](crate::Job::catch_up_spacing) to spread them out.
+    RunAll,
+}
+
 #[doc(hidden)]
 pub trait WithSchedule<Tz, Tp>
 where
@@ -54,6 +100,7 @@ where
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) enum RunCount {
     Never,
     Times(usize),
@@ -61,22 +108,96 @@ pub(crate) enum RunCount {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) struct RepeatConfig {
     repeats: usize,
     repeat_interval: Interval,
+    // Progress through the current run, rather than configuration
+    #[cfg_attr(feature = "serde", serde(skip))]
     repeats_left: usize,
 }
 
+/// Called by [`JobSchedule::on_reschedule`] with the new next run time
+type RescheduleHook<Tz> = Box<dyn FnMut(Option<&DateTime<Tz>>) + Send>;
+
 pub struct JobSchedule<Tz = Local, Tp = ChronoTimeProvider>
 where
     Tz: TimeZone,
     Tp: TimeProvider,
 {
+    id: JobId,
     frequency: Vec<RunConfig>,
     next_run: Option<DateTime<Tz>>,
     last_run: Option<DateTime<Tz>>,
     run_count: RunCount,
+    // The number of runs set by `once`, `count` or `forever`, for `reset`
+    configured_run_count: RunCount,
     repeat_config: Option<RepeatConfig>,
+    on_finished: Option<Box<dyn FnOnce() + Send>>,
+    on_reschedule: Option<RescheduleHook<Tz>>,
+    // Set by `on_nth`: how often to call the callback, counted in runs
+    every_nth: Option<(u64, Box<dyn FnMut() + Send>)>,
+    monotonic: bool,
+    next_run_instant: Option<Instant>,
+    max_lateness: Option<chrono::Duration>,
+    min_interval: chrono::Duration,
+    run_immediately: bool,
+    relative: bool,
+    // For `from_registration`: whether it was requested, and the time the job was registered
+    from_registration: bool,
+    anchor: Option<DateTime<Tz>>,
+    label: Option<String>,
+    // Metadata for admin tooling, from `describe` and `tag`
+    description: Option<String>,
+    tags: BTreeMap<String, String>,
+    priority: i32,
+    group: Option<String>,
+    paused: bool,
+    times_run: u64,
+    // Length of the window for `at_random_between`, and how far into it the next run is
+    random_window: Option<chrono::Duration>,
+    random_offset: chrono::Duration,
+    rng: Rng,
+    // Step and window length for `every_step_in_window`
+    step_window: Option<(chrono::Duration, chrono::Duration)>,
+    business_hours: Option<BusinessHours>,
+    excluded_days: WeekdaySet,
+    // Applied to each of the job's schedules, for `Scheduler::with_dst_policy`
+    dst_policy: DstPolicy,
+    // Times passed to `at` that couldn't be parsed, and phases passed to `every_with_phase` that don't fit
+    errors: Vec<BuildError>,
+    // For `after_uptime`: when the process (or scheduler) started, and how long after that to wait
+    start_instant: Instant,
+    min_uptime: Option<Duration>,
+    // For `initial_delay`: how long after the schedule starts to first run
+    initial_delay: Option<chrono::Duration>,
+    catch_up: CatchUp,
+    catch_up_spacing: Option<chrono::Duration>,
+    // While replaying missed runs with `CatchUp::RunAll`, the scheduled time of the run being replayed
+    replaying: Option<DateTime<Tz>>,
+    // For `run_if`: whether the job should run when it's due
+    condition: Option<Box<dyn Fn() -> bool + Send>>,
+    // For `with_probability`: the chance of each due run actually running
+    probability: Option<f64>,
+    // For `max_per_day`: the daily limit, and the local date and number of runs so far that day
+    max_per_day: Option<u32>,
+    runs_today: Option<(NaiveDate, u32)>,
+    // The shortest time allowed between runs, for `min_gap`
+    min_gap: Option<chrono::Duration>,
+    // Consecutive failed runs. This is shared with the futures of async jobs, which finish after
+    // the job has been rescheduled.
+    failures: Arc<AtomicU32>,
+    // When the last successful run started, in milliseconds since the epoch, or `NO_SUCCESS`.
+    // Like `failures`, this is shared with the futures of async jobs.
+    last_success: Arc<AtomicI64>,
+    // For `cadence_from_success`: time runs from `last_success`
+    cadence_from_success: bool,
+    max_failures: Option<u32>,
+    on_disabled: Option<Box<dyn FnOnce() + Send>>,
+    // Changes requested by the job during its current run
+    context: JobContext<Tz>,
+    // Used instead of `Tp` when the scheduler was given a clock instance
+    clock: Option<SharedClock>,
     tz: Tz,
     _tp: PhantomData<Tp>,
 }
@@ -88,15 +209,50 @@ where
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("JobSchedule")
+            .field("id", &self.id)
             .field("frequency", &self.frequency)
             .field("next_run", &self.next_run)
             .field("last_run", &self.last_run)
             .field("run_count", &self.run_count)
             .field("repeat_config", &self.repeat_config)
+            .field("label", &self.label)
+            .field("description", &self.description)
+            .field("tags", &self.tags)
+            .field("group", &self.group)
+            .field("paused", &self.paused)
+            .field("catch_up", &self.catch_up)
             .finish()
     }
 }
 
+/// Only the schedule's configuration is serialized; run times and callbacks are runtime state.
+/// The description and tags are included if the job has them.
+#[cfg(feature = "serde")]
+impl<Tz, Tp> serde::Serialize for JobSchedule<Tz, Tp>
+where
+    Tz: TimeZone,
+    Tp: TimeProvider,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let len = 3 + usize::from(self.description.is_some()) + usize::from(!self.tags.is_empty());
+        let mut state = serializer.serialize_struct("JobSchedule", len)?;
+        state.serialize_field("frequency", &self.frequency)?;
+        state.serialize_field("run_count", &self.run_count)?;
+        state.serialize_field("repeat_config", &self.repeat_config)?;
+        match &self.description {
+            Some(description) => state.serialize_field("description", description)?,
+            None => state.skip_field("description")?,
+        }
+        if self.tags.is_empty() {
+            state.skip_field("tags")?;
+        } else {
+            state.serialize_field("tags", &self.tags)?;
+        }
+        state.end()
+    }
+}
+
 impl<Tz, Tp> JobSchedule<Tz, Tp>
 where
     Tz: chrono::TimeZone + Sync + Send,
@@ -104,28 +260,99 @@ where
 {
     pub(crate) fn new(ival: Interval, tz: Tz) -> Self {
         Self {
+            id: JobId::next(),
             frequency: vec![RunConfig::from_interval(ival)],
             next_run: None,
             last_run: None,
             run_count: RunCount::Forever,
+            configured_run_count: RunCount::Forever,
             repeat_config: None,
+            on_finished: None,
+            on_reschedule: None,
+            every_nth: None,
+            monotonic: false,
+            next_run_instant: None,
+            max_lateness: None,
+            min_interval: chrono::Duration::zero(),
+            run_immediately: false,
+            relative: false,
+            from_registration: false,
+            anchor: None,
+            label: None,
+            description: None,
+            tags: BTreeMap::new(),
+            priority: 0,
+            group: None,
+            paused: false,
+            times_run: 0,
+            random_window: None,
+            random_offset: chrono::Duration::zero(),
+            rng: Rng::new(),
+            step_window: None,
+            business_hours: None,
+            excluded_days: WeekdaySet::empty(),
+            dst_policy: DstPolicy::default(),
+            errors: vec![],
+            start_instant: monotonic_now(),
+            min_uptime: None,
+            initial_delay: None,
+            catch_up: CatchUp::default(),
+            catch_up_spacing: Some(chrono::Duration::zero()),
+            replaying: None,
+            condition: None,
+            probability: None,
+            max_per_day: None,
+            runs_today: None,
+            min_gap: None,
+            failures: Arc::new(AtomicU32::new(0)),
+            last_success: Arc::new(AtomicI64::new(NO_SUCCESS)),
+            cadence_from_success: false,
+            max_failures: None,
+            on_disabled: None,
+            context: JobContext::new(),
+            clock: None,
             tz,
             _tp: PhantomData,
         }
     }
 
+    /// A schedule for `ival`, with the job's [`DstPolicy`]
+    fn new_frequency(&self, ival: Interval) -> RunConfig {
+        RunConfig::from_interval(ival).with_dst_policy(self.dst_policy)
+    }
+
+    pub(crate) fn set_dst_policy(&mut self, policy: DstPolicy) {
+        self.dst_policy = policy;
+        for freq in &mut self.frequency {
+            *freq = freq.with_dst_policy(policy);
+        }
+    }
+
     fn last_frequency(&mut self) -> &mut RunConfig {
         let last_idx = self.frequency.len() - 1;
         &mut self.frequency[last_idx]
     }
 
     pub fn at(&mut self, time: &str) -> &mut Self {
-        self.try_at(time)
-            .expect("Could not convert value into a time")
+        // Reported by `try_build`, or when the job is started
+        if let Err(e) = self.try_at(time) {
+            self.errors.push(e.into());
+        }
+        self
+    }
+
+    pub fn try_build(&self) -> Result<(), ScheduleError> {
+        let warnings = self.validate();
+        if self.errors.is_empty() && warnings.is_empty() {
+            Ok(())
+        } else {
+            Err(ScheduleError::new(self.errors.clone(), warnings))
+        }
     }
 
-    pub fn try_at(&mut self, time: &str) -> Result<&mut Self, chrono::ParseError> {
-        Ok(self.at_time(parse_time(time)?))
+    pub fn try_at(&mut self, time: &str) -> Result<&mut Self, AtError> {
+        let parsed = parse_time(time).map_err(|e| AtError::new(time, e))?;
+        Ok(self.at_time(parsed))
     }
 
     pub fn at_time(&mut self, time: NaiveTime) -> &mut Self {
@@ -136,6 +363,132 @@ where
         self
     }
 
+    pub fn at_times(&mut self, times: &[NaiveTime]) -> &mut Self {
+        if let Some((&first, rest)) = times.split_first() {
+            let base = self.last_frequency().with_time(first);
+            *self.last_frequency() = base.clone();
+            self.frequency
+                .extend(rest.iter().map(|&time| base.with_time(time)));
+        }
+        self
+    }
+
+    pub fn at_random_between(&mut self, start: NaiveTime, end: NaiveTime) -> &mut Self {
+        // Windows that cross midnight have end < start
+        let window = chrono::Duration::seconds((end - start).num_seconds().rem_euclid(86400));
+        self.random_window = Some(window);
+        self.at_time(start)
+    }
+
+    pub fn every_step_in_window(
+        &mut self,
+        step: Interval,
+        start: NaiveTime,
+        end: NaiveTime,
+    ) -> &mut Self {
+        let step = match step.fixed_duration() {
+            Some(step) if step > chrono::Duration::zero() => step,
+            _ => panic!(
+                "The step must be a non-zero fixed-length interval, not {:?}",
+                step
+            ),
+        };
+        // Windows that cross midnight have end < start
+        let window = chrono::Duration::seconds((end - start).num_seconds().rem_euclid(86400));
+        self.step_window = Some((step, window));
+        self.at_time(start)
+    }
+
+    pub fn business_hours<BTz>(&mut self, tz: BTz, open: NaiveTime, close: NaiveTime) -> &mut Self
+    where
+        BTz: TimeZone + Send + Sync + 'static,
+    {
+        // Hours that cross midnight have close < open, and the same time means open all day
+        let length = match (close - open).num_seconds().rem_euclid(86400) {
+            0 => 86400,
+            length => length,
+        };
+        self.business_hours = Some(BusinessHours {
+            open,
+            length: chrono::Duration::seconds(length),
+            zone: Arc::new(tz),
+        });
+        self
+    }
+
+    pub fn exclude_weekdays(&mut self, days: &[Weekday]) -> &mut Self {
+        for &day in days {
+            self.excluded_days.insert(day);
+        }
+        self
+    }
+
+    pub fn after_uptime(&mut self, uptime: Duration) -> &mut Self {
+        self.min_uptime = Some(uptime);
+        self
+    }
+
+    pub fn initial_delay(&mut self, delay: Duration) -> &mut Self {
+        // A delay too long to represent means the job never runs
+        self.initial_delay = Some(
+            chrono::Duration::from_std(delay)
+                .unwrap_or_else(|_| chrono::Duration::milliseconds(i64::MAX)),
+        );
+        self
+    }
+
+    pub(crate) fn set_start_instant(&mut self, start: Instant) {
+        self.start_instant = start;
+    }
+
+    /// Apply the changes in `context` when the job is next rescheduled
+    pub(crate) fn set_context(&mut self, context: JobContext<Tz>) {
+        self.context = context;
+    }
+
+    pub(crate) fn set_clock(&mut self, clock: Option<SharedClock>) {
+        self.clock = clock;
+    }
+
+    /// The current time, from the clock instance if there is one, or else from `Tp`
+    fn now(&self) -> DateTime<Tz> {
+        match &self.clock {
+            Some(clock) => clock.now(&self.tz),
+            None => Tp::now(&self.tz),
+        }
+    }
+
+    /// The monotonic time, from the clock instance if there is one
+    fn monotonic_now(&self) -> Instant {
+        match &self.clock {
+            Some(clock) => clock.monotonic_now(),
+            None => monotonic_now(),
+        }
+    }
+
+    /// The earliest time the job can first run, given any [`after_uptime`](Self::after_uptime) delay,
+    /// or `None` if that's too far away to ever come
+    fn earliest_start(&self, now: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let uptime = match self.min_uptime {
+            Some(uptime) => uptime,
+            None => return Some(now.clone()),
+        };
+        let ready_at = self.start_instant.checked_add(uptime)?;
+        let delay = ready_at.saturating_duration_since(self.monotonic_now());
+        // Round up to a whole second, so that sub-second differences don't skip a run
+        let delay_secs = delay
+            .as_secs()
+            .saturating_add(u64::from(delay.subsec_nanos() > 0));
+        chrono::Duration::from_std(Duration::from_secs(delay_secs))
+            .ok()
+            .and_then(|delay| now.clone().checked_add_signed(delay))
+    }
+
+    pub fn random_seed(&mut self, seed: u64) -> &mut Self {
+        self.rng = Rng::with_seed(seed);
+        self
+    }
+
     pub fn plus(&mut self, ival: Interval) -> &mut Self {
         {
             let frequency = self.last_frequency();
@@ -144,158 +497,1553 @@ where
         self
     }
 
+    pub fn every_with_phase(&mut self, interval: Interval, phase: Interval) -> &mut Self {
+        match (interval.fixed_duration(), phase.fixed_duration()) {
+            (Some(length), Some(offset)) if offset < length => {
+                *self.last_frequency() = self.new_frequency(interval).with_phase(phase);
+            }
+            // Reported by `try_build`, or when the job is started
+            _ => self.errors.push(BuildError::Phase { interval, phase }),
+        }
+        self
+    }
+
+    pub fn every_other_day(&mut self) -> &mut Self {
+        let today = self.now().date_naive();
+        self.every_starting_on(Interval::Days(2), today)
+    }
+
+    pub fn every_other_week_on(&mut self, day: Weekday) -> &mut Self {
+        let today = self.now().date_naive();
+        let days_until =
+            (7 + day.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+        self.every_starting_on(
+            Interval::Weeks(2),
+            today + chrono::Duration::days(i64::from(days_until)),
+        )
+    }
+
+    /// Run every `interval`, phased so that `date` is one of the days it runs on
+    fn every_starting_on(&mut self, interval: Interval, date: NaiveDate) -> &mut Self {
+        let midday = Utc.from_utc_datetime(&date.and_hms_opt(12, 0, 0).unwrap());
+        let boundary = interval.prev(&midday).date_naive();
+        let phase = (date - boundary).num_days() as u32;
+        self.every_with_phase(interval, Interval::Days(phase))
+    }
+
     pub fn and_every(&mut self, ival: Interval) -> &mut Self {
-        self.frequency.push(RunConfig::from_interval(ival));
+        self.frequency.push(self.new_frequency(ival));
         self
     }
 
     pub fn once(&mut self) -> &mut Self {
-        self.run_count = RunCount::Times(1);
-        self
+        self.count(1)
     }
 
     pub fn forever(&mut self) -> &mut Self {
         self.run_count = RunCount::Forever;
+        self.configured_run_count = RunCount::Forever;
         self
     }
 
     pub fn count(&mut self, count: usize) -> &mut Self {
         self.run_count = RunCount::Times(count);
+        self.configured_run_count = RunCount::Times(count);
         self
     }
 
-    fn next_run_time(&self, now: &DateTime<Tz>) -> Option<DateTime<Tz>> {
-        match self.run_count {
-            RunCount::Never => None,
-            _ => self.frequency.iter().map(|freq| freq.next(now)).min(),
+    pub fn reset(&mut self) -> &mut Self {
+        self.run_count = self.configured_run_count;
+        self.failures.store(0, Ordering::SeqCst);
+        self.last_success.store(NO_SUCCESS, Ordering::SeqCst);
+        self.next_run = None;
+        self.next_run_instant = None;
+        self.replaying = None;
+        self.start_schedule()
+    }
+
+    pub fn on_finished<F>(&mut self, f: F) -> &mut Self
+    where
+        F: 'static + FnOnce() + Send,
+    {
+        self.on_finished = Some(Box::new(f));
+        self
+    }
+
+    pub fn disable_after_failures(&mut self, n: u32) -> &mut Self {
+        assert!(n > 0, "disable_after_failures requires n to be at least 1");
+        self.max_failures = Some(n);
+        self
+    }
+
+    pub fn on_disabled<F>(&mut self, f: F) -> &mut Self
+    where
+        F: 'static + FnOnce() + Send,
+    {
+        self.on_disabled = Some(Box::new(f));
+        self
+    }
+
+    /// The count of consecutive failures, for updating once a run has finished
+    pub(crate) fn failures(&self) -> Arc<AtomicU32> {
+        self.failures.clone()
+    }
+
+    /// When the last successful run started, for updating once a run has finished
+    pub(crate) fn last_success_millis(&self) -> Arc<AtomicI64> {
+        self.last_success.clone()
+    }
+
+    pub fn last_success(&self) -> Option<DateTime<Tz>> {
+        match self.last_success.load(Ordering::SeqCst) {
+            NO_SUCCESS => None,
+            millis => self.tz.timestamp_millis_opt(millis).single(),
         }
     }
 
-    /// Has this job exhausted its runs?
-    pub fn can_run_again(&self) -> bool {
-        self.run_count != RunCount::Never
+    /// Stop the job for good if too many runs in a row have failed. Returns whether it was stopped.
+    pub(crate) fn disable_if_failing(&mut self) -> bool {
+        let failing =
+            matches!(self.max_failures, Some(n) if self.failures.load(Ordering::SeqCst) >= n);
+        if !failing || self.run_count == RunCount::Never {
+            return false;
+        }
+        self.run_count = RunCount::Never;
+        if let Some(on_disabled) = self.on_disabled.take() {
+            on_disabled();
+        }
+        true
     }
 
-    /// Specify a task to run, and schedule its next run
-    pub fn start_schedule(&mut self) -> &mut Self {
-        if let None = self.next_run {
-            let now = Tp::now(&self.tz);
-            self.next_run = self.next_run_time(&now);
-            match &mut self.repeat_config {
-                Some(RepeatConfig {
-                    repeats,
-                    repeats_left,
-                    ..
-                }) => {
-                    *repeats_left = *repeats;
-                }
-                None => (),
+    pub fn on_reschedule<F>(&mut self, f: F) -> &mut Self
+    where
+        F: 'static + FnMut(Option<&DateTime<Tz>>) + Send,
+    {
+        self.on_reschedule = Some(Box::new(f));
+        self
+    }
+
+    pub fn on_nth<F>(&mut self, n: u64, f: F) -> &mut Self
+    where
+        F: 'static + FnMut() + Send,
+    {
+        assert!(n > 0, "on_nth requires n to be at least 1");
+        self.every_nth = Some((n, Box::new(f)));
+        self
+    }
+
+    /// Call the [`on_nth`](Self::on_nth) callback if the run that's about to be counted is one it's due on
+    pub(crate) fn call_on_nth(&mut self) {
+        if let Some((n, f)) = self.every_nth.as_mut() {
+            if (self.times_run + 1) % *n == 0 {
+                f();
             }
         }
-        self
     }
 
-    /// Test whether a job is scheduled to run again. This is usually only called by
-    /// [Scheduler::run_pending()](::Scheduler::run_pending).
-    pub fn is_pending(&self, now: &DateTime<Tz>) -> bool {
-        match &self.next_run {
-            Some(dt) => *dt <= *now,
-            None => false,
+    pub fn run_immediately(&mut self) -> &mut Self {
+        self.run_immediately = true;
+        if self.next_run.is_some() && self.last_run.is_none() {
+            // Already started, but hasn't run yet
+            let now = self.now();
+            self.next_run = Some(now.clone());
+            self.update_next_run_instant(&now);
         }
+        self
     }
 
-    /// Run a task and re-schedule it. This is usually only called by
-    /// [Scheduler::run_pending()](::Scheduler::run_pending).
-    pub fn schedule_next(&mut self, now: &DateTime<Tz>) {
-        // Don't do anything if we're run out of runs
-        if self.run_count == RunCount::Never {
+    pub fn relative_to_start(&mut self) -> &mut Self {
+        self.relative = true;
+        self
+    }
+
+    pub fn cadence_from_success(&mut self) -> &mut Self {
+        self.relative = true;
+        self.cadence_from_success = true;
+        self
+    }
+
+    #[allow(clippy::wrong_self_convention)]
+    pub fn from_registration(&mut self) -> &mut Self {
+        self.from_registration = true;
+        self
+    }
+
+    pub fn label(&mut self, label: &str) -> &mut Self {
+        self.label = Some(label.to_owned());
+        self
+    }
+
+    pub fn get_label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    pub fn describe(&mut self, description: &str) -> &mut Self {
+        self.description = Some(description.to_owned());
+        self
+    }
+
+    pub fn get_description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn tag(&mut self, key: &str, value: &str) -> &mut Self {
+        self.tags.insert(key.to_owned(), value.to_owned());
+        self
+    }
+
+    pub fn get_tag(&self, key: &str) -> Option<&str> {
+        self.tags.get(key).map(String::as_str)
+    }
+
+    pub fn tags(&self) -> &BTreeMap<String, String> {
+        &self.tags
+    }
+
+    pub fn group(&mut self, name: &str) -> &mut Self {
+        self.group = Some(name.to_owned());
+        self
+    }
+
+    pub fn priority(&mut self, priority: i32) -> &mut Self {
+        self.priority = priority;
+        self
+    }
+
+    pub(crate) fn get_priority(&self) -> i32 {
+        self.priority
+    }
+
+    pub(crate) fn in_group(&self, name: &str) -> bool {
+        self.group.as_deref() == Some(name)
+    }
+
+    /// Stop the job from running until it's resumed.
+    pub(crate) fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Let a paused job run again. Any runs it missed while paused are skipped.
+    pub(crate) fn resume(&mut self) {
+        if !self.paused {
             return;
         }
+        self.paused = false;
+        self.skip_missed_runs(&self.now());
+    }
 
-        // We compute this up front since we can't borrow self immutably while doing this next bit
-        let next_run_time = self.next_run_time(now);
-        match &mut self.repeat_config {
-            Some(RepeatConfig {
-                repeats,
-                repeats_left,
-                repeat_interval,
-            }) => {
-                if *repeats_left > 0 {
-                    *repeats_left -= 1;
-                    // Normal scheduling is aligned with the day: if you ask for something every hour, it will
-                    // run at the start of the next hour, not one hour after being scheduled.
-                    // For repeats, though, we want them aligned the first run of the repeats.
-                    // This means we want to align with with next_run (which should be not far in the past),
-                    // or if it's somehow unavailable, the current time.
-                    // It's possible that we're really far behind. If so, find the next repeat interval that's
-                    // still in the future (relative to when we start this run.)
-                    let mut next = self.next_run.as_ref().unwrap_or(now).clone();
-                    loop {
-                        next = repeat_interval.next_from(&next);
-                        if next > *now {
-                            break;
-                        }
-                    }
-                    self.next_run = Some(next);
-                } else {
-                    self.next_run = next_run_time;
-                    *repeats_left = *repeats;
-                }
-            }
-            None => self.next_run = next_run_time,
+    /// If the job is overdue, move its next run to the first one after `now`.
+    pub(crate) fn skip_missed_runs(&mut self, now: &DateTime<Tz>) {
+        if matches!(&self.next_run, Some(next_run) if next_run <= now) {
+            self.next_run = self.next_run_time(now);
         }
+        self.replaying = None;
+        self.update_next_run_instant(now);
+    }
 
-        self.last_run = Some(now.clone());
-        self.run_count = match self.run_count {
-            RunCount::Never => RunCount::Never,
-            RunCount::Times(n) if n > 1 => RunCount::Times(n - 1),
-            RunCount::Times(_) => RunCount::Never,
-            RunCount::Forever => RunCount::Forever,
-        };
+    pub fn skip_if_late_by(&mut self, threshold: std::time::Duration) -> &mut Self {
+        // A threshold too large to represent can never be exceeded
+        self.max_lateness = chrono::Duration::from_std(threshold).ok();
+        self
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::JobSchedule;
-    use crate::{intervals::*, timeprovider::TimeProvider, Job, SyncJob};
-    use chrono::prelude::*;
+    pub fn run_if<F>(&mut self, predicate: F) -> &mut Self
+    where
+        F: 'static + Fn() -> bool + Send,
+    {
+        self.condition = Some(Box::new(predicate));
+        self
+    }
 
-    #[test]
-    fn test_repeating() {
-        fn utc_hms(h: u32, m: u32, s: u32) -> DateTime<Utc> {
-            Utc.from_utc_datetime(&NaiveDate::from_ymd(2020, 6, 16).and_hms(h, m, s))
-        }
-        struct TestTimeProvider;
-        impl TimeProvider for TestTimeProvider {
-            fn now<Tz>(tz: &Tz) -> chrono::DateTime<Tz>
-            where
-                Tz: chrono::TimeZone + Sync + Send,
-            {
-                utc_hms(7, 58, 0).with_timezone(tz)
-            }
+    pub fn with_probability(&mut self, p: f64) -> &mut Self {
+        assert!(
+            (0.0..=1.0).contains(&p),
+            "with_probability requires p to be between 0 and 1, not {}",
+            p
+        );
+        self.probability = Some(p);
+        self
+    }
+
+    pub fn max_per_day(&mut self, limit: u32) -> &mut Self {
+        self.max_per_day = Some(limit);
+        self
+    }
+
+    pub fn min_gap(&mut self, gap: Duration) -> &mut Self {
+        // A gap too large to represent never ends
+        self.min_gap = Some(
+            chrono::Duration::from_std(gap)
+                .unwrap_or_else(|_| chrono::Duration::milliseconds(i64::MAX)),
+        );
+        self
+    }
+
+    /// Is `now` too soon after the last run, according to [`min_gap`](Self::min_gap)?
+    /// This includes times before the last run, if the clock has gone backwards.
+    fn in_cooldown(&self, now: &DateTime<Tz>) -> bool {
+        match (&self.last_run, self.min_gap) {
+            (Some(last_run), Some(gap)) => last_run
+                .clone()
+                .checked_add_signed(gap)
+                .map_or(true, |ready| *now < ready),
+            _ => false,
         }
-        let mut job = SyncJob::<Utc, TestTimeProvider>::new(1.hour(), Utc);
-        job.repeating_every(45.minutes()).times(2);
-        job.run(|| {});
+    }
 
-        assert!(!job.is_pending(&utc_hms(7, 59, 0)));
-        assert!(job.is_pending(&utc_hms(8, 0, 0)));
-        job.execute(&utc_hms(8, 0, 0));
+    /// How many times the job has run on the local date of `now`
+    fn runs_on_day_of(&self, now: &DateTime<Tz>) -> u32 {
+        match self.runs_today {
+            Some((date, runs)) if date == now.date_naive() => runs,
+            _ => 0,
+        }
+    }
 
-        assert!(!job.is_pending(&utc_hms(8, 44, 0)));
-        assert!(job.is_pending(&utc_hms(8, 45, 0)));
-        job.execute(&utc_hms(8, 45, 0));
+    /// Has the job already run as many times on the local date of `now` as allowed by
+    /// [`max_per_day`](Self::max_per_day)?
+    fn over_daily_limit(&self, now: &DateTime<Tz>) -> bool {
+        matches!(self.max_per_day, Some(limit) if self.runs_on_day_of(now) >= limit)
+    }
 
-        // Skips 9:00 because it's still repeating at 45 minutes
-        assert!(!job.is_pending(&utc_hms(9, 0, 0)));
+    /// If the job has reached its [`max_per_day`](Self::max_per_day) limit, move its next run to the first
+    /// one on the following day.
+    fn wait_if_over_daily_limit(&mut self, now: &DateTime<Tz>) {
+        if self.over_daily_limit(now) {
+            let tomorrow = Interval::Days(1).next(now);
+            if self.next_run.as_ref().is_some_and(|next| *next < tomorrow) {
+                self.replaying = None;
+                self.next_run = self.next_run_time(&(tomorrow - chrono::Duration::seconds(1)));
+            }
+        }
+    }
 
-        assert!(!job.is_pending(&utc_hms(9, 29, 0)));
-        assert!(job.is_pending(&utc_hms(9, 30, 0)));
-        job.execute(&utc_hms(9, 30, 0));
+    /// Count a run triggered outside the job's schedule towards [`max_per_day`](Self::max_per_day).
+    /// Returns `false`, without counting it, if the job has already reached its limit today.
+    pub(crate) fn count_manual_run(&mut self, now: &DateTime<Tz>) -> bool {
+        if self.over_daily_limit(now) {
+            return false;
+        }
+        self.runs_today = Some((now.date_naive(), self.runs_on_day_of(now) + 1));
+        self.wait_if_over_daily_limit(now);
+        self.update_next_run_instant(now);
+        true
+    }
 
-        assert!(!job.is_pending(&utc_hms(9, 59, 0)));
-        assert!(job.is_pending(&utc_hms(10, 0, 0)));
+    /// If the job has already run as many times today as allowed by [`max_per_day`](Self::max_per_day),
+    /// skip the pending run and schedule the next one. Returns whether the run was skipped.
+    pub fn skip_if_over_daily_limit(&mut self, now: &DateTime<Tz>) -> bool {
+        let over = self.over_daily_limit(now);
+        if over {
+            self.skip_missed_runs(now);
+        }
+        over
+    }
+
+    /// Check the condition given to [`run_if`](Self::run_if) for a due run. If it doesn't hold,
+    /// skip the run and schedule the next one. Returns whether the run was skipped.
+    /// Skipped runs don't count towards [`count`](Self::count).
+    pub fn skip_if_condition_unmet(&mut self, now: &DateTime<Tz>) -> bool {
+        let skip = self
+            .condition
+            .as_ref()
+            .is_some_and(|condition| !condition());
+        if skip {
+            self.skip_missed_runs(now);
+        }
+        skip
+    }
+
+    /// Roll the dice for a due run, according to [`with_probability`](Self::with_probability).
+    /// If the run loses, skip it and schedule the next one. Returns whether the run was skipped.
+    /// Skipped runs don't count towards [`count`](Self::count).
+    pub fn skip_by_chance(&mut self, now: &DateTime<Tz>) -> bool {
+        let skip = match self.probability {
+            Some(p) => !self.rng.chance(p),
+            None => false,
+        };
+        if skip {
+            self.skip_missed_runs(now);
+        }
+        skip
+    }
+
+    pub fn catch_up(&mut self, policy: CatchUp) -> &mut Self {
+        self.catch_up = policy;
+        self
+    }
+
+    pub fn catch_up_spacing(&mut self, spacing: Duration) -> &mut Self {
+        // Spacing too large to represent means missed runs are never replayed
+        self.catch_up_spacing = chrono::Duration::from_std(spacing).ok();
+        self
+    }
+
+    /// With [`CatchUp::RunAll`], the scheduled time of the next missed run still to be replayed
+    /// after the one just run, and when to replay it.
+    fn next_missed_run(&self, now: &DateTime<Tz>) -> Option<(DateTime<Tz>, DateTime<Tz>)> {
+        if self.catch_up != CatchUp::RunAll || self.repeat_config.is_some() {
+            return None;
+        }
+        let scheduled = self.replaying.as_ref().or(self.next_run.as_ref())?;
+        let missed = self
+            .next_run_time(scheduled)
+            .filter(|following| following <= now)?;
+        let replay_at = now.clone().checked_add_signed(self.catch_up_spacing?)?;
+        Some((missed, replay_at))
+    }
+
+    pub fn validate(&self) -> Vec<ScheduleWarning> {
+        let mut warnings = vec![];
+        for weekday in self.frequency.iter().map(RunConfig::base) {
+            if !weekday.is_day_of_week() {
+                continue;
+            }
+            for other in self.frequency.iter().map(RunConfig::base) {
+                if other.is_sub_hour() {
+                    warnings.push(ScheduleWarning::WeekdayWithSubHourInterval { weekday, other });
+                }
+            }
+        }
+        warnings
+    }
+
+    pub fn explain(&self) -> String {
+        let schedules: Vec<_> = self
+            .frequency
+            .iter()
+            .map(|freq| self.explain_frequency(freq))
+            .collect();
+        let mut clauses = vec![schedules.join(" and ")];
+        if self.from_registration {
+            clauses.push("counted from when the job was added".to_owned());
+        } else if self.cadence_from_success {
+            clauses.push("counted from the start of the last successful run".to_owned());
+        } else if self.relative {
+            clauses.push("counted from the start of the previous run".to_owned());
+        }
+        if let Some(rc) = &self.repeat_config {
+            clauses.push(format!(
+                "repeating {} {} times",
+                rc.repeat_interval, rc.repeats
+            ));
+        }
+        if let Some(delay) = self.initial_delay.filter(|_| self.last_run.is_none()) {
+            clauses.push(format!(
+                "first running {} after being added",
+                explain_duration(Some(delay))
+            ));
+        } else if self.run_immediately && self.last_run.is_none() {
+            clauses.push("running once straight away".to_owned());
+        }
+        if let Some(uptime) = self.min_uptime {
+            clauses.push(format!(
+                "once the scheduler has been running for {}",
+                explain_duration(chrono::Duration::from_std(uptime).ok())
+            ));
+        }
+        if let Some(lateness) = self.max_lateness {
+            clauses.push(format!(
+                "skipping runs more than {} late",
+                explain_duration(Some(lateness))
+            ));
+        }
+        match self.catch_up {
+            CatchUp::RunOnce => (),
+            CatchUp::Skip => clauses.push("skipping missed runs".to_owned()),
+            CatchUp::RunAll => clauses.push(match self.catch_up_spacing {
+                Some(spacing) if spacing.is_zero() => {
+                    "replaying each missed run back to back".to_owned()
+                }
+                Some(spacing) => format!(
+                    "replaying each missed run, {} apart",
+                    explain_duration(Some(spacing))
+                ),
+                // Spacing too large to represent
+                None => "not replaying missed runs".to_owned(),
+            }),
+        }
+        if !self.excluded_days.is_empty() {
+            let days = Interval::DaysOfWeek(self.excluded_days).to_string();
+            clauses.push(format!("except {}", days.trim_start_matches("every ")));
+        }
+        if let Some(hours) = &self.business_hours {
+            clauses.push(format!(
+                "only on weekdays from {} to {}",
+                explain_time(hours.open),
+                explain_time(hours.open + hours.length)
+            ));
+        }
+        if self.condition.is_some() {
+            clauses.push("only when its condition holds".to_owned());
+        }
+        if let Some(p) = self.probability {
+            // Rounded, so that e.g. 0.07 isn't written as 7.000000000000001%
+            let percent = (p * 10_000.0).round() / 100.0;
+            clauses.push(format!("only running {}% of the time", percent));
+        }
+        if let Some(limit) = self.max_per_day {
+            clauses.push(format!("at most {} times a day", limit));
+        }
+        if let Some(gap) = self.min_gap {
+            clauses.push(format!("at least {} apart", explain_duration(Some(gap))));
+        }
+        if let Some(failures) = self.max_failures {
+            clauses.push(format!("stopping after {} failures in a row", failures));
+        }
+        match self.run_count {
+            RunCount::Forever => (),
+            RunCount::Times(1) => clauses.push("once more".to_owned()),
+            RunCount::Times(n) => clauses.push(format!("{} more times", n)),
+            RunCount::Never => clauses.push("with no runs left".to_owned()),
+        }
+        if self.paused {
+            clauses.push("currently paused".to_owned());
+        }
+        let explanation = clauses.join(", ");
+        let mut chars = explanation.chars();
+        match chars.next() {
+            Some(first) => format!("{}{}.", first.to_uppercase(), chars.as_str()),
+            None => explanation,
+        }
+    }
+
+    /// Describe one of the job's schedules, e.g. "every weekday at 09:00 plus 30 seconds"
+    fn explain_frequency(&self, freq: &RunConfig) -> String {
+        let mut explanation = freq.base().to_string();
+        if let Some(time) = freq.time() {
+            let start = explain_time(time);
+            match (self.step_window, self.random_window) {
+                (Some((step, window)), _) => explanation.push_str(&format!(
+                    ", every {} from {} to {}",
+                    explain_duration(Some(step)),
+                    start,
+                    explain_time(time + window)
+                )),
+                (None, Some(window)) => explanation.push_str(&format!(
+                    " at a random time between {} and {}",
+                    start,
+                    explain_time(time + window)
+                )),
+                (None, None) => explanation.push_str(&format!(" at {}", start)),
+            }
+        }
+        for offset in freq.offsets() {
+            explanation.push_str(&format!(" plus {}", explain_interval(*offset)));
+        }
+        if let Some(phase) = freq.phase() {
+            explanation.push_str(&format!(
+                ", {} after each boundary",
+                explain_interval(phase)
+            ));
+        }
+        explanation
+    }
+
+    pub fn scheduled_prev(&self, from: DateTime<Tz>) -> Option<DateTime<Tz>> {
+        match self.run_count {
+            RunCount::Never => None,
+            _ => self.frequency.iter().map(|freq| freq.prev(&from)).max(),
+        }
+    }
+
+    fn next_run_time(&self, now: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        self.next_run_time_from(now, self.random_offset)
+    }
+
+    /// The next run time after `now`, given that the previous run was `window_offset` into its
+    /// random window (see [`JobSchedule::at_random_between`]).
+    fn next_run_time_from(
+        &self,
+        now: &DateTime<Tz>,
+        window_offset: chrono::Duration,
+    ) -> Option<DateTime<Tz>> {
+        if self.run_count == RunCount::Never {
+            return None;
+        }
+        let mut from = now.clone();
+        // Give up on schedules that never run at an allowed time, e.g. at midnight with 9 to 5 business hours
+        for _ in 0..1000 {
+            let next = self.next_in_window(&(from - window_offset))?;
+            let next = at_least_minimum(next + self.random_offset, now, self.min_interval);
+            match self.excluded_until(&next) {
+                // Allow a run exactly when the excluded time ends
+                Some(end) => from = end - chrono::Duration::seconds(1),
+                None => return Some(next),
+            }
+        }
+        None
+    }
+
+    /// If `time` is on a day excluded by [`exclude_weekdays`](Self::exclude_weekdays), or outside
+    /// [`business_hours`](Self::business_hours), the next time that isn't.
+    fn excluded_until(&self, time: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        if self.excluded_days.contains(time.weekday()) {
+            return Some(Interval::Days(1).next(time));
+        }
+        match &self.business_hours {
+            Some(hours) if !hours.is_open(&time.naive_utc()) => {
+                let opening = hours.next_opening(&time.naive_utc());
+                Some(self.tz.from_utc_datetime(&opening))
+            }
+            _ => None,
+        }
+    }
+
+    /// The next time after `from` given by the job's intervals, taking into account any
+    /// [`every_step_in_window`](Self::every_step_in_window) steps.
+    fn next_in_window(&self, from: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let (step, window) = match self.step_window {
+            Some(step_window) => step_window,
+            None => return next_of(&self.frequency, from, self.relative, self.anchor.as_ref()),
+        };
+        // The window that's in progress at `from`, or failing that, the next one
+        let window_start = next_of(
+            &self.frequency,
+            &(from.clone() - window),
+            self.relative,
+            self.anchor.as_ref(),
+        )?;
+        if window_start > *from {
+            return Some(window_start);
+        }
+        let steps = (from.clone() - window_start.clone()).num_seconds() / step.num_seconds() + 1;
+        let next = window_start.clone() + step * steps as i32;
+        if next <= window_start.clone() + window {
+            Some(next)
+        } else {
+            next_of(
+                &self.frequency,
+                &window_start,
+                self.relative,
+                self.anchor.as_ref(),
+            )
+        }
+    }
+
+    /// Pick how far into the random window the next run should be
+    fn pick_random_offset(&mut self) {
+        if let Some(window) = self.random_window {
+            let seconds = self.rng.up_to(window.num_seconds() as u64);
+            self.random_offset = chrono::Duration::seconds(seconds as i64);
+        }
+    }
+
+    pub(crate) fn set_minimum_interval(&mut self, min_interval: Duration) {
+        // A minimum too large to represent would stop the job from ever running again; use a day instead
+        self.min_interval =
+            chrono::Duration::from_std(min_interval).unwrap_or_else(|_| chrono::Duration::days(1));
+    }
+
+    pub fn frequencies(&self) -> &[RunConfig] {
+        &self.frequency
+    }
+
+    pub fn schedule_config(&self) -> ScheduleConfig {
+        ScheduleConfig {
+            frequency: self.frequency.clone(),
+            run_count: self.run_count,
+            repeat_config: self.repeat_config.clone(),
+            relative: self.relative,
+            cadence_from_success: self.cadence_from_success,
+            from_registration: self.from_registration,
+            step_window: self.step_window,
+            business_hours: self.business_hours.clone(),
+            excluded_days: self.excluded_days,
+        }
+    }
+
+    /// Replace this job's schedule with a copy of `config`.
+    pub(crate) fn set_schedule_config(&mut self, config: &ScheduleConfig) {
+        self.frequency = config.frequency.clone();
+        self.run_count = config.run_count;
+        self.configured_run_count = config.run_count;
+        self.repeat_config = config.repeat_config.clone();
+        self.relative = config.relative;
+        self.cadence_from_success = config.cadence_from_success;
+        self.from_registration = config.from_registration;
+        self.step_window = config.step_window;
+        self.business_hours = config.business_hours.clone();
+        self.excluded_days = config.excluded_days;
+        self.set_dst_policy(self.dst_policy);
+    }
+
+    pub fn fire_times(&self, from: DateTime<Tz>) -> FireTimes<Tz> {
+        FireTimes {
+            frequency: self.frequency.clone(),
+            repeat: self
+                .repeat_config
+                .as_ref()
+                .map(|rc| (rc.repeats, rc.repeat_interval)),
+            repeats_left: self.repeat_config.as_ref().map_or(0, |rc| rc.repeats),
+            runs_left: match self.run_count {
+                RunCount::Never => Some(0),
+                RunCount::Times(n) => Some(n),
+                RunCount::Forever => None,
+            },
+            relative: self.relative,
+            anchor: self.anchor.clone(),
+            next: next_of(&self.frequency, &from, self.relative, self.anchor.as_ref()),
+        }
+    }
+
+    /// Track the next run using a monotonic clock, if this job only uses fixed intervals.
+    /// Has no effect on jobs that run at a particular time of day, or day of the week.
+    pub(crate) fn use_monotonic(&mut self, enabled: bool) {
+        self.monotonic = enabled;
+        if self.next_run.is_some() {
+            let now = self.now();
+            self.update_next_run_instant(&now);
+        }
+    }
+
+    fn update_next_run_instant(&mut self, now: &DateTime<Tz>) {
+        self.next_run_instant = match &self.next_run {
+            Some(next_run)
+                if self.monotonic && self.frequency.iter().all(RunConfig::is_fixed_interval) =>
+            {
+                let delay = (next_run.clone() - now.clone())
+                    .to_std()
+                    .unwrap_or_default();
+                Some(self.monotonic_now() + delay)
+            }
+            _ => None,
+        };
+    }
+
+    pub fn id(&self) -> JobId {
+        self.id
+    }
+
+    /// How long the job has been due, if it's due to run at `now`. See [`JobSchedule::is_due`] for `from_clock`.
+    pub(crate) fn overdue_by(&self, now: &DateTime<Tz>, from_clock: bool) -> Option<Duration> {
+        if !self.can_run_again() || !self.is_due(now, from_clock) {
+            return None;
+        }
+        let next_run = self.next_run.as_ref()?;
+        // A job that's due by the monotonic clock can be due before its time on the wall clock
+        Some(
+            (now.clone() - next_run.clone())
+                .to_std()
+                .unwrap_or_default(),
+        )
+    }
+
+    pub(crate) fn next_run(&self) -> Option<&DateTime<Tz>> {
+        self.next_run.as_ref()
+    }
+
+    /// When the job will next run, unless it's paused or won't run again
+    pub(crate) fn upcoming_run(&self) -> Option<&DateTime<Tz>> {
+        if self.paused || !self.can_run_again() {
+            return None;
+        }
+        self.next_run.as_ref()
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    pub(crate) fn last_run(&self) -> Option<&DateTime<Tz>> {
+        self.last_run.as_ref()
+    }
+
+    /// Has this job exhausted its runs?
+    pub fn can_run_again(&self) -> bool {
+        self.run_count != RunCount::Never
+    }
+
+    pub fn runs_remaining(&self) -> Option<usize> {
+        match self.run_count {
+            RunCount::Never => Some(0),
+            RunCount::Times(n) => Some(n),
+            RunCount::Forever => None,
+        }
+    }
+
+    /// Specify a task to run, and schedule its next run
+    pub fn start_schedule(&mut self) -> &mut Self {
+        if let Some(e) = self.errors.first() {
+            panic!("{}", e);
+        }
+        if self.next_run.is_none() {
+            let now = self.now();
+            if self.from_registration {
+                self.anchor = Some(now.clone());
+            }
+            self.pick_random_offset();
+            let delay = self.initial_delay.filter(|_| self.can_run_again());
+            self.next_run = self.earliest_start(&now).and_then(|earliest| {
+                if let Some(delay) = delay {
+                    now.clone()
+                        .checked_add_signed(delay)
+                        .map(|delayed| delayed.max(earliest))
+                } else if self.run_immediately && self.can_run_again() {
+                    Some(earliest)
+                } else if earliest > now {
+                    // Allow the first run to be exactly at `earliest`
+                    self.next_run_time(&(earliest - chrono::Duration::seconds(1)))
+                } else {
+                    self.next_run_time(&now)
+                }
+            });
+            self.update_next_run_instant(&now);
+            if let Some(RepeatConfig {
+                repeats,
+                repeats_left,
+                ..
+            }) = &mut self.repeat_config
+            {
+                *repeats_left = *repeats;
+            }
+        }
+        self
+    }
+
+    /// Test whether a job is scheduled to run again. This is usually only called by
+    /// [Scheduler::run_pending()](::Scheduler::run_pending).
+    ///
+    /// This goes by `now`, even for jobs timed with
+    /// [`Scheduler::use_monotonic_for_intervals`](crate::Scheduler::use_monotonic_for_intervals),
+    /// since there's no telling which monotonic time goes with an arbitrary `now`.
+    pub fn is_pending(&self, now: &DateTime<Tz>) -> bool {
+        self.is_due(now, false)
+    }
+
+    /// Test whether a job is due to run. If `from_clock` is set, `now` was just read from the job's
+    /// clock, so jobs timed by the monotonic clock go by that instead.
+    pub(crate) fn is_due(&self, now: &DateTime<Tz>, from_clock: bool) -> bool {
+        if self.paused || self.in_cooldown(now) || self.over_daily_limit(now) {
+            return false;
+        }
+        match (&self.next_run_instant, &self.next_run) {
+            (Some(instant), _) if from_clock => self.monotonic_now() >= *instant,
+            (_, Some(dt)) => *dt <= *now,
+            _ => false,
+        }
+    }
+
+    /// If the pending run is later than allowed by [`skip_if_late_by`](Self::skip_if_late_by),
+    /// skip it and schedule the next one. Returns whether the run was skipped.
+    /// Skipped runs don't count towards [`count`](Self::count).
+    pub fn skip_if_late(&mut self, now: &DateTime<Tz>) -> bool {
+        let late = match (&self.next_run, self.max_lateness) {
+            (Some(next_run), Some(max_lateness)) => now.clone() - next_run.clone() > max_lateness,
+            _ => false,
+        };
+        let overtaken = self.catch_up == CatchUp::Skip && self.is_overtaken(now);
+        if late || overtaken {
+            self.replaying = None;
+            self.next_run = self.next_run_time(now);
+            self.update_next_run_instant(now);
+            if let Some(RepeatConfig {
+                repeats,
+                repeats_left,
+                ..
+            }) = &mut self.repeat_config
+            {
+                *repeats_left = *repeats;
+            }
+        }
+        late || overtaken
+    }
+
+    /// Has the pending run been overtaken by the one after it, so that at least one run has been missed?
+    pub(crate) fn is_overtaken(&self, now: &DateTime<Tz>) -> bool {
+        matches!(
+            self.next_run.as_ref().and_then(|next_run| self.next_run_time(next_run)),
+            Some(following) if following <= *now
+        )
+    }
+
+    /// Delay the next run by `delay`. Later runs follow the usual schedule.
+    pub(crate) fn defer(&mut self, delay: Duration) {
+        let now = self.now();
+        if let Some(next_run) = self.next_run.take() {
+            // A delay too long to represent puts the run off indefinitely
+            self.next_run = chrono::Duration::from_std(delay)
+                .ok()
+                .and_then(|delay| next_run.checked_add_signed(delay));
+        }
+        self.update_next_run_instant(&now);
+    }
+
+    /// Skip the next run, moving on to the one after it.
+    pub(crate) fn skip_next(&mut self) {
+        let now = self.now();
+        if let Some(next_run) = self.next_run.take() {
+            let from = if next_run > now {
+                next_run
+            } else {
+                now.clone()
+            };
+            self.next_run = self.next_run_time(&from);
+        }
+        self.replaying = None;
+        self.update_next_run_instant(&now);
+    }
+
+    /// How long until this job is next due, or `None` if it will never run again.
+    /// Overdue jobs are due in zero time. Like [`JobSchedule::is_pending`], this goes by `now`.
+    pub fn time_until_next_run(&self, now: &DateTime<Tz>) -> Option<Duration> {
+        self.time_until_due(now, false)
+    }
+
+    /// Like [`JobSchedule::time_until_next_run`]. See [`JobSchedule::is_due`] for `from_clock`.
+    pub(crate) fn time_until_due(&self, now: &DateTime<Tz>, from_clock: bool) -> Option<Duration> {
+        if !self.can_run_again() || self.paused {
+            return None;
+        }
+        if let (Some(instant), true) = (self.next_run_instant, from_clock) {
+            return Some(instant.saturating_duration_since(self.monotonic_now()));
+        }
+        self.next_run.as_ref().map(|next_run| {
+            (next_run.clone() - now.clone())
+                .to_std()
+                .unwrap_or_default()
+        })
+    }
+
+    /// Run a task and re-schedule it. This is usually only called by
+    /// [Scheduler::run_pending()](::Scheduler::run_pending).
+    pub fn schedule_next(&mut self, now: &DateTime<Tz>) {
+        // Don't do anything if we're run out of runs
+        if self.run_count == RunCount::Never {
+            return;
+        }
+        self.schedule_next_quietly(now);
+        self.notify_rescheduled();
+    }
+
+    /// A copy of this schedule without its callbacks or [`run_if`](Self::run_if) condition, for working
+    /// out when the job would run without changing it. The copy's failure count and last success
+    /// aren't shared with the job's.
+    pub(crate) fn detached_copy(&self) -> Self {
+        Self {
+            id: self.id,
+            frequency: self.frequency.clone(),
+            next_run: self.next_run.clone(),
+            last_run: self.last_run.clone(),
+            run_count: self.run_count,
+            configured_run_count: self.configured_run_count,
+            repeat_config: self.repeat_config.clone(),
+            on_finished: None,
+            on_reschedule: None,
+            every_nth: None,
+            monotonic: self.monotonic,
+            next_run_instant: self.next_run_instant,
+            max_lateness: self.max_lateness,
+            min_interval: self.min_interval,
+            run_immediately: self.run_immediately,
+            relative: self.relative,
+            from_registration: self.from_registration,
+            anchor: self.anchor.clone(),
+            label: self.label.clone(),
+            description: self.description.clone(),
+            tags: self.tags.clone(),
+            priority: self.priority,
+            group: self.group.clone(),
+            paused: self.paused,
+            times_run: self.times_run,
+            random_window: self.random_window,
+            random_offset: self.random_offset,
+            rng: self.rng.clone(),
+            step_window: self.step_window,
+            business_hours: self.business_hours.clone(),
+            excluded_days: self.excluded_days,
+            dst_policy: self.dst_policy,
+            errors: self.errors.clone(),
+            start_instant: self.start_instant,
+            min_uptime: self.min_uptime,
+            initial_delay: self.initial_delay,
+            catch_up: self.catch_up,
+            catch_up_spacing: self.catch_up_spacing,
+            replaying: self.replaying.clone(),
+            condition: None,
+            probability: self.probability,
+            max_per_day: self.max_per_day,
+            runs_today: self.runs_today,
+            min_gap: self.min_gap,
+            failures: Arc::new(AtomicU32::new(self.failures.load(Ordering::SeqCst))),
+            last_success: Arc::new(AtomicI64::new(self.last_success.load(Ordering::SeqCst))),
+            cadence_from_success: self.cadence_from_success,
+            max_failures: self.max_failures,
+            on_disabled: None,
+            context: JobContext {
+                interval: self.context.interval,
+                next_run: self.context.next_run.clone(),
+            },
+            clock: self.clock.clone(),
+            tz: self.tz.clone(),
+            _tp: PhantomData,
+        }
+    }
+
+    /// Reschedule the job as if it had run at `now`, but without running it or calling any callbacks,
+    /// returning whether it would have run. Runs that are too late or over the daily limit are skipped as usual.
+    /// Used by [`Scheduler::dry_run`](crate::Scheduler::dry_run), on a [`detached_copy`](Self::detached_copy).
+    pub(crate) fn run_dry(&mut self, now: &DateTime<Tz>) -> bool {
+        if !self.can_run_again() || self.skip_if_late(now) || self.skip_if_over_daily_limit(now) {
+            return false;
+        }
+        self.schedule_next_quietly(now);
+        true
+    }
+
+    /// Like [`JobSchedule::schedule_next`], but without calling the `on_reschedule` or `on_finished` callbacks.
+    pub(crate) fn schedule_next_quietly(&mut self, now: &DateTime<Tz>) {
+        if self.run_count == RunCount::Never {
+            return;
+        }
+        self.times_run += 1;
+        self.runs_today = Some((now.date_naive(), self.runs_on_day_of(now) + 1));
+
+        let context = std::mem::replace(&mut self.context, JobContext::new());
+        if let Some(interval) = context.interval {
+            self.frequency = vec![self.new_frequency(interval)];
+        }
+        if let Some(next) = context.next_run {
+            self.replaying = None;
+            self.next_run = Some(at_least_minimum(next, now, self.min_interval));
+        } else if let Some((missed, next)) = self.next_missed_run(now) {
+            // Replay the missed run now, or after the spacing if there is one
+            self.replaying = Some(missed);
+            self.next_run = Some(next);
+        } else {
+            self.replaying = None;
+            self.schedule_after(now);
+        }
+        if let Some(earliest) = self
+            .min_gap
+            .and_then(|gap| now.clone().checked_add_signed(gap))
+        {
+            if self.next_run.as_ref().is_some_and(|next| *next < earliest) {
+                self.next_run = Some(earliest);
+            }
+        }
+        self.wait_if_over_daily_limit(now);
+        self.update_next_run_instant(now);
+
+        self.last_run = Some(now.clone());
+        self.run_count = match self.run_count {
+            RunCount::Never => RunCount::Never,
+            RunCount::Times(n) if n > 1 => RunCount::Times(n - 1),
+            RunCount::Times(_) => RunCount::Never,
+            RunCount::Forever => RunCount::Forever,
+        };
+    }
+
+    /// Call the `on_reschedule` callback, and `on_finished` if the job won't run again, once the job has
+    /// been rescheduled with [`JobSchedule::schedule_next_quietly`].
+    pub(crate) fn notify_rescheduled(&mut self) {
+        if let Some(on_reschedule) = self.on_reschedule.as_mut() {
+            let finished = self.run_count == RunCount::Never;
+            let next_run = self.next_run.as_ref().filter(|_| !finished);
+            on_reschedule(next_run);
+        }
+        if self.run_count == RunCount::Never {
+            if let Some(on_finished) = self.on_finished.take() {
+                on_finished();
+            }
+        }
+    }
+
+    /// For [`cadence_from_success`](Self::cadence_from_success): the first run timed from the last success
+    /// that's after `now`. Runs missed since then, because runs failed, are skipped.
+    fn next_run_time_after_success(
+        &self,
+        success: &DateTime<Tz>,
+        now: &DateTime<Tz>,
+        last_offset: chrono::Duration,
+    ) -> Option<DateTime<Tz>> {
+        let mut next = self.next_run_time_from(success, last_offset)?;
+        while next <= *now {
+            match self.next_run_time(&next) {
+                Some(following) if following > next => next = following,
+                // Zero-length intervals never catch up
+                _ => return self.next_run_time(now),
+            }
+        }
+        Some(at_least_minimum(next, now, self.min_interval))
+    }
+
+    /// Schedule the next run after `now`, following the usual schedule and any repeats.
+    fn schedule_after(&mut self, now: &DateTime<Tz>) {
+        // We compute this up front since we can't borrow self immutably while doing this next bit
+        let last_offset = self.random_offset;
+        self.pick_random_offset();
+        let next_run_time = match self.last_success().filter(|_| self.cadence_from_success) {
+            Some(success) => self.next_run_time_after_success(&success, now, last_offset),
+            None => self.next_run_time_from(now, last_offset),
+        };
+        match &mut self.repeat_config {
+            Some(RepeatConfig {
+                repeats,
+                repeats_left,
+                repeat_interval,
+            }) => {
+                if *repeats_left > 0 {
+                    *repeats_left -= 1;
+                    // Normal scheduling is aligned with the day: if you ask for something every hour, it will
+                    // run at the start of the next hour, not one hour after being scheduled.
+                    // For repeats, though, we want them aligned the first run of the repeats.
+                    // This means we want to align with with next_run (which should be not far in the past),
+                    // or if it's somehow unavailable, the current time.
+                    // It's possible that we're really far behind. If so, find the next repeat interval that's
+                    // still in the future (relative to when we start this run.)
+                    let mut next = self.next_run.as_ref().unwrap_or(now).clone();
+                    loop {
+                        let following = repeat_interval.next_from(&next);
+                        if following <= next {
+                            // Zero-length repeat intervals never catch up
+                            break;
+                        }
+                        next = following;
+                        if next > *now {
+                            break;
+                        }
+                    }
+                    self.next_run = Some(at_least_minimum(next, now, self.min_interval));
+                } else {
+                    self.next_run = next_run_time;
+                    *repeats_left = *repeats;
+                }
+            }
+            None => self.next_run = next_run_time,
+        }
+    }
+}
+
+/// The jobs in the order they should run when due at the same time: highest priority first,
+/// and then in the order they were added.
+pub(crate) fn by_priority<J: WithSchedule<Tz, Tp>, Tz, Tp>(jobs: &mut [J]) -> Vec<&mut J>
+where
+    Tz: TimeZone + Sync + Send,
+    Tp: TimeProvider,
+{
+    let mut jobs: Vec<_> = jobs.iter_mut().collect();
+    jobs.sort_by_key(|job| std::cmp::Reverse(job.schedule().get_priority()));
+    jobs
+}
+
+/// Call `f` on the schedule of every job in the group `name`, returning how many jobs are in the group.
+pub(crate) fn for_each_in_group<J: WithSchedule<Tz, Tp>, Tz, Tp>(
+    jobs: &mut [J],
+    name: &str,
+    mut f: impl FnMut(&mut JobSchedule<Tz, Tp>),
+) -> usize
+where
+    Tz: TimeZone + Sync + Send,
+    Tp: TimeProvider,
+{
+    let mut count = 0;
+    for job in jobs.iter_mut().filter(|job| job.schedule().in_group(name)) {
+        f(job.schedule_mut());
+        count += 1;
+    }
+    count
+}
+
+/// Remove every job in the group `name`, returning how many jobs were removed.
+pub(crate) fn remove_group<J: WithSchedule<Tz, Tp>, Tz, Tp>(jobs: &mut Vec<J>, name: &str) -> usize
+where
+    Tz: TimeZone + Sync + Send,
+    Tp: TimeProvider,
+{
+    let before = jobs.len();
+    jobs.retain(|job| !job.schedule().in_group(name));
+    before - jobs.len()
+}
+
+/// The value of [`JobSchedule::last_success_millis`] before any run has succeeded
+const NO_SUCCESS: i64 = i64::MIN;
+
+/// Note that a run that started at `started` (in milliseconds since the epoch) has succeeded.
+pub(crate) fn record_success(last_success: &AtomicI64, started: i64) {
+    // Async runs can finish out of order
+    last_success.fetch_max(started, Ordering::SeqCst);
+}
+
+/// Update a job's count of consecutive failures with the outcome of a run.
+pub(crate) fn record_outcome(failures: &AtomicU32, success: bool) {
+    if success {
+        failures.store(0, Ordering::SeqCst);
+    } else {
+        let _ = failures.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+            Some(n.saturating_add(1))
+        });
+    }
+}
+
+/// Keep runs at least `min_interval` apart, and make sure that the next run is after `now`,
+/// so that zero-length intervals don't make a job run over and over again.
+fn at_least_minimum<Tz: TimeZone>(
+    next: DateTime<Tz>,
+    now: &DateTime<Tz>,
+    min_interval: chrono::Duration,
+) -> DateTime<Tz> {
+    let earliest = now.clone() + min_interval;
+    let next = if next < earliest { earliest } else { next };
+    if next > *now {
+        next
+    } else {
+        Interval::Seconds(1).next(now)
+    }
+}
+
+/// Opening hours on weekdays in a time zone, for [`JobSchedule::business_hours`]
+#[derive(Clone)]
+struct BusinessHours {
+    open: NaiveTime,
+    length: chrono::Duration,
+    zone: Arc<dyn Zone>,
+}
+
+impl fmt::Debug for BusinessHours {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BusinessHours")
+            .field("open", &self.open)
+            .field("length", &self.length)
+            .finish()
+    }
+}
+
+impl BusinessHours {
+    /// Is it within business hours at `utc`? Hours that cross midnight belong to the day they open.
+    fn is_open(&self, utc: &NaiveDateTime) -> bool {
+        let local = self.zone.local(utc);
+        let since_open = (local.time() - self.open).num_seconds().rem_euclid(86400);
+        let opened = local - chrono::Duration::seconds(since_open);
+        since_open < self.length.num_seconds() && is_weekday(opened.weekday())
+    }
+
+    /// The next time after `utc` that business hours start, in UTC
+    fn next_opening(&self, utc: &NaiveDateTime) -> NaiveDateTime {
+        let local = self.zone.local(utc);
+        let mut opening = local.date().and_time(self.open);
+        while opening <= local || !is_weekday(opening.weekday()) {
+            opening += chrono::Duration::days(1);
+        }
+        self.zone.utc(&opening)
+    }
+}
+
+fn is_weekday(day: Weekday) -> bool {
+    !matches!(day, Weekday::Sat | Weekday::Sun)
+}
+
+/// Converts between UTC and local times in a time zone of any type, so that a job can use a different
+/// time zone to its scheduler for [`JobSchedule::business_hours`].
+trait Zone: Send + Sync {
+    fn local(&self, utc: &NaiveDateTime) -> NaiveDateTime;
+    fn utc(&self, local: &NaiveDateTime) -> NaiveDateTime;
+}
+
+impl<Z: TimeZone + Send + Sync> Zone for Z {
+    fn local(&self, utc: &NaiveDateTime) -> NaiveDateTime {
+        self.from_utc_datetime(utc).naive_local()
+    }
+
+    fn utc(&self, local: &NaiveDateTime) -> NaiveDateTime {
+        match self.from_local_datetime(local).earliest() {
+            Some(time) => time.naive_utc(),
+            // The local time was skipped by a daylight saving time change; use the offset from before the change,
+            // which gives a time just after it
+            None => {
+                let before = *local - chrono::Duration::days(1);
+                let offset = self.offset_from_utc_datetime(&before).fix();
+                *local - chrono::Duration::seconds(i64::from(offset.local_minus_utc()))
+            }
+        }
+    }
+}
+
+/// A time of day for [`JobSchedule::explain`], leaving out the seconds if there aren't any
+fn explain_time(time: NaiveTime) -> String {
+    if time.second() == 0 {
+        time.format("%H:%M").to_string()
+    } else {
+        time.format("%H:%M:%S").to_string()
+    }
+}
+
+/// An interval without its leading "every", e.g. "30 seconds"
+fn explain_interval(interval: Interval) -> String {
+    let explanation = interval.to_string();
+    match explanation.strip_prefix("every ") {
+        Some(rest) => rest.to_owned(),
+        None => explanation,
+    }
+}
+
+/// A duration in the largest unit that it's a whole number of, e.g. "90 minutes".
+/// Durations too long to represent are described as "forever".
+fn explain_duration(duration: Option<chrono::Duration>) -> String {
+    let duration = match duration {
+        Some(duration) => duration,
+        None => return "forever".to_owned(),
+    };
+    let seconds = duration.num_seconds();
+    if duration.subsec_nanos() != 0 || seconds == 0 {
+        return format!("{} ms", duration.num_milliseconds());
+    }
+    let (count, unit) = [(86400, "day"), (3600, "hour"), (60, "minute")]
+        .iter()
+        .find(|(length, _)| seconds % length == 0)
+        .map_or((seconds, "second"), |&(length, unit)| {
+            (seconds / length, unit)
+        });
+    let plural = if count == 1 { "" } else { "s" };
+    format!("{} {}{}", count, unit, plural)
+}
+
+fn next_of<Tz: TimeZone>(
+    frequency: &[RunConfig],
+    from: &DateTime<Tz>,
+    relative: bool,
+    anchor: Option<&DateTime<Tz>>,
+) -> Option<DateTime<Tz>> {
+    if let Some(anchor) = anchor {
+        frequency
+            .iter()
+            .filter_map(|freq| next_from_anchor(freq, anchor, from))
+            .min()
+    } else if relative {
+        frequency.iter().map(|freq| freq.next_from(from)).min()
+    } else {
+        frequency.iter().map(|freq| freq.next(from)).min()
+    }
+}
+
+/// The first of `anchor + k * freq`, for `k >= 1`, that's after `from`. See [`JobSchedule::from_registration`].
+fn next_from_anchor<Tz: TimeZone>(
+    freq: &RunConfig,
+    anchor: &DateTime<Tz>,
+    from: &DateTime<Tz>,
+) -> Option<DateTime<Tz>> {
+    let period = freq
+        .fixed_period()
+        .map(|period| period.num_seconds())
+        .filter(|&period| period > 0);
+    if let Some(period) = period {
+        let elapsed = (from.clone() - anchor.clone()).num_seconds().max(0);
+        let periods = elapsed / period + 1;
+        return anchor
+            .clone()
+            .checked_add_signed(chrono::Duration::seconds(periods.checked_mul(period)?));
+    }
+    // Intervals without a fixed length have to be stepped through
+    let mut next = anchor.clone();
+    loop {
+        let following = freq.next_from(&next);
+        if following <= next {
+            // Zero-length intervals never move
+            return None;
+        }
+        next = following;
+        if next > *from {
+            return Some(next);
+        }
+    }
+}
+
+/// Lets a job change its own schedule while it runs, e.g. to poll more often when busy.
+/// Passed to the task given to [`SyncJob::run_with_context`](crate::SyncJob::run_with_context).
+/// Changes take effect when the job is rescheduled after the current run.
+#[derive(Debug)]
+pub struct JobContext<Tz: TimeZone> {
+    interval: Option<Interval>,
+    next_run: Option<DateTime<Tz>>,
+}
+
+impl<Tz: TimeZone> JobContext<Tz> {
+    pub(crate) fn new() -> Self {
+        JobContext {
+            interval: None,
+            next_run: None,
+        }
+    }
+
+    /// Run every `interval` from now on, replacing the job's schedule, including any times of day
+    /// and offsets. The next run is worked out from the new interval.
+    pub fn set_interval(&mut self, interval: Interval) {
+        self.interval = Some(interval);
+    }
+
+    /// Run next at `next_run`, rather than at the next scheduled time. Runs after that follow
+    /// the schedule as usual. If the interval is also changed, this still decides the next run.
+    ///
+    /// Like any other run, the next run is always after the current one, and at least the scheduler's
+    /// [minimum interval](crate::Scheduler::set_minimum_interval) later. A `next_run` earlier than that is moved
+    /// forward, so that a job can't make itself run over and over again.
+    pub fn set_next_run(&mut self, next_run: DateTime<Tz>) {
+        self.next_run = Some(next_run);
+    }
+}
+
+/// A copy of a job's schedule: its intervals, times, repeats and number of runs.
+/// Use this to create similar jobs with [`Scheduler::every_like`](crate::Scheduler::every_like).
+/// Created by [`Job::schedule_config`](crate::Job::schedule_config).
+#[derive(Debug, Clone)]
+pub struct ScheduleConfig {
+    frequency: Vec<RunConfig>,
+    run_count: RunCount,
+    repeat_config: Option<RepeatConfig>,
+    relative: bool,
+    cadence_from_success: bool,
+    from_registration: bool,
+    step_window: Option<(chrono::Duration, chrono::Duration)>,
+    business_hours: Option<BusinessHours>,
+    excluded_days: WeekdaySet,
+}
+
+impl ScheduleConfig {
+    /// The job's first interval
+    pub(crate) fn interval(&self) -> Interval {
+        self.frequency[0].base()
+    }
+}
+
+/// Reads back what serializing a job's schedule writes: its intervals, times, repeats and number of runs.
+/// The description and tags are ignored, since they belong to the job rather than its schedule,
+/// and everything else that isn't serialized, such as business hours, is left unset.
+/// ```rust
+/// # use clokwerk::*;
+/// let config: ScheduleConfig = serde_json::from_str(r#"{
+///     "frequency": [{"base": {"Days": 1}, "time": "02:00:00", "offsets": []}],
+///     "run_count": "Forever",
+///     "repeat_config": null
+/// }"#).unwrap();
+/// let mut scheduler = Scheduler::new();
+/// scheduler.every_like(&config, || println!("Backing up"));
+/// ```
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ScheduleConfig {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "JobSchedule")]
+        struct Serialized {
+            frequency: Vec<RunConfig>,
+            run_count: RunCount,
+            repeat_config: Option<RepeatConfig>,
+        }
+        let serialized = Serialized::deserialize(deserializer)?;
+        if serialized.frequency.is_empty() {
+            return Err(serde::de::Error::invalid_length(
+                0,
+                &"at least one frequency",
+            ));
+        }
+        Ok(ScheduleConfig {
+            frequency: serialized.frequency,
+            run_count: serialized.run_count,
+            repeat_config: serialized.repeat_config,
+            relative: false,
+            cadence_from_success: false,
+            from_registration: false,
+            step_window: None,
+            business_hours: None,
+            excluded_days: WeekdaySet::empty(),
+        })
+    }
+}
+
+/// An iterator over the times a job is scheduled to run.
+/// Created by [`Job::fire_times`](crate::Job::fire_times).
+pub struct FireTimes<Tz: TimeZone> {
+    frequency: Vec<RunConfig>,
+    repeat: Option<(usize, Interval)>,
+    repeats_left: usize,
+    runs_left: Option<usize>,
+    relative: bool,
+    anchor: Option<DateTime<Tz>>,
+    next: Option<DateTime<Tz>>,
+}
+
+impl<Tz: TimeZone> Iterator for FireTimes<Tz> {
+    type Item = DateTime<Tz>;
+
+    fn next(&mut self) -> Option<DateTime<Tz>> {
+        if self.runs_left == Some(0) {
+            return None;
+        }
+        let current = self.next.take()?;
+        if let Some(runs_left) = &mut self.runs_left {
+            *runs_left -= 1;
+        }
+        // This mirrors JobSchedule::schedule_next, assuming each run happens exactly on time
+        self.next = match self.repeat {
+            Some((_, repeat_interval)) if self.repeats_left > 0 => {
+                self.repeats_left -= 1;
+                Some(repeat_interval.next_from(&current))
+            }
+            Some((repeats, _)) => {
+                self.repeats_left = repeats;
+                next_of(
+                    &self.frequency,
+                    &current,
+                    self.relative,
+                    self.anchor.as_ref(),
+                )
+            }
+            None => next_of(
+                &self.frequency,
+                &current,
+                self.relative,
+                self.anchor.as_ref(),
+            ),
+        };
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CatchUp, JobSchedule, RepeatConfig};
+    use crate::{
+        intervals::*,
+        timeprovider::{MockClock, TimeProvider},
+        BuildError, Job, ScheduleWarning, SyncJob,
+    };
+    use chrono::prelude::*;
+
+    #[test]
+    fn test_repeating() {
+        fn utc_hms(h: u32, m: u32, s: u32) -> DateTime<Utc> {
+            Utc.from_utc_datetime(
+                &NaiveDate::from_ymd_opt(2020, 6, 16)
+                    .unwrap()
+                    .and_hms_opt(h, m, s)
+                    .unwrap(),
+            )
+        }
+        struct TestTimeProvider;
+        impl TimeProvider for TestTimeProvider {
+            fn now<Tz>(tz: &Tz) -> chrono::DateTime<Tz>
+            where
+                Tz: chrono::TimeZone + Sync + Send,
+            {
+                utc_hms(7, 58, 0).with_timezone(tz)
+            }
+        }
+        let mut job = SyncJob::<Utc, TestTimeProvider>::new(1.hour(), Utc);
+        job.repeating_every(45.minutes()).times(2);
+        job.run(|| {});
+
+        assert!(!job.is_pending(&utc_hms(7, 59, 0)));
+        assert!(job.is_pending(&utc_hms(8, 0, 0)));
+        job.execute(&utc_hms(8, 0, 0));
+
+        assert!(!job.is_pending(&utc_hms(8, 44, 0)));
+        assert!(job.is_pending(&utc_hms(8, 45, 0)));
+        job.execute(&utc_hms(8, 45, 0));
+
+        // Skips 9:00 because it's still repeating at 45 minutes
+        assert!(!job.is_pending(&utc_hms(9, 0, 0)));
+
+        assert!(!job.is_pending(&utc_hms(9, 29, 0)));
+        assert!(job.is_pending(&utc_hms(9, 30, 0)));
+        job.execute(&utc_hms(9, 30, 0));
+
+        assert!(!job.is_pending(&utc_hms(9, 59, 0)));
+        assert!(job.is_pending(&utc_hms(10, 0, 0)));
     }
 
     #[test]
@@ -306,6 +2054,759 @@ mod test {
         // &String
         job.try_at(&format!("{}:{}", 12, 32)).unwrap();
         // NaiveTime
-        job.at_time(NaiveTime::from_hms(12, 32, 0));
+        job.at_time(NaiveTime::from_hms_opt(12, 32, 0).unwrap());
+    }
+
+    #[test]
+    fn test_at_plus() {
+        let mut job = JobSchedule::<Utc>::new(1.day(), Utc);
+        job.at("09:00").plus(30.seconds());
+        let now = Utc.with_ymd_and_hms(2020, 6, 16, 8, 0, 0).unwrap();
+        assert_eq!(
+            job.next_run_time(&now),
+            Some(Utc.with_ymd_and_hms(2020, 6, 16, 9, 0, 30).unwrap())
+        );
+        let now = Utc.with_ymd_and_hms(2020, 6, 16, 9, 0, 30).unwrap();
+        assert_eq!(
+            job.next_run_time(&now),
+            Some(Utc.with_ymd_and_hms(2020, 6, 17, 9, 0, 30).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_scheduled_prev() {
+        let mut job = JobSchedule::<Utc>::new(Interval::Monday, Utc);
+        job.at("09:00").and_every(Interval::Thursday).at("14:00");
+        // Wednesday
+        let now = Utc.with_ymd_and_hms(2020, 6, 17, 8, 0, 0).unwrap();
+        assert_eq!(
+            job.scheduled_prev(now),
+            Some(Utc.with_ymd_and_hms(2020, 6, 15, 9, 0, 0).unwrap())
+        );
+        // Friday
+        let now = Utc.with_ymd_and_hms(2020, 6, 19, 8, 0, 0).unwrap();
+        assert_eq!(
+            job.scheduled_prev(now),
+            Some(Utc.with_ymd_and_hms(2020, 6, 18, 14, 0, 0).unwrap())
+        );
+        job.run_count = super::RunCount::Never;
+        assert_eq!(job.scheduled_prev(now), None);
+    }
+
+    #[test]
+    fn test_different_times_on_different_days() {
+        let mut job = JobSchedule::<Utc>::new(Interval::Monday, Utc);
+        job.at("09:00").and_every(Interval::Thursday).at("14:00");
+        let expected = vec![
+            Utc.with_ymd_and_hms(2020, 6, 18, 14, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2020, 6, 22, 9, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2020, 6, 25, 14, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2020, 6, 29, 9, 0, 0).unwrap(),
+        ];
+        // Monday, after the Monday run
+        let now = Utc.with_ymd_and_hms(2020, 6, 15, 10, 0, 0).unwrap();
+        let times: Vec<_> = job.fire_times(now).take(4).collect();
+        assert_eq!(times, expected);
+
+        // Running the job gives the same times
+        let mut now = now;
+        let mut times = vec![];
+        for _ in 0..4 {
+            now = job.next_run_time(&now).unwrap();
+            times.push(now);
+            job.schedule_next(&now);
+            assert_eq!(job.next_run, job.next_run_time(&now));
+        }
+        assert_eq!(times, expected);
+
+        // Each day only uses its own time
+        let thursday_morning = Utc.with_ymd_and_hms(2020, 6, 18, 9, 0, 0).unwrap();
+        assert_eq!(job.next_run_time(&thursday_morning), Some(expected[0]));
+        let monday_afternoon = Utc.with_ymd_and_hms(2020, 6, 22, 14, 0, 0).unwrap();
+        assert_eq!(job.next_run_time(&monday_afternoon), Some(expected[2]));
+    }
+
+    #[test]
+    fn test_at_times() {
+        let mut job = JobSchedule::<Utc>::new(Interval::Weekday, Utc);
+        job.at_times(&[
+            NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(12, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+        ]);
+        let at = |d, h, m| Utc.with_ymd_and_hms(2020, 6, d, h, m, 0).unwrap();
+        // Friday the 19th, then the weekend
+        let cases = [
+            (at(19, 8, 59), at(19, 9, 0)),
+            (at(19, 9, 0), at(19, 12, 0)),
+            (at(19, 11, 59), at(19, 12, 0)),
+            (at(19, 12, 0), at(19, 17, 0)),
+            (at(19, 16, 59), at(19, 17, 0)),
+            (at(19, 17, 0), at(22, 9, 0)),
+            (at(20, 12, 0), at(22, 9, 0)),
+        ];
+        for (now, expected) in cases {
+            assert_eq!(job.next_run_time(&now), Some(expected), "from {}", now);
+        }
+    }
+
+    #[test]
+    fn test_every_with_phase() {
+        let mut job = JobSchedule::<Utc>::new(1.day(), Utc);
+        job.every_with_phase(15.minutes(), 5.minutes());
+        let at = |h, m, s| Utc.with_ymd_and_hms(2020, 6, 19, h, m, s).unwrap();
+        let cases = [
+            // Around the hour boundary
+            (at(11, 50, 0), at(12, 5, 0)),
+            (at(11, 59, 59), at(12, 5, 0)),
+            (at(12, 0, 0), at(12, 5, 0)),
+            // Around the phase crossing
+            (at(12, 4, 59), at(12, 5, 0)),
+            (at(12, 5, 0), at(12, 20, 0)),
+            (at(12, 5, 1), at(12, 20, 0)),
+            (at(12, 19, 59), at(12, 20, 0)),
+            (at(12, 50, 0), at(13, 5, 0)),
+            (
+                at(23, 55, 0),
+                Utc.with_ymd_and_hms(2020, 6, 20, 0, 5, 0).unwrap(),
+            ),
+        ];
+        for (now, expected) in cases {
+            assert_eq!(job.next_run_time(&now), Some(expected), "from {}", now);
+        }
+
+        // The phase isn't rounded up to a multiple of itself, as with `plus`
+        job.every_with_phase(15.minutes(), 7.minutes());
+        assert_eq!(job.next_run_time(&at(12, 7, 0)), Some(at(12, 22, 0)));
+        assert_eq!(job.scheduled_prev(at(12, 7, 0)), Some(at(11, 52, 0)));
+    }
+
+    #[test]
+    fn test_every_other() {
+        let _guard = crate::timeprovider::lock_mock_clock();
+        let at = |m, d, h| Utc.with_ymd_and_hms(2020, m, d, h, 0, 0).unwrap();
+        // Wednesday, and the Wednesday a week later, to check both weeks of the fortnight
+        for (now, first) in [(at(6, 17, 8), 23), (at(6, 24, 8), 30)] {
+            MockClock::set(now);
+            let mut job = JobSchedule::<Utc, MockClock>::new(1.day(), Utc);
+            job.every_other_week_on(Weekday::Tue).at("10:00");
+            let times: Vec<_> = job.fire_times(now).take(3).collect();
+            let first = at(6, first, 10);
+            let fortnight = chrono::Duration::weeks(2);
+            assert_eq!(times, vec![first, first + fortnight, first + fortnight * 2]);
+        }
+
+        // On the day itself, the job runs today if it still can
+        MockClock::set(at(6, 17, 8));
+        let mut job = JobSchedule::<Utc, MockClock>::new(1.day(), Utc);
+        job.every_other_week_on(Weekday::Wed).at("10:00");
+        let times: Vec<_> = job.fire_times(at(6, 17, 8)).take(2).collect();
+        assert_eq!(times, vec![at(6, 17, 10), at(7, 1, 10)]);
+
+        for now in [at(6, 17, 8), at(6, 18, 8)] {
+            MockClock::set(now);
+            let mut job = JobSchedule::<Utc, MockClock>::new(1.day(), Utc);
+            job.every_other_day().at("09:00");
+            let today = now.date_naive();
+            let times: Vec<_> = job
+                .fire_times(now)
+                .take(2)
+                .map(|time| (time.date_naive() - today).num_days())
+                .collect();
+            assert_eq!(times, vec![0, 2]);
+        }
+        // Without a time of day, today's run has already passed
+        let mut job = JobSchedule::<Utc, MockClock>::new(1.day(), Utc);
+        job.every_other_day();
+        assert_eq!(job.next_run_time(&at(6, 18, 8)), Some(at(6, 20, 0)));
+    }
+
+    #[test]
+    fn test_explain() {
+        let mut job = JobSchedule::<Utc>::new(1.hour(), Utc);
+        assert_eq!(job.explain(), "Every 1 hour.");
+
+        job.every_with_phase(15.minutes(), 5.minutes())
+            .and_every(Interval::Saturday)
+            .at("10:30:15")
+            .plus(30.seconds())
+            .relative_to_start()
+            .skip_if_late_by(std::time::Duration::from_secs(90))
+            .catch_up(CatchUp::RunAll)
+            .catch_up_spacing(std::time::Duration::from_secs(2 * 3600))
+            .min_gap(std::time::Duration::from_millis(1500))
+            .disable_after_failures(3)
+            .count(2);
+        job.pause();
+        assert_eq!(
+            job.explain(),
+            concat!(
+                "Every 15 minutes, 5 minutes after each boundary and every Saturday at 10:30:15 plus 30 seconds, ",
+                "counted from the start of the previous run, skipping runs more than 90 seconds late, ",
+                "replaying each missed run, 2 hours apart, at least 1500 ms apart, ",
+                "stopping after 3 failures in a row, 2 more times, currently paused."
+            )
+        );
+
+        let mut job = JobSchedule::<Utc>::new(1.day(), Utc);
+        job.at_random_between(
+            NaiveTime::from_hms_opt(23, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(1, 0, 0).unwrap(),
+        )
+        .once();
+        assert_eq!(
+            job.explain(),
+            "Every 1 day at a random time between 23:00 and 01:00, once more."
+        );
+
+        let mut job = JobSchedule::<Utc>::new(Interval::Weekday, Utc);
+        job.every_step_in_window(
+            2.hours(),
+            NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+        )
+        .catch_up(CatchUp::Skip)
+        .run_immediately();
+        assert_eq!(
+            job.explain(),
+            "Every weekday, every 2 hours from 08:00 to 18:00, running once straight away, skipping missed runs."
+        );
+
+        let mut job = JobSchedule::<Utc>::new(1.minute(), Utc);
+        job.with_probability(0.07);
+        assert_eq!(
+            job.explain(),
+            "Every 1 minute, only running 7% of the time."
+        );
+
+        let mut job = JobSchedule::<Utc>::new(1.hour(), Utc);
+        job.catch_up(CatchUp::RunAll);
+        assert_eq!(
+            job.explain(),
+            "Every 1 hour, replaying each missed run back to back."
+        );
+        job.catch_up_spacing(std::time::Duration::MAX);
+        assert_eq!(job.explain(), "Every 1 hour, not replaying missed runs.");
+    }
+
+    #[test]
+    fn test_business_hours() {
+        let hms = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+        // 19 June 2020 is a Friday
+        let utc = |d, h, m| Utc.with_ymd_and_hms(2020, 6, d, h, m, 0).unwrap();
+        let new_york = FixedOffset::west_opt(5 * 3600).unwrap();
+        let local = |d, h, m| {
+            new_york
+                .with_ymd_and_hms(2020, 6, d, h, m, 0)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        let mut job = JobSchedule::<Utc>::new(1.hour(), Utc);
+        job.business_hours(new_york, hms(9, 0), hms(17, 0));
+        let cases = [
+            // During the day
+            (local(19, 15, 30), local(19, 16, 0)),
+            (local(18, 9, 0), local(18, 10, 0)),
+            // Before opening, the first run is at opening time
+            (local(18, 3, 0), local(18, 9, 0)),
+            (local(18, 8, 59), local(18, 9, 0)),
+            // Closing time is outside the hours, so the job waits until the next day
+            (local(18, 16, 0), local(19, 9, 0)),
+            (local(18, 23, 0), local(19, 9, 0)),
+            // Over the weekend
+            (local(19, 16, 30), local(22, 9, 0)),
+            (local(20, 12, 0), local(22, 9, 0)),
+            (local(21, 23, 30), local(22, 9, 0)),
+        ];
+        for (now, expected) in cases {
+            assert_eq!(job.next_run_time(&now), Some(expected), "from {}", now);
+        }
+        // Midnight UTC on Saturday is still Friday in New York, but after closing time
+        assert_eq!(job.next_run_time(&utc(19, 23, 0)), Some(local(22, 9, 0)));
+        assert_eq!(
+            job.explain(),
+            "Every 1 hour, only on weekdays from 09:00 to 17:00."
+        );
+
+        // The first scheduled time after opening, if that isn't a scheduled time
+        let mut job = JobSchedule::<Utc>::new(45.minutes(), Utc);
+        job.business_hours(Utc, hms(9, 10), hms(17, 0));
+        assert_eq!(job.next_run_time(&utc(18, 17, 0)), Some(utc(19, 9, 45)));
+
+        // Hours that cross midnight belong to the day they open
+        let mut job = JobSchedule::<Utc>::new(1.hour(), Utc);
+        job.business_hours(Utc, hms(22, 0), hms(6, 0));
+        assert_eq!(job.next_run_time(&utc(19, 12, 0)), Some(utc(19, 22, 0)));
+        assert_eq!(job.next_run_time(&utc(20, 4, 0)), Some(utc(20, 5, 0)));
+        assert_eq!(job.next_run_time(&utc(20, 5, 0)), Some(utc(22, 22, 0)));
+        assert_eq!(job.next_run_time(&utc(21, 23, 0)), Some(utc(22, 22, 0)));
+
+        // A job scheduled outside the hours never runs
+        let mut job = JobSchedule::<Utc>::new(1.day(), Utc);
+        job.at("07:00")
+            .business_hours(new_york, hms(9, 0), hms(17, 0));
+        assert_eq!(job.next_run_time(&utc(19, 12, 0)), None);
+    }
+
+    #[test]
+    fn test_exclude_weekdays() {
+        // 18 June 2020 is a Thursday
+        let at = |d, h| Utc.with_ymd_and_hms(2020, 6, d, h, 0, 0).unwrap();
+        let mut job = JobSchedule::<Utc>::new(Interval::Weekday, Utc);
+        job.at("17:00").exclude_weekdays(&[Weekday::Fri]);
+        assert_eq!(job.next_run_time(&at(18, 12)), Some(at(18, 17)));
+        // Thursday's run is followed by Monday's
+        assert_eq!(job.next_run_time(&at(18, 17)), Some(at(22, 17)));
+        assert_eq!(job.next_run_time(&at(19, 12)), Some(at(22, 17)));
+        assert_eq!(job.next_run_time(&at(22, 17)), Some(at(23, 17)));
+        assert_eq!(job.explain(), "Every weekday at 17:00, except Friday.");
+
+        // Excluding more days adds to the ones already excluded
+        let mut job = JobSchedule::<Utc>::new(1.hour(), Utc);
+        job.exclude_weekdays(&[Weekday::Sat])
+            .exclude_weekdays(&[Weekday::Sun]);
+        assert_eq!(job.next_run_time(&at(19, 22)), Some(at(19, 23)));
+        assert_eq!(job.next_run_time(&at(19, 23)), Some(at(22, 0)));
+        assert_eq!(job.explain(), "Every 1 hour, except Saturday and Sunday.");
+
+        let mut job = JobSchedule::<Utc>::new(1.day(), Utc);
+        job.exclude_weekdays(&[
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+            Weekday::Sat,
+            Weekday::Sun,
+        ]);
+        assert_eq!(job.next_run_time(&at(18, 12)), None);
+    }
+
+    #[test]
+    fn test_runs_at_midnight() {
+        // 19 June 2020 is a Friday
+        let at = |d, h, s| Utc.with_ymd_and_hms(2020, 6, d, h, 0, s).unwrap();
+        for (interval, after_friday) in [(1.day(), 20), (Interval::Weekday, 22)] {
+            let mut job = JobSchedule::<Utc>::new(interval, Utc);
+            job.at("00:00");
+            assert_eq!(job.next_run_time(&at(18, 12, 0)), Some(at(19, 0, 0)));
+            // Running on time, or a little late, doesn't skip or repeat a day
+            job.schedule_next(&at(19, 0, 0));
+            assert_eq!(job.next_run, Some(at(after_friday, 0, 0)), "{}", interval);
+            job.schedule_next(&at(19, 0, 2));
+            assert_eq!(job.next_run, Some(at(after_friday, 0, 0)), "{}", interval);
+        }
+    }
+
+    #[test]
+    fn test_initial_delay() {
+        let _guard = crate::timeprovider::lock_mock_clock();
+        let at = |m, s| Utc.with_ymd_and_hms(2020, 6, 19, 12, m, s).unwrap();
+        MockClock::set(at(0, 10));
+        let mut job = JobSchedule::<Utc, MockClock>::new(5.minutes(), Utc);
+        job.initial_delay(std::time::Duration::from_secs(90));
+        assert_eq!(
+            job.explain(),
+            "Every 5 minutes, first running 90 seconds after being added."
+        );
+        job.start_schedule();
+        // Not aligned to the interval
+        assert_eq!(job.next_run, Some(at(1, 40)));
+        assert!(!job.is_pending(&at(1, 39)));
+        assert!(job.is_pending(&at(1, 40)));
+        // Later runs are
+        job.schedule_next(&at(1, 40));
+        assert_eq!(job.next_run, Some(at(5, 0)));
+        job.schedule_next(&at(5, 0));
+        assert_eq!(job.next_run, Some(at(10, 0)));
+        assert_eq!(job.explain(), "Every 5 minutes.");
+
+        // The delay is counted from when the job starts, not when it's created
+        MockClock::set(at(20, 0));
+        job.reset();
+        assert_eq!(job.next_run, Some(at(21, 30)));
+    }
+
+    #[test]
+    fn test_random_window_first_run() {
+        let _guard = crate::timeprovider::lock_mock_clock();
+        let at = |d, h, m| Utc.with_ymd_and_hms(2020, 6, d, h, m, 0).unwrap();
+        let two_am = NaiveTime::from_hms_opt(2, 0, 0).unwrap();
+        let four_am = NaiveTime::from_hms_opt(4, 0, 0).unwrap();
+        // Scheduled during the window, the first run is today if the time picked is still to come
+        MockClock::set(at(19, 3, 0));
+        let (mut today, mut tomorrow) = (0, 0);
+        for seed in 0..20 {
+            let mut job = JobSchedule::<Utc, MockClock>::new(1.day(), Utc);
+            job.at_random_between(two_am, four_am)
+                .random_seed(seed)
+                .start_schedule();
+            let picked = at(19, 2, 0) + job.random_offset;
+            if picked > at(19, 3, 0) {
+                assert_eq!(job.next_run, Some(picked));
+                today += 1;
+            } else {
+                assert_eq!(job.next_run, Some(picked + chrono::Duration::days(1)));
+                tomorrow += 1;
+            }
+        }
+        assert!(today > 0 && tomorrow > 0);
+
+        // Frequencies added with `and_every` get the random delay too
+        MockClock::set(at(19, 12, 0));
+        let mut job = JobSchedule::<Utc, MockClock>::new(Interval::Saturday, Utc);
+        job.at_random_between(two_am, four_am)
+            .and_every(Interval::Monday)
+            .random_seed(1)
+            .start_schedule();
+        assert_eq!(job.next_run, Some(at(20, 2, 0) + job.random_offset));
+        job.schedule_next(&at(20, 4, 0));
+        assert!(job.random_offset > chrono::Duration::zero());
+        assert_eq!(job.next_run, Some(at(22, 0, 0) + job.random_offset));
+    }
+
+    #[test]
+    fn test_min_gap() {
+        let at = |h, m| Utc.with_ymd_and_hms(2020, 6, 19, h, m, 0).unwrap();
+        let mut job = JobSchedule::<Utc, MockClock>::new(1.minute(), Utc);
+        job.min_gap(std::time::Duration::from_secs(10 * 60));
+        job.schedule_next(&at(12, 0));
+        // The next run is pushed back to the end of the gap
+        assert_eq!(job.next_run, Some(at(12, 10)));
+
+        // Even if something else schedules it sooner, it doesn't run within the gap
+        job.next_run = Some(at(12, 1));
+        assert!(!job.is_pending(&at(12, 1)));
+        assert!(!job.is_pending(&at(12, 9)));
+        assert!(!job.is_pending(&at(11, 50)));
+        assert!(job.is_pending(&at(12, 10)));
+    }
+
+    #[test]
+    #[should_panic(expected = "The phase must be")]
+    fn test_every_with_phase_too_long() {
+        let mut job = JobSchedule::<Utc>::new(1.day(), Utc);
+        job.every_with_phase(15.minutes(), 15.minutes());
+        job.start_schedule();
+    }
+
+    #[test]
+    fn test_fire_times() {
+        let mut job = JobSchedule::<Utc>::new(1.hour(), Utc);
+        job.count(3);
+        let now = Utc.with_ymd_and_hms(2020, 6, 16, 12, 30, 0).unwrap();
+        let times: Vec<_> = job.fire_times(now).collect();
+        assert_eq!(
+            times,
+            vec![
+                Utc.with_ymd_and_hms(2020, 6, 16, 13, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2020, 6, 16, 14, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2020, 6, 16, 15, 0, 0).unwrap(),
+            ]
+        );
+        // The job itself is unchanged
+        assert_eq!(job.run_count, super::RunCount::Times(3));
+        assert_eq!(job.next_run, None);
+
+        let mut job = SyncJob::<Utc>::new(1.day(), Utc);
+        job.at("7:40").repeating_every(10.minutes()).times(2);
+        let times: Vec<_> = job.fire_times(now).take(4).collect();
+        assert_eq!(
+            times,
+            vec![
+                Utc.with_ymd_and_hms(2020, 6, 17, 7, 40, 0).unwrap(),
+                Utc.with_ymd_and_hms(2020, 6, 17, 7, 50, 0).unwrap(),
+                Utc.with_ymd_and_hms(2020, 6, 17, 8, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2020, 6, 18, 7, 40, 0).unwrap(),
+            ]
+        );
+
+        let mut job = JobSchedule::<Utc>::new(1.hour(), Utc);
+        job.run_count = super::RunCount::Never;
+        assert_eq!(job.fire_times(now).next(), None);
+
+        let mut job = JobSchedule::<Utc>::new(1.hour(), Utc);
+        job.relative_to_start();
+        let times: Vec<_> = job.fire_times(now).take(2).collect();
+        assert_eq!(
+            times,
+            vec![
+                Utc.with_ymd_and_hms(2020, 6, 16, 13, 30, 0).unwrap(),
+                Utc.with_ymd_and_hms(2020, 6, 16, 14, 30, 0).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_on_reschedule() {
+        use std::sync::{Arc, Mutex};
+        let reschedules = Arc::new(Mutex::new(vec![]));
+        let mut job = JobSchedule::<Utc>::new(1.day(), Utc);
+        {
+            let reschedules = reschedules.clone();
+            job.at("09:00").count(4).on_reschedule(move |next_run| {
+                reschedules.lock().unwrap().push(next_run.cloned());
+            });
+        }
+        job.repeat_config = Some(RepeatConfig {
+            repeats: 1,
+            repeat_interval: 30.minutes(),
+            repeats_left: 0,
+        });
+        let at = |d, h, m| Utc.with_ymd_and_hms(2020, 6, d, h, m, 0).unwrap();
+        job.next_run = Some(at(16, 9, 0));
+        for _ in 0..5 {
+            let now = job.next_run.unwrap();
+            job.schedule_next(&now);
+        }
+        // Called for both regular runs and repeats, but not once the job has finished
+        assert_eq!(
+            *reschedules.lock().unwrap(),
+            vec![
+                Some(at(17, 9, 0)),
+                Some(at(17, 9, 30)),
+                Some(at(18, 9, 0)),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn test_on_finished() {
+        use std::sync::{
+            atomic::{AtomicU32, Ordering},
+            Arc,
+        };
+        let finished = Arc::new(AtomicU32::new(0));
+        let mut job = JobSchedule::<Utc>::new(1.hour(), Utc);
+        {
+            let finished = finished.clone();
+            job.count(2).on_finished(move || {
+                finished.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        let now = Utc.with_ymd_and_hms(2020, 6, 16, 8, 0, 0).unwrap();
+        job.schedule_next(&now);
+        assert_eq!(0, finished.load(Ordering::SeqCst));
+        job.schedule_next(&now);
+        assert_eq!(1, finished.load(Ordering::SeqCst));
+        assert!(!job.can_run_again());
+        job.schedule_next(&now);
+        assert_eq!(1, finished.load(Ordering::SeqCst));
+
+        // Never fires for jobs that run forever
+        let finished = Arc::new(AtomicU32::new(0));
+        let mut job = JobSchedule::<Utc>::new(1.hour(), Utc);
+        {
+            let finished = finished.clone();
+            job.on_finished(move || {
+                finished.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        for _ in 0..5 {
+            job.schedule_next(&now);
+        }
+        assert_eq!(0, finished.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_runs_remaining() {
+        let mut job = JobSchedule::<Utc>::new(1.hour(), Utc);
+        assert_eq!(job.runs_remaining(), None);
+        job.count(2);
+        assert_eq!(job.runs_remaining(), Some(2));
+        let now = Utc.with_ymd_and_hms(2020, 6, 16, 8, 0, 0).unwrap();
+        job.schedule_next(&now);
+        assert_eq!(job.runs_remaining(), Some(1));
+        job.schedule_next(&now);
+        assert_eq!(job.runs_remaining(), Some(0));
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut job = JobSchedule::<Utc>::new(Interval::Monday, Utc);
+        job.at("09:00")
+            .and_every(Interval::Thursday)
+            .and_every(2.hours());
+        assert_eq!(job.validate(), vec![]);
+
+        let mut job = JobSchedule::<Utc>::new(Interval::Weekday, Utc);
+        job.and_every(59.minutes()).and_every(60.minutes());
+        assert_eq!(
+            job.validate(),
+            vec![ScheduleWarning::WeekdayWithSubHourInterval {
+                weekday: Interval::Weekday,
+                other: 59.minutes()
+            }]
+        );
+
+        let mut job = JobSchedule::<Utc>::new(30.seconds(), Utc);
+        job.and_every(Interval::Friday);
+        assert_eq!(
+            job.validate(),
+            vec![ScheduleWarning::WeekdayWithSubHourInterval {
+                weekday: Interval::Friday,
+                other: 30.seconds()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_run_immediately_after_start() {
+        let _guard = crate::timeprovider::lock_mock_clock();
+        MockClock::set(Utc.with_ymd_and_hms(2021, 3, 4, 12, 30, 0).unwrap());
+        let mut job = JobSchedule::<Utc, MockClock>::new(1.day(), Utc);
+        job.start_schedule();
+        assert!(!job.is_pending(&MockClock::now(&Utc)));
+        // Can also be set once the job has been started, as long as it hasn't run yet
+        job.run_immediately();
+        assert!(job.is_pending(&MockClock::now(&Utc)));
+    }
+
+    #[test]
+    fn test_at_error() {
+        let mut job = JobSchedule::<Utc>::new(1.day(), Utc);
+        let err = job.try_at("25:61").unwrap_err();
+        assert_eq!(err.input(), "25:61");
+        assert!(err.to_string().contains("\"25:61\""));
+    }
+
+    #[test]
+    #[should_panic(expected = "Could not convert \"half past noon\" into a time")]
+    fn test_at_panic_message() {
+        let mut job = JobSchedule::<Utc>::new(1.day(), Utc);
+        job.at("half past noon");
+        job.start_schedule();
+    }
+
+    #[test]
+    fn test_try_build() {
+        let mut job = JobSchedule::<Utc>::new(1.day(), Utc);
+        assert_eq!(job.try_build(), Ok(()));
+        job.at("25:61")
+            .and_every(Interval::Monday)
+            .and_every(5.minutes());
+        job.at("noonish");
+        let err = job.try_build().unwrap_err();
+        let inputs: Vec<_> = err
+            .errors()
+            .iter()
+            .filter_map(|e| match e {
+                BuildError::At(e) => Some(e.input()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(inputs, vec!["25:61", "noonish"]);
+        assert_eq!(err.warnings().len(), 1);
+        assert!(err.to_string().contains("\"noonish\""));
+
+        let mut job = JobSchedule::<Utc>::new(1.day(), Utc);
+        job.every_with_phase(Interval::Monday, 5.minutes())
+            .every_with_phase(15.minutes(), 1.hour());
+        assert_eq!(
+            job.try_build().unwrap_err().errors(),
+            &[
+                BuildError::Phase {
+                    interval: Interval::Monday,
+                    phase: 5.minutes()
+                },
+                BuildError::Phase {
+                    interval: 15.minutes(),
+                    phase: 1.hour()
+                }
+            ]
+        );
+        // The job keeps the schedule it had
+        assert_eq!(job.frequency[0].base(), 1.day());
+    }
+
+    #[cfg(feature = "serde")]
+    mod serialization {
+        use super::super::{JobSchedule, RepeatConfig, RunConfig, ScheduleConfig};
+        use crate::{Interval, NextTime, TimeUnits};
+        use chrono::prelude::*;
+
+        // These strings are what's persisted by users; if these tests fail, the format has changed.
+        const SERIALIZED_RUN_CONFIG: &str =
+            r#"{"base":{"Days":1},"time":"16:45:07","offsets":[{"Seconds":30}]}"#;
+        const SERIALIZED_SCHEDULE: &str = concat!(
+            r#"{"frequency":[{"base":{"Days":1},"time":"16:45:07","offsets":[{"Seconds":30}]},"#,
+            r#"{"base":{"DaysOfWeek":["Mon","Fri"]},"time":null,"offsets":[]}],"#,
+            r#""run_count":{"Times":3},"#,
+            r#""repeat_config":{"repeats":2,"repeat_interval":{"Minutes":10}}}"#
+        );
+
+        fn run_config() -> RunConfig {
+            RunConfig::from_interval(1.day())
+                .with_time(NaiveTime::from_hms_opt(16, 45, 7).unwrap())
+                .with_subinterval(30.seconds())
+        }
+
+        #[test]
+        fn test_serialized_run_config() {
+            let serialized = serde_json::to_string(&run_config()).unwrap();
+            assert_eq!(serialized, SERIALIZED_RUN_CONFIG);
+
+            let rc: RunConfig = serde_json::from_str(SERIALIZED_RUN_CONFIG).unwrap();
+            let from = Utc.with_ymd_and_hms(2021, 3, 4, 12, 0, 0).unwrap();
+            let expected = Utc.with_ymd_and_hms(2021, 3, 4, 16, 45, 37).unwrap();
+            assert_eq!(rc.next(&from), expected);
+            assert_eq!(serde_json::to_string(&rc).unwrap(), SERIALIZED_RUN_CONFIG);
+        }
+
+        #[test]
+        fn test_serialized_schedule() {
+            let mut schedule = JobSchedule::<Utc>::new(1.day(), Utc);
+            schedule
+                .at_time(NaiveTime::from_hms_opt(16, 45, 7).unwrap())
+                .plus(30.seconds())
+                .and_every(Interval::days_of_week(&[Weekday::Mon, Weekday::Fri]))
+                .count(3);
+            schedule.repeat_config = Some(RepeatConfig {
+                repeats: 2,
+                repeat_interval: 10.minutes(),
+                repeats_left: 1,
+            });
+            let serialized = serde_json::to_string(&schedule).unwrap();
+            assert_eq!(serialized, SERIALIZED_SCHEDULE);
+        }
+
+        #[test]
+        fn test_deserialized_schedule() {
+            let config: ScheduleConfig = serde_json::from_str(SERIALIZED_SCHEDULE).unwrap();
+            let mut schedule = JobSchedule::<Utc>::new(1.hour(), Utc);
+            schedule.set_schedule_config(&config);
+            assert_eq!(
+                serde_json::to_string(&schedule).unwrap(),
+                SERIALIZED_SCHEDULE
+            );
+
+            // Metadata isn't part of the schedule
+            let mut described = JobSchedule::<Utc>::new(1.hour(), Utc);
+            described.describe("Hourly").tag("team", "search");
+            let serialized = serde_json::to_string(&described).unwrap();
+            let config: ScheduleConfig = serde_json::from_str(&serialized).unwrap();
+            assert_eq!(config.interval(), 1.hour());
+
+            assert!(serde_json::from_str::<ScheduleConfig>(
+                r#"{"frequency":[],"run_count":"Forever","repeat_config":null}"#
+            )
+            .is_err());
+        }
+
+        #[test]
+        fn test_serialized_metadata() {
+            let mut schedule = JobSchedule::<Utc>::new(1.hour(), Utc);
+            schedule
+                .describe("Rebuilds the search index")
+                .tag("team", "search")
+                .tag("owner", "sam");
+            let serialized = serde_json::to_value(&schedule).unwrap();
+            assert_eq!(serialized["description"], "Rebuilds the search index");
+            assert_eq!(
+                serialized["tags"],
+                serde_json::json!({"owner": "sam", "team": "search"})
+            );
+        }
     }
 }
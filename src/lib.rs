@@ -9,28 +9,75 @@
 //! See [`Scheduler`].
 //! ### Asynchronous
 //! See [`AsyncScheduler`].
+//! ### Without `std`
+//! With the default `std` feature disabled, only the schedule calculations are available: [`Interval`],
+//! [`RunConfig`] and the [`NextTime`] trait. This only requires `alloc`, and leaves it up to you to check
+//! the time and run jobs when they're due.
+//! ### Tracing
+//! With the `tracing` feature enabled, each job run is wrapped in a `job` span from the
+//! [tracing](https://docs.rs/tracing) crate, with the job's label. Panicking jobs are logged as warnings,
+//! as are late runs: those skipped because of [`Job::skip_if_late_by`], and those that go ahead after
+//! the job's following run was already due.
+//! ### Serde
+//! With the `serde` feature enabled, intervals and schedules can be serialized. By default, an [`Interval`] is
+//! represented as an enum, e.g. `{"Seconds":5}`; see [`serde_string`] for a string form like `"5s"` that suits
+//! hand-written TOML or YAML.
+//! ### Calendars
+//! With the `ical` feature enabled, [`Scheduler::to_ics`] lists upcoming runs as an iCalendar file,
+//! which can be shared with people who don't read code.
 //! ## Caveats
 //! Some combinations of times or intervals are permissible, but make little sense, e.g. `every(10.seconds()).at("16:00")`, which would next run at the next 4 PM after the next multiple of 10 seconds.
 //!
 //! ## Similar libraries
 //! * [schedule-rs](https://github.com/mehcode/schedule-rs) and [job_scheduler](https://github.com/lholden/job_scheduler) are two other Rust scheduler libraries. Both use `cron` syntax for scheduling.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+extern crate alloc;
+
 #[cfg(feature = "async")]
 mod async_job;
 #[cfg(feature = "async")]
 mod async_scheduler;
+mod error;
+#[cfg(feature = "ical")]
+mod ical;
 mod intervals;
+#[cfg(feature = "std")]
 mod job;
+#[cfg(feature = "std")]
 mod job_schedule;
+#[cfg(feature = "std")]
+mod rng;
+#[cfg(feature = "std")]
 mod scheduler;
+#[cfg(feature = "serde")]
+pub mod serde_string;
+#[cfg(feature = "std")]
 mod sync_job;
+#[cfg(feature = "std")]
+mod thread_pool;
+#[cfg(feature = "std")]
 pub mod timeprovider;
+#[cfg(feature = "tracing")]
+mod tracing_support;
 
-pub use crate::intervals::{Interval, NextTime, TimeUnits};
+#[cfg(feature = "std")]
+pub use crate::error::JobError;
+pub use crate::error::{
+    AtError, BuildError, IntervalError, ScheduleError, ScheduleWarning, SpecError,
+};
+pub use crate::intervals::{DstPolicy, Interval, NextTime, Rate, RunConfig, TimeUnits, WeekdaySet};
+#[cfg(feature = "std")]
 pub use crate::job::Job;
+#[cfg(feature = "std")]
+pub use crate::job_schedule::{CatchUp, FireTimes, JobContext, JobId, ScheduleConfig};
+#[cfg(feature = "testing")]
+pub use crate::scheduler::{JobSnapshot, SchedulerSnapshot};
+#[cfg(feature = "std")]
 pub use crate::scheduler::{ScheduleHandle, Scheduler};
+#[cfg(feature = "std")]
 pub use crate::sync_job::SyncJob;
 
 #[cfg(feature = "async")]
-pub use crate::async_job::AsyncJob;
+pub use crate::async_job::{AsyncJob, JobFuture};
 #[cfg(feature = "async")]
 pub use crate::async_scheduler::AsyncScheduler;
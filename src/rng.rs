@@ -0,0 +1,69 @@
+//! A small, seedable random number generator, for jobs that run at random times.
+//!
+//! This uses SplitMix64, which is fast and good enough for spreading out load,
+//! but isn't suitable for anything security-related.
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+#[derive(Debug, Clone)]
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    /// A generator with an unpredictable seed
+    pub(crate) fn new() -> Self {
+        Rng(RandomState::new().build_hasher().finish())
+    }
+
+    pub(crate) fn with_seed(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// `true` with probability `p`, which should be between 0 and 1
+    pub(crate) fn chance(&mut self, p: f64) -> bool {
+        // The top 53 bits give a float evenly spread over [0, 1)
+        let sample = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        sample < p
+    }
+
+    /// A random number from `0` to `n`, inclusive
+    pub(crate) fn up_to(&mut self, n: u64) -> u64 {
+        match n.checked_add(1) {
+            Some(bound) => self.next_u64() % bound,
+            None => self.next_u64(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rng;
+
+    #[test]
+    fn test_seeded() {
+        let mut a = Rng::with_seed(42);
+        let mut b = Rng::with_seed(42);
+        for _ in 0..100 {
+            let n = a.up_to(10);
+            assert!(n <= 10);
+            assert_eq!(n, b.up_to(10));
+        }
+        assert_eq!(0, a.up_to(0));
+    }
+
+    #[test]
+    fn test_chance() {
+        let mut rng = Rng::with_seed(7);
+        assert!((0..100).all(|_| rng.chance(1.0)));
+        assert!((0..100).all(|_| !rng.chance(0.0)));
+        let hits = (0..10_000).filter(|_| rng.chance(0.3)).count();
+        assert!((2_800..3_200).contains(&hits), "{} hits", hits);
+    }
+}
@@ -1,16 +1,31 @@
-use crate::Interval;
 use crate::SyncJob;
 use crate::{
-    timeprovider::{ChronoTimeProvider, TimeProvider},
-    Job,
+    job_schedule::{
+        by_priority, for_each_in_group, remove_group, JobId, JobSchedule, ScheduleConfig,
+        WithSchedule,
+    },
+    thread_pool::ThreadPool,
+    timeprovider::{
+        monotonic_now, ChronoTimeProvider, Clock, FrozenClock, SharedClock, TimeProvider,
+    },
+    Job, SpecError,
 };
+use crate::{DstPolicy, Interval};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, NaiveTime};
+use std::any::Any;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::default::Default;
+use std::fmt;
 use std::marker::PhantomData;
-use std::sync::atomic::AtomicBool;
+use std::panic::{self, AssertUnwindSafe};
 use std::sync::atomic::Ordering;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64};
+use std::sync::{mpsc, Arc};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 /// Synchronous job scheduler
 ///
 /// ### Usage examples
@@ -64,7 +79,7 @@ use std::time::Duration;
 ///     .run(|| println!("I run every half hour from 10 AM to 1 PM inclusive."));
 /// scheduler
 ///     .every(1.day())
-///         .at_time(chrono::NaiveTime::from_hms(13, 12, 14))
+///         .at_time(chrono::NaiveTime::from_hms_opt(13, 12, 14).unwrap())
 ///     .run(|| println!("You can also pass chrono::NaiveTimes to `at_time`."));
 ///
 /// // Manually run the scheduler in an event loop
@@ -87,16 +102,86 @@ where
 {
     jobs: Vec<SyncJob<Tz, Tp>>,
     tz: Tz,
+    monotonic_intervals: bool,
+    normalize_intervals: bool,
+    dst_policy: DstPolicy,
+    min_interval: Duration,
+    time_format: String,
+    paused: Arc<AtomicBool>,
+    was_paused: bool,
+    tick_warning: Option<TickWarning>,
+    idle_job: Option<IdleJob>,
+    on_jobs_changed: Option<JobsChanged>,
+    last_tick: Option<DateTime<Tz>>,
+    start_instant: Instant,
+    clock: Option<SharedClock>,
+    // Set for schedulers created with `frozen_at`, so that `advance` can move the clock
+    frozen_clock: Option<FrozenClock>,
+    // Set by `with_thread_pool`, for `run_pending_parallel` to use instead of a thread per job
+    thread_pool: Option<PooledRunner<Tz, Tp>>,
     _tp: PhantomData<Tp>,
 }
 
+/// Runs the pending jobs on a thread pool, returning whether any ran
+type PoolRun<Tz, Tp> = fn(&ThreadPool, &mut Vec<SyncJob<Tz, Tp>>, &DateTime<Tz>) -> bool;
+
+/// The worker threads set with [`Scheduler::with_thread_pool`], along with the function that runs jobs on them.
+/// The function is picked in `with_thread_pool`, where the `'static` bounds the workers need are checked,
+/// so that `run_pending_parallel` doesn't need them too.
+struct PooledRunner<Tz, Tp>
+where
+    Tz: chrono::TimeZone,
+    Tp: TimeProvider,
+{
+    pool: ThreadPool,
+    run: PoolRun<Tz, Tp>,
+}
+
+impl<Tz, Tp> fmt::Debug for PooledRunner<Tz, Tp>
+where
+    Tz: chrono::TimeZone,
+    Tp: TimeProvider,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.pool.fmt(f)
+    }
+}
+
+/// Watchdog for gaps between calls to [`Scheduler::run_pending`]; see [`Scheduler::set_tick_warning`].
+struct TickWarning {
+    threshold: Duration,
+    hook: Box<dyn FnMut(Duration) + Send>,
+}
+
+impl fmt::Debug for TickWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TickWarning")
+            .field("threshold", &self.threshold)
+            .finish()
+    }
+}
+
+/// Runs when nothing else did; see [`Scheduler::set_idle_job`].
+struct IdleJob(Box<dyn FnMut() + Send>);
+
+impl fmt::Debug for IdleJob {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("IdleJob")
+    }
+}
+
+/// Called when jobs are added or removed; see [`Scheduler::on_jobs_changed`].
+struct JobsChanged(Box<dyn FnMut(usize) + Send>);
+
+impl fmt::Debug for JobsChanged {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("JobsChanged")
+    }
+}
+
 impl Default for Scheduler {
     fn default() -> Self {
-        Scheduler::<chrono::Local> {
-            jobs: vec![],
-            tz: chrono::Local,
-            _tp: PhantomData,
-        }
+        Scheduler::with_tz(chrono::Local)
     }
 }
 
@@ -106,13 +191,52 @@ impl Scheduler {
         Scheduler::default()
     }
 
+    /// Create a new scheduler with a job for each spec, from a table of specs and a map of closures.
+    ///
+    /// Each spec is a label, an interval, and optionally a time of day, as for [`Scheduler::every`] and [`Job::at_time`].
+    /// It's run by the closure with exactly the same label, and the job is given that label (see [`Job::label`]).
+    /// Jobs are added in the same order as the specs, and run forever.
+    ///
+    /// Returns an error if a spec has no matching closure, or if two specs have the same label.
+    /// Closures that don't match any spec are dropped.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use chrono::NaiveTime;
+    /// use std::collections::HashMap;
+    /// let specs = vec![
+    ///     ("backup".to_string(), 1.day(), Some(NaiveTime::from_hms_opt(2, 0, 0).unwrap())),
+    ///     ("heartbeat".to_string(), 30.seconds(), None),
+    /// ];
+    /// let mut closures: HashMap<String, Box<dyn FnMut() + Send>> = HashMap::new();
+    /// closures.insert("backup".to_string(), Box::new(|| println!("Backing up")));
+    /// closures.insert("heartbeat".to_string(), Box::new(|| println!("Still here")));
+    /// let scheduler = Scheduler::from_specs(specs, closures).unwrap();
+    /// ```
+    pub fn from_specs<F>(
+        specs: Vec<(String, Interval, Option<NaiveTime>)>,
+        mut closures: HashMap<String, F>,
+    ) -> Result<Self, SpecError>
+    where
+        F: 'static + FnMut() + Send,
+    {
+        let mut scheduler = Scheduler::new();
+        for (label, ival, time) in specs {
+            let job = scheduler.every_unique(ival, &label)?;
+            let f = match closures.remove(&label) {
+                Some(f) => f,
+                None => return Err(SpecError::MissingClosure { label }),
+            };
+            if let Some(time) = time {
+                job.at_time(time);
+            }
+            job.run(f);
+        }
+        Ok(scheduler)
+    }
+
     /// Create a new scheduler. Dates and times will be interpretted using the specified timezone.
     pub fn with_tz<Tz: chrono::TimeZone>(tz: Tz) -> Scheduler<Tz> {
-        Scheduler {
-            jobs: vec![],
-            tz,
-            _tp: PhantomData,
-        }
+        Scheduler::with_tz_and_provider(tz)
     }
 
     /// Create a new scheduler. Dates and times will be interpretted using the specified timezone.
@@ -124,9 +248,91 @@ impl Scheduler {
         Scheduler {
             jobs: vec![],
             tz,
+            monotonic_intervals: false,
+            normalize_intervals: false,
+            dst_policy: DstPolicy::default(),
+            min_interval: Duration::ZERO,
+            time_format: DEFAULT_TIME_FORMAT.to_owned(),
+            paused: Arc::new(AtomicBool::new(false)),
+            was_paused: false,
+            tick_warning: None,
+            idle_job: None,
+            on_jobs_changed: None,
+            last_tick: None,
+            start_instant: monotonic_now(),
+            clock: None,
+            frozen_clock: None,
+            thread_pool: None,
             _tp: PhantomData,
         }
     }
+
+    /// Create a new scheduler. Dates and times will be interpretted using the specified timezone,
+    /// and the current time comes from `provider`.
+    ///
+    /// Unlike [`Scheduler::with_tz_and_provider`], the provider is a value rather than a type, so it
+    /// can carry its own state. For instance, each test can have its own clock, instead of sharing
+    /// a global one. The provider is shared with every job added to the scheduler.
+    ///
+    /// To migrate from the type-parameter form, implement [`Clock`] for your provider,
+    /// and replace `Scheduler::with_tz_and_provider::<Tz, MyProvider>(tz)` with
+    /// `Scheduler::with_tz_and_provider_instance(tz, my_provider)`.
+    /// [`ChronoTimeProvider`] and [`MockClock`](crate::timeprovider::MockClock) implement both traits.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use clokwerk::timeprovider::Clock;
+    /// use chrono::{DateTime, TimeZone, Utc};
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// #[derive(Clone)]
+    /// struct ManualClock(Arc<Mutex<DateTime<Utc>>>);
+    ///
+    /// impl Clock for ManualClock {
+    ///     fn now(&self) -> DateTime<Utc> {
+    ///         *self.0.lock().unwrap()
+    ///     }
+    /// }
+    ///
+    /// let clock = ManualClock(Arc::new(Mutex::new(Utc.with_ymd_and_hms(2021, 3, 14, 15, 9, 0).unwrap())));
+    /// let mut scheduler = Scheduler::with_tz_and_provider_instance(Utc, clock.clone());
+    /// scheduler.every(1.minute()).run(|| println!("Tick"));
+    /// scheduler.run_pending(); // Nothing happens
+    /// *clock.0.lock().unwrap() += chrono::Duration::minutes(1);
+    /// scheduler.run_pending(); // Prints "Tick"
+    /// ```
+    pub fn with_tz_and_provider_instance<Tz: chrono::TimeZone>(
+        tz: Tz,
+        provider: impl Clock + 'static,
+    ) -> Scheduler<Tz> {
+        let clock = SharedClock::new(provider);
+        Scheduler {
+            start_instant: clock.monotonic_now(),
+            clock: Some(clock),
+            ..Scheduler::with_tz(tz)
+        }
+    }
+
+    /// Create a new scheduler whose current time is `now`, until moved forward with [`Scheduler::advance`].
+    /// Dates and times will be interpretted using the specified timezone.
+    /// This makes tests reproducible, without sleeping or sharing a clock between tests.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// use chrono::{TimeZone, Utc};
+    /// use std::time::Duration;
+    ///
+    /// let mut scheduler = Scheduler::frozen_at(Utc, Utc.with_ymd_and_hms(2021, 3, 14, 15, 9, 0).unwrap());
+    /// scheduler.every(1.minute()).run(|| println!("Tick"));
+    /// scheduler.run_pending(); // Nothing happens
+    /// scheduler.advance(Duration::from_secs(60));
+    /// scheduler.run_pending(); // Prints "Tick"
+    /// ```
+    pub fn frozen_at<Tz: chrono::TimeZone>(tz: Tz, now: DateTime<Tz>) -> Scheduler<Tz> {
+        let clock = FrozenClock::new(now);
+        Scheduler {
+            frozen_clock: Some(clock.clone()),
+            ..Scheduler::with_tz_and_provider_instance(tz, clock)
+        }
+    }
 }
 
 impl<Tz, Tp> Scheduler<Tz, Tp>
@@ -134,6 +340,73 @@ where
     Tz: chrono::TimeZone + Sync + Send,
     Tp: TimeProvider,
 {
+    /// The current time, from the clock instance if there is one, or else from `Tp`
+    fn now(&self) -> DateTime<Tz> {
+        match &self.clock {
+            Some(clock) => clock.now(&self.tz),
+            None => Tp::now(&self.tz),
+        }
+    }
+
+    /// Run jobs started by [`Scheduler::run_pending_parallel`] on `size` worker threads, which are reused
+    /// on each tick, rather than starting a new thread for each job.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::with_tz(chrono::Utc);
+    /// scheduler.with_thread_pool(4);
+    /// scheduler.every(1.minute()).run(|| println!("Fetching"));
+    /// scheduler.every(1.minute()).run(|| println!("Indexing"));
+    /// scheduler.run_pending_parallel();
+    /// ```
+    /// If more jobs are due than there are workers, the rest wait in a queue until a worker is free,
+    /// so a few slow jobs can delay the others. `run_pending_parallel` still returns only once every job has finished.
+    /// The workers are stopped when the scheduler is dropped, after finishing the job they're running.
+    ///
+    /// # Panics
+    /// Panics if `size` is zero.
+    pub fn with_thread_pool(&mut self, size: usize) -> &mut Self
+    where
+        Tz: 'static,
+        Tz::Offset: Send + Sync,
+        Tp: Send + 'static,
+    {
+        self.thread_pool = Some(PooledRunner {
+            pool: ThreadPool::new(size),
+            run: Self::run_on_thread_pool,
+        });
+        self
+    }
+
+    /// Choose how jobs that run at a time of day handle daylight saving time changes that repeat or skip
+    /// that time. The default is [`DstPolicy::Earliest`].
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new().with_dst_policy(DstPolicy::SkipForward);
+    /// // Runs at 3:00 am if clocks go forward from 2 am to 3 am
+    /// scheduler.every(1.day()).at("2:30").run(|| println!("Nightly backup"));
+    /// ```
+    /// This applies to both existing and future jobs.
+    pub fn with_dst_policy(mut self, policy: DstPolicy) -> Self {
+        self.dst_policy = policy;
+        for job in &mut self.jobs {
+            job.schedule_mut().set_dst_policy(policy);
+        }
+        self
+    }
+
+    /// Move the current time forward by `duration`, for a scheduler created with [`Scheduler::frozen_at`].
+    /// Jobs don't run until the scheduler is next polled, e.g. with [`Scheduler::run_pending`].
+    /// This also moves the monotonic clock used by [`Scheduler::use_monotonic_for_intervals`].
+    ///
+    /// # Panics
+    /// Panics if the scheduler wasn't created with [`Scheduler::frozen_at`].
+    pub fn advance(&mut self, duration: Duration) {
+        self.frozen_clock
+            .as_ref()
+            .expect("Only schedulers created with Scheduler::frozen_at can be advanced")
+            .advance(duration);
+    }
+
     /// Add a new job to the scheduler to be run on the given interval
     /// ```rust
     /// # use clokwerk::*;
@@ -145,12 +418,426 @@ where
     /// scheduler.every(Weekday).run(|| println!("Every weekday at midnight"));
     /// ```
     pub fn every(&mut self, ival: Interval) -> &mut SyncJob<Tz, Tp> {
-        let job = SyncJob::<Tz, Tp>::new(ival, self.tz.clone());
+        let ival = if self.normalize_intervals {
+            ival.normalized()
+        } else {
+            ival
+        };
+        let mut job = SyncJob::<Tz, Tp>::new(ival, self.tz.clone());
+        job.schedule_mut().use_monotonic(self.monotonic_intervals);
+        job.schedule_mut().set_start_instant(self.start_instant);
+        job.schedule_mut().set_clock(self.clock.clone());
+        job.schedule_mut().set_minimum_interval(self.min_interval);
+        job.schedule_mut().set_dst_policy(self.dst_policy);
         self.jobs.push(job);
+        self.jobs_changed();
         let last_index = self.jobs.len() - 1;
         &mut self.jobs[last_index]
     }
 
+    /// Add a job that runs `f` once, the next time the scheduler is polled, e.g. for a startup task.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.run_once_now(|| println!("Starting up"));
+    /// scheduler.every(1.hour()).run(|| println!("Hourly task"));
+    /// scheduler.run_pending(); // Prints "Starting up"
+    /// ```
+    /// This is the same as `scheduler.every(1.day()).run_immediately().once().run(f)`.
+    pub fn run_once_now<F>(&mut self, f: F) -> &mut SyncJob<Tz, Tp>
+    where
+        F: 'static + FnMut() + Send,
+    {
+        self.every(Interval::Days(1))
+            .run_immediately()
+            .once()
+            .run(f)
+    }
+
+    /// Add a new job that runs every other day, counting from today. See [`Job::every_other_day`].
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every_other_day().at("18:00").run(|| println!("Water the plants"));
+    /// ```
+    pub fn every_other_day(&mut self) -> &mut SyncJob<Tz, Tp> {
+        self.every(Interval::Days(2)).every_other_day()
+    }
+
+    /// Add a new job that runs on `day` every other week, counting from the next `day`.
+    /// See [`Job::every_other_week_on`].
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use chrono::Weekday;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every_other_week_on(Weekday::Tue).at("10:00").run(|| println!("Sprint planning"));
+    /// ```
+    pub fn every_other_week_on(&mut self, day: chrono::Weekday) -> &mut SyncJob<Tz, Tp> {
+        self.every(Interval::Weeks(2)).every_other_week_on(day)
+    }
+
+    /// Add a new job that runs every `n` seconds, the same as `every(n.seconds())` without needing [`TimeUnits`](crate::TimeUnits).
+    pub fn every_seconds(&mut self, n: u32) -> &mut SyncJob<Tz, Tp> {
+        self.every(Interval::Seconds(n))
+    }
+
+    /// Add a new job that runs every `n` minutes, the same as `every(n.minutes())`.
+    /// ```rust
+    /// # use clokwerk::{Job, Scheduler};
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every_minutes(10).run(|| println!("Checking the queue"));
+    /// ```
+    pub fn every_minutes(&mut self, n: u32) -> &mut SyncJob<Tz, Tp> {
+        self.every(Interval::Minutes(n))
+    }
+
+    /// Add a new job that runs every `n` hours, the same as `every(n.hours())`.
+    pub fn every_hours(&mut self, n: u32) -> &mut SyncJob<Tz, Tp> {
+        self.every(Interval::Hours(n))
+    }
+
+    /// Add a new job that runs every `n` days, the same as `every(n.days())`.
+    pub fn every_days(&mut self, n: u32) -> &mut SyncJob<Tz, Tp> {
+        self.every(Interval::Days(n))
+    }
+
+    /// Add a new job that runs every `n` weeks, the same as `every(n.weeks())`.
+    pub fn every_weeks(&mut self, n: u32) -> &mut SyncJob<Tz, Tp> {
+        self.every(Interval::Weeks(n))
+    }
+
+    /// Add a new job that runs every day at `time`, the same as `every(1.day()).at(time)`.
+    /// As with [`Job::at`], an unparseable time panics when the task is given, e.g. with [`SyncJob::run`].
+    /// ```rust
+    /// # use clokwerk::{Job, Scheduler};
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every_day_at("3:30 am").run(|| println!("Rotating logs"));
+    /// ```
+    pub fn every_day_at(&mut self, time: &str) -> &mut SyncJob<Tz, Tp> {
+        self.every(Interval::Days(1)).at(time)
+    }
+
+    /// Add a new job that runs on `ival` at `time`, the same as `every(ival).at_time(time)`.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use clokwerk::Interval::*;
+    /// # use chrono::NaiveTime;
+    /// let mut scheduler = Scheduler::new();
+    /// let nine_am = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+    /// scheduler.every_at(1.day(), nine_am).run(|| println!("Good morning"));
+    /// scheduler.every_at(Monday, nine_am).run(|| println!("Weekly planning"));
+    /// ```
+    pub fn every_at(&mut self, ival: Interval, time: NaiveTime) -> &mut SyncJob<Tz, Tp> {
+        self.every(ival).at_time(time)
+    }
+
+    /// Add a new job that runs `f` on the same schedule as an existing job. See [`Job::schedule_config`].
+    pub fn every_like<F>(&mut self, config: &ScheduleConfig, f: F) -> &mut SyncJob<Tz, Tp>
+    where
+        F: 'static + FnMut() + Send,
+    {
+        let job = self.every(config.interval());
+        job.schedule_mut().set_schedule_config(config);
+        job.run(f)
+    }
+
+    /// Add a new job with the label `label`, like [`Scheduler::every`] followed by [`Job::label`],
+    /// unless the scheduler already has a job with that label.
+    /// This can catch mistakes like registering the same job twice.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every_unique(1.day(), "backup")?.at("2:00").run(|| println!("Backing up"));
+    /// assert!(scheduler.every_unique(1.hour(), "backup").is_err());
+    /// # Ok::<(), SpecError>(())
+    /// ```
+    pub fn every_unique(
+        &mut self,
+        ival: Interval,
+        label: &str,
+    ) -> Result<&mut SyncJob<Tz, Tp>, SpecError> {
+        if self.jobs.iter().any(|job| job.get_label() == Some(label)) {
+            return Err(SpecError::DuplicateLabel {
+                label: label.to_owned(),
+            });
+        }
+        Ok(self.every(ival).label(label))
+    }
+
+    /// The job with the label `label` (see [`Job::label`]), if there is one.
+    /// If more than one job has that label, this is the one that was added first;
+    /// use [`Scheduler::every_unique`] to keep labels unique.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.day()).at("2:00").label("backup").run(|| println!("Backing up"));
+    /// let backup = scheduler.get("backup").unwrap();
+    /// assert_eq!(backup.runs_remaining(), None);
+    /// assert!(scheduler.get("restore").is_none());
+    /// ```
+    pub fn get(&self, label: &str) -> Option<&SyncJob<Tz, Tp>> {
+        self.jobs.iter().find(|job| job.get_label() == Some(label))
+    }
+
+    /// Like [`Scheduler::get`], but allows the job to be changed, e.g. to pause it or change how many more times it runs.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.hour()).label("report").run(|| println!("Reporting"));
+    /// if let Some(report) = scheduler.get_mut("report") {
+    ///     report.count(3);
+    /// }
+    /// ```
+    pub fn get_mut(&mut self, label: &str) -> Option<&mut SyncJob<Tz, Tp>> {
+        self.jobs
+            .iter_mut()
+            .find(|job| job.get_label() == Some(label))
+    }
+
+    /// Pause every job in the group `name` (see [`Job::group`]), returning how many jobs were paused.
+    /// Paused jobs don't run until resumed with [`Scheduler::resume_group`].
+    pub fn pause_group(&mut self, name: &str) -> usize {
+        for_each_in_group(&mut self.jobs, name, JobSchedule::pause)
+    }
+
+    /// Resume every job in the group `name`, returning how many jobs are in the group.
+    /// Runs that were missed while the jobs were paused are skipped, rather than run late.
+    pub fn resume_group(&mut self, name: &str) -> usize {
+        for_each_in_group(&mut self.jobs, name, JobSchedule::resume)
+    }
+
+    /// Remove every job in the group `name` from the scheduler, returning how many jobs were removed.
+    pub fn remove_group(&mut self, name: &str) -> usize {
+        let removed = remove_group(&mut self.jobs, name);
+        if removed > 0 {
+            self.jobs_changed();
+        }
+        removed
+    }
+
+    /// Pause the whole scheduler, e.g. during maintenance. No jobs run until [`Scheduler::resume`] is called.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.minute()).run(|| println!("Periodic task"));
+    /// scheduler.pause();
+    /// scheduler.run_pending(); // Does nothing
+    /// scheduler.resume();
+    /// ```
+    /// A scheduler running on a background thread can be paused with [`ScheduleHandle::pause`].
+    pub fn pause(&mut self) {
+        self.paused.store(true, Ordering::SeqCst);
+        // So that resuming skips missed runs, even if the scheduler isn't polled in between
+        self.was_paused = true;
+    }
+
+    /// Resume a paused scheduler. Runs that were missed while it was paused are skipped, rather
+    /// than all being run at once.
+    pub fn resume(&mut self) {
+        self.paused.store(false, Ordering::SeqCst);
+        self.catch_up_after_pause(&self.now());
+    }
+
+    /// Is the scheduler paused?
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    /// Checks whether the scheduler is paused, skipping any missed runs if it has just been resumed.
+    fn catch_up_after_pause(&mut self, now: &DateTime<Tz>) -> bool {
+        if self.is_paused() {
+            self.was_paused = true;
+            return true;
+        }
+        if self.was_paused {
+            self.was_paused = false;
+            for job in &mut self.jobs {
+                job.schedule_mut().skip_missed_runs(now);
+            }
+        }
+        false
+    }
+
+    /// Call `hook` whenever the gap between two calls to [`Scheduler::run_pending`] is longer than
+    /// `threshold`, passing it the length of the gap. Jobs are run late if the scheduler isn't polled
+    /// often enough, so this can help detect a stalled or blocked event loop.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use std::time::Duration;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.set_tick_warning(Duration::from_secs(5), |gap| {
+    ///     eprintln!("Scheduler wasn't polled for {:?}", gap)
+    /// });
+    /// ```
+    /// The gap is measured using the scheduler's time provider, or the times passed to
+    /// [`Scheduler::run_pending_at`].
+    pub fn set_tick_warning<F>(&mut self, threshold: Duration, hook: F)
+    where
+        F: 'static + FnMut(Duration) + Send,
+    {
+        self.tick_warning = Some(TickWarning {
+            threshold,
+            hook: Box::new(hook),
+        });
+    }
+
+    /// Record that the scheduler was polled at `now`, warning if it's been too long since the last time.
+    fn record_tick(&mut self, now: &DateTime<Tz>) {
+        let last_tick = self.last_tick.replace(now.clone());
+        if let (Some(last_tick), Some(warning)) = (last_tick, self.tick_warning.as_mut()) {
+            if let Ok(gap) = (now.clone() - last_tick).to_std() {
+                if gap > warning.threshold {
+                    (warning.hook)(gap);
+                }
+            }
+        }
+    }
+
+    /// Run `f` whenever the scheduler is polled (e.g. with [`Scheduler::run_pending`]) and no job runs,
+    /// such as to send a keepalive during quiet periods. Unlike a scheduled job, it has no interval
+    /// of its own, and doesn't count as a job. It doesn't run while the scheduler is paused.
+    /// Replaces any previous idle job.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(10.minutes()).run(|| println!("Syncing"));
+    /// scheduler.set_idle_job(|| println!("Keepalive"));
+    /// scheduler.run_pending(); // Prints "Keepalive"
+    /// ```
+    pub fn set_idle_job<F>(&mut self, f: F)
+    where
+        F: 'static + FnMut() + Send,
+    {
+        self.idle_job = Some(IdleJob(Box::new(f)));
+    }
+
+    /// Call `f` with the number of jobs whenever a job is added to or removed from the scheduler,
+    /// e.g. to refresh a list of jobs. Replaces any previous callback.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.on_jobs_changed(|count| println!("Now running {} jobs", count));
+    /// let id = scheduler.every(1.hour()).run(|| println!("Hourly task")).id(); // Prints "Now running 1 jobs"
+    /// scheduler.remove(id); // Prints "Now running 0 jobs"
+    /// ```
+    /// Adding a job calls `f` as soon as the job is created with [`Scheduler::every`] or similar,
+    /// before its schedule is set up. Jobs that have finished running stay on the scheduler, so don't call `f`.
+    pub fn on_jobs_changed<F>(&mut self, f: F)
+    where
+        F: 'static + FnMut(usize) + Send,
+    {
+        self.on_jobs_changed = Some(JobsChanged(Box::new(f)));
+    }
+
+    fn jobs_changed(&mut self) {
+        if let Some(JobsChanged(f)) = self.on_jobs_changed.as_mut() {
+            f(self.jobs.len());
+        }
+    }
+
+    /// Run the idle job, if there is one, unless another job `ran` this tick.
+    fn run_idle_job(&mut self, ran: bool) {
+        if let (false, Some(IdleJob(f))) = (ran, self.idle_job.as_mut()) {
+            f();
+        }
+    }
+
+    /// Keep each job's runs at least `min_interval` apart, delaying runs that would come sooner.
+    /// This guards against misconfigured schedules running much more often than intended.
+    /// This setting applies to both existing and future jobs.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use std::time::Duration;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.set_minimum_interval(Duration::from_secs(60));
+    /// // Runs once a minute, rather than every second
+    /// scheduler.every(1.second()).run(|| println!("Hello"));
+    /// ```
+    ///
+    /// Even without a minimum, a job's next run is always after the current time, so a job with a
+    /// zero-length interval like `every(0.seconds())` runs at most once a second.
+    pub fn set_minimum_interval(&mut self, min_interval: Duration) {
+        self.min_interval = min_interval;
+        for job in &mut self.jobs {
+            job.schedule_mut().set_minimum_interval(min_interval);
+        }
+    }
+
+    /// Set the format used when displaying times, e.g. in the scheduler's [`Display`](std::fmt::Display) output.
+    /// This uses [chrono's format syntax](chrono::format::strftime); the default is `%Y-%m-%d %H:%M:%S`.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.with_time_format("%a %-I:%M %p");
+    /// scheduler.every(1.day()).at("14:30").label("tea").run(|| println!("Tea time"));
+    /// println!("{}", scheduler); // e.g. "tea: next run at Thu 2:30 PM"
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if `format` isn't a valid format string.
+    pub fn with_time_format(&mut self, format: &str) -> &mut Self {
+        if StrftimeItems::new(format).any(|item| item == Item::Error) {
+            panic!("Invalid time format {:?}", format);
+        }
+        self.time_format = format.to_owned();
+        self
+    }
+
+    /// Set when the scheduler started, for jobs that use [`Job::after_uptime`]. This defaults to when the
+    /// scheduler was created, but could be e.g. when the process started.
+    /// This applies to jobs added after it's called.
+    pub fn with_start_instant(&mut self, start: Instant) -> &mut Self {
+        self.start_instant = start;
+        self
+    }
+
+    /// Time jobs that only use fixed intervals (seconds, minutes or hours) using a monotonic clock,
+    /// rather than the wall clock.
+    ///
+    /// Normally, if the system clock jumps (e.g. because it was corrected, or because of daylight saving time),
+    /// a job's next run moves with it: a job that runs every 30 seconds might not run for an hour,
+    /// or run twice in quick succession.
+    /// With this enabled, once a job's next run has been computed, the job waits for that much real time to elapse,
+    /// regardless of what the wall clock says.
+    /// The next run is still aligned to the wall clock when it's computed, after each run.
+    ///
+    /// Jobs that run at a particular time of day (e.g. using [`Job::at`]), or on particular days of the week
+    /// always use the wall clock. This setting applies to both existing and future jobs.
+    ///
+    /// The monotonic clock is only used when the scheduler reads the time itself, e.g. in
+    /// [`Scheduler::run_pending`]. [`Scheduler::run_pending_at`] and [`Scheduler::simulate`] go by the time
+    /// they're given. For a scheduler created with [`Scheduler::frozen_at`], [`Scheduler::advance`] moves
+    /// the monotonic clock along with the wall clock.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.use_monotonic_for_intervals(true);
+    /// scheduler.every(30.seconds()).run(|| println!("Unaffected by clock changes"));
+    /// scheduler.every(1.day()).at("9:00").run(|| println!("Still runs at 9:00, by the wall clock"));
+    /// ```
+    pub fn use_monotonic_for_intervals(&mut self, enabled: bool) {
+        self.monotonic_intervals = enabled;
+        for job in &mut self.jobs {
+            job.schedule_mut().use_monotonic(enabled);
+        }
+    }
+
+    /// Convert the interval of each job added with [`Scheduler::every`] to its canonical form, using
+    /// [`Interval::normalized`], so that e.g. `every(3600.seconds())` and `every(1.hour())` give jobs with the same
+    /// interval. See `Interval::normalized` for when this can change the times jobs run.
+    ///
+    /// This applies to jobs added after it's called.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.normalize_intervals(true);
+    /// let job = scheduler.every(120.minutes()).run(|| println!("Every two hours"));
+    /// assert_eq!(job.frequencies()[0].base(), 2.hours());
+    /// ```
+    pub fn normalize_intervals(&mut self, enabled: bool) {
+        self.normalize_intervals = enabled;
+    }
+
     /// Run all jobs that should run at this time.
     ///
     /// This method blocks while jobs are being run. If a job takes a long time, it may prevent
@@ -169,19 +856,726 @@ where
     /// }
     /// ```
     pub fn run_pending(&mut self) {
-        let now = Tp::now(&self.tz);
-        for job in &mut self.jobs {
-            if job.is_pending(&now) {
-                job.execute(&now);
-            }
+        let now = self.now();
+        self.run_due_jobs(&now, true);
+    }
+
+    /// Run only the job that's been due the longest, if any job is due, returning whether a job ran.
+    /// Unlike [`Scheduler::run_pending`], this returns after at most one job, so that a single-threaded
+    /// event loop can do other work between jobs.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.minute()).run(|| println!("Periodic task"));
+    /// while scheduler.run_next_pending() {
+    ///     // Handle other events between jobs
+    /// }
+    /// ```
+    /// Jobs due for the same length of time run in the same order as with `run_pending`: highest
+    /// [priority](Job::priority) first, and then in the order they were added. Due jobs that are skipped,
+    /// e.g. for being too late, don't count as running.
+    pub fn run_next_pending(&mut self) -> bool {
+        let now = self.now();
+        self.record_tick(&now);
+        if self.catch_up_after_pause(&now) {
+            return false;
         }
+        let mut due: Vec<_> = self
+            .jobs
+            .iter()
+            .enumerate()
+            .filter_map(|(index, job)| {
+                let overdue = job.schedule().overdue_by(&now, true)?;
+                Some((
+                    Reverse(overdue),
+                    Reverse(job.schedule().get_priority()),
+                    index,
+                ))
+            })
+            .collect();
+        due.sort();
+        let ran = due
+            .into_iter()
+            .any(|(_, _, index)| self.jobs[index].execute(&now));
+        self.run_idle_job(ran);
+        ran
     }
-}
 
-impl<Tz> Scheduler<Tz>
-where
-    Tz: chrono::TimeZone + Sync + Send + 'static,
-    <Tz as chrono::TimeZone>::Offset: Send,
+    /// Run all jobs that should run at the time `now`, rather than the time given by the scheduler's
+    /// [`TimeProvider`]. This is useful for driving the scheduler from your own clock or timer.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use chrono::{TimeZone, Utc};
+    /// let mut scheduler = Scheduler::with_tz(Utc);
+    /// scheduler.every(1.hour()).run(|| println!("Hourly"));
+    /// scheduler.run_pending_at(Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap());
+    /// ```
+    /// Jobs work out when they should first run using the [`TimeProvider`], so `now` should come from
+    /// a clock that agrees with it.
+    /// Jobs that use a monotonic clock (see [`Scheduler::use_monotonic_for_intervals`]) also go by `now`
+    /// here, since there's no monotonic time to go with it.
+    pub fn run_pending_at(&mut self, now: DateTime<Tz>) {
+        self.run_due_jobs(&now, false);
+    }
+
+    /// Step through virtual time from `from` to `to`, inclusive, in steps of `step`, running every job
+    /// that's due as if [`Scheduler::run_pending_at`] were called at each step. Returns the time and job ID
+    /// of every run, in order. This is useful for checking a whole schedule in tests without sleeping.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use chrono::{Duration as ChronoDuration, Utc};
+    /// # use std::time::Duration;
+    /// let mut scheduler = Scheduler::with_tz(Utc);
+    /// let id = scheduler.every(1.hour()).run(|| {}).id();
+    /// let start = Utc::now();
+    /// let runs = scheduler.simulate(start, start + ChronoDuration::days(1), Duration::from_secs(60));
+    /// assert!(runs.iter().all(|&(_, job)| job == id));
+    /// assert!(runs.windows(2).all(|pair| pair[1].0 - pair[0].0 == ChronoDuration::hours(1)));
+    /// ```
+    /// As with `run_pending_at`, jobs work out when they should first run using the scheduler's [`TimeProvider`],
+    /// so `from` should be no earlier than when the jobs were added. Runs happen at the first step on or after
+    /// their scheduled time, so they're only as precise as `step`.
+    ///
+    /// Panics if `step` is zero.
+    pub fn simulate(
+        &mut self,
+        from: DateTime<Tz>,
+        to: DateTime<Tz>,
+        step: Duration,
+    ) -> Vec<(DateTime<Tz>, JobId)> {
+        assert!(
+            !step.is_zero(),
+            "The simulation step must be more than zero"
+        );
+        let step = chrono::Duration::from_std(step).ok();
+        let mut runs = vec![];
+        let mut now = Some(from);
+        while let Some(current) = now.filter(|now| *now <= to) {
+            self.run_due_jobs_with(&current, false, |id| runs.push((current.clone(), id)));
+            now = step.and_then(|step| current.checked_add_signed(step));
+        }
+        runs
+    }
+
+    /// List when each job would run from `from` to `to`, inclusive, without running any of them.
+    /// Returns the ID and time of every run, in order. This is a safe way to check a schedule
+    /// before putting it into production.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use chrono::{Duration, TimeZone, Utc};
+    /// let mut scheduler = Scheduler::with_tz(Utc);
+    /// let id = scheduler.every(1.day()).at("09:00").run(|| panic!("Not in a dry run")).id();
+    /// let start = Utc::now();
+    /// let runs = scheduler.dry_run(start, start + Duration::weeks(1));
+    /// assert_eq!(runs.len(), 7);
+    /// assert!(runs.iter().all(|(job, time)| *job == id && time.time() == chrono::NaiveTime::from_hms_opt(9, 0, 0).unwrap()));
+    /// ```
+    /// Unlike [`Scheduler::simulate`], neither the jobs' tasks nor callbacks like [`Job::on_reschedule`] are called,
+    /// and runs happen exactly when they're scheduled. The jobs themselves are left as they are: the runs are worked
+    /// out on copies of their schedules, so they don't count towards limits like [`Job::count`].
+    /// Conditions given to [`Job::run_if`] aren't checked, and runs left to chance with [`Job::with_probability`]
+    /// are assumed to go ahead.
+    ///
+    /// As with [`Scheduler::run_pending_at`], jobs work out when they should first run using the scheduler's
+    /// [`TimeProvider`], so `from` should be no earlier than when the jobs were added. Runs that were due before `from`
+    /// happen at `from`. Paused jobs don't run.
+    pub fn dry_run(&self, from: DateTime<Tz>, to: DateTime<Tz>) -> Vec<(JobId, DateTime<Tz>)> {
+        let mut schedules: Vec<_> = self
+            .jobs
+            .iter()
+            .map(|job| job.schedule().detached_copy())
+            .collect();
+        // Jobs due at the same time run in the same order as with `run_pending`
+        schedules.sort_by_key(|schedule| Reverse(schedule.get_priority()));
+        let mut runs = vec![];
+        loop {
+            let next = schedules
+                .iter()
+                .filter_map(|schedule| schedule.upcoming_run())
+                .min()
+                .map(|next| next.clone().max(from.clone()));
+            let now = match next {
+                Some(next) if next <= to => next,
+                _ => break,
+            };
+            for schedule in &mut schedules {
+                let due = matches!(schedule.upcoming_run(), Some(next) if *next <= now);
+                if due && schedule.run_dry(&now) {
+                    runs.push((schedule.id(), now.clone()));
+                }
+            }
+        }
+        runs
+    }
+
+    /// Run all jobs that should run at this time, like [`Scheduler::run_pending`], but catch any job that
+    /// panics and pass the panic to `on_panic`, along with the job's ID, rather than letting it unwind
+    /// out of the scheduler. The run counts as a failure (see [`Job::disable_after_failures`]), the job is
+    /// rescheduled, and the remaining due jobs still run. Callbacks such as [`Job::on_reschedule`] aren't
+    /// called for a run that panics; if one of them panics itself, its panic is passed to `on_panic` too.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.minute()).run(|| panic!("Oops"));
+    /// scheduler.run_pending_with(|id, panic| {
+    ///     let message = panic.downcast_ref::<&str>().copied().unwrap_or("unknown error");
+    ///     eprintln!("Job {:?} panicked: {}", id, message);
+    /// });
+    /// ```
+    /// The panic message is still printed by the panic hook; see [`std::panic::set_hook`] to change that.
+    pub fn run_pending_with<F>(&mut self, mut on_panic: F)
+    where
+        F: FnMut(JobId, Box<dyn Any + Send>),
+    {
+        let now = self.now();
+        self.record_tick(&now);
+        if self.catch_up_after_pause(&now) {
+            return;
+        }
+        let mut ran = false;
+        for job in by_priority(&mut self.jobs) {
+            let id = job.id();
+            ran |=
+                job.run_while_pending_catching(&now, true, || (), |payload| on_panic(id, payload));
+        }
+        self.run_idle_job(ran);
+    }
+
+    /// Run all jobs that should run at this time, like [`Scheduler::run_pending`], but with each
+    /// job on its own thread, so that a slow job doesn't hold up the others.
+    /// This method returns once all of the jobs have finished.
+    ///
+    /// Jobs that share state must synchronize access to it (e.g. using a `Mutex`), since they may now
+    /// run at the same time. If a job panics, the panic is passed on once the other jobs have finished.
+    ///
+    /// By default, a new thread is started for each job that's due. To reuse the same threads on each tick,
+    /// call [`Scheduler::with_thread_pool`] first.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// use std::sync::{Arc, Mutex};
+    /// let mut scheduler = Scheduler::new();
+    /// let log = Arc::new(Mutex::new(Vec::new()));
+    /// let log2 = log.clone();
+    /// scheduler.every(1.minute()).run(move || log2.lock().unwrap().push("fetched"));
+    /// scheduler.every(1.minute()).run(move || log.lock().unwrap().push("indexed"));
+    /// scheduler.run_pending_parallel();
+    /// ```
+    pub fn run_pending_parallel(&mut self)
+    where
+        Tz::Offset: Send + Sync,
+        Tp: Send,
+    {
+        let now = self.now();
+        self.record_tick(&now);
+        if self.catch_up_after_pause(&now) {
+            return;
+        }
+        if let Some(runner) = &self.thread_pool {
+            let ran = (runner.run)(&runner.pool, &mut self.jobs, &now);
+            self.run_idle_job(ran);
+            return;
+        }
+        let now = &now;
+        let ran = thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .jobs
+                .iter_mut()
+                .filter(|job| job.schedule().is_due(now, true))
+                .map(|job| {
+                    // Missed runs replayed with `CatchUp::RunAll` stay on the job's thread
+                    scope.spawn(move || job.run_while_pending(now, true, || ()))
+                })
+                .collect();
+            // Joining the rest is left to the scope
+            handles
+                .into_iter()
+                .any(|handle| handle.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+        });
+        self.run_idle_job(ran);
+    }
+
+    /// Run the pending jobs on the thread pool, returning whether any ran. Each job is moved to a
+    /// worker while it runs, and put back in its place once it's done.
+    fn run_on_thread_pool(
+        pool: &ThreadPool,
+        all_jobs: &mut Vec<SyncJob<Tz, Tp>>,
+        now: &DateTime<Tz>,
+    ) -> bool
+    where
+        Tz: 'static,
+        Tz::Offset: Send + Sync,
+        Tp: Send + 'static,
+    {
+        let mut jobs: Vec<_> = std::mem::take(all_jobs).into_iter().map(Some).collect();
+        let (done, finished) = mpsc::channel();
+        let mut started = 0;
+        for (index, slot) in jobs.iter_mut().enumerate() {
+            let mut job = match slot.take() {
+                Some(job) if job.schedule().is_due(now, true) => job,
+                job => {
+                    *slot = job;
+                    continue;
+                }
+            };
+            let now = now.clone();
+            let done = done.clone();
+            pool.execute(move || {
+                // Missed runs replayed with `CatchUp::RunAll` stay on the same worker
+                let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                    job.run_while_pending(&now, true, || ())
+                }));
+                // The receiver only goes away if the scheduler panicked while waiting
+                let _ = done.send((index, job, result));
+            });
+            started += 1;
+        }
+        let mut ran = false;
+        let mut panicked = None;
+        for (index, job, result) in finished.iter().take(started) {
+            jobs[index] = Some(job);
+            match result {
+                Ok(job_ran) => ran |= job_ran,
+                Err(payload) => {
+                    panicked.get_or_insert(payload);
+                }
+            }
+        }
+        *all_jobs = jobs.into_iter().map(|job| job.unwrap()).collect();
+        if let Some(payload) = panicked {
+            panic::resume_unwind(payload);
+        }
+        ran
+    }
+
+    /// Run all jobs that should run at this time, and return how many ran, and how long
+    /// until the next job is due. This is the same as calling [Scheduler::run_pending()] followed by
+    /// [Scheduler::time_until_next_run()], and is useful for loops that sleep until there's work to do.
+    ///
+    /// The time until the next job is `None` if no jobs will ever run again.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// use std::thread;
+    /// use std::time::Duration;
+    /// # let mut scheduler = Scheduler::new();
+    /// # scheduler.every(1.minute()).run(|| {});
+    /// loop {
+    ///     let (_ran, next) = scheduler.run_pending_and_advise();
+    ///     // Check back at least once a minute, in case the system clock changes
+    ///     let next = next.unwrap_or(Duration::MAX).min(Duration::from_secs(60));
+    ///     # break;
+    ///     thread::sleep(next);
+    /// }
+    /// ```
+    pub fn run_pending_and_advise(&mut self) -> (usize, Option<Duration>) {
+        let now = self.now();
+        let ran = self.run_due_jobs(&now, true);
+        (ran, self.time_until_next_run())
+    }
+
+    fn run_due_jobs(&mut self, now: &DateTime<Tz>, from_clock: bool) -> usize {
+        let mut ran = 0;
+        self.run_due_jobs_with(now, from_clock, |_| ran += 1);
+        ran
+    }
+
+    /// Run the jobs that are due at `now`, calling `on_run` with the ID of each one that runs.
+    /// `from_clock` is set if `now` was just read from the scheduler's clock, so that jobs timed by the
+    /// monotonic clock can go by that instead.
+    fn run_due_jobs_with<F: FnMut(JobId)>(
+        &mut self,
+        now: &DateTime<Tz>,
+        from_clock: bool,
+        mut on_run: F,
+    ) {
+        self.record_tick(now);
+        if self.catch_up_after_pause(now) {
+            return;
+        }
+        let mut ran = false;
+        for job in by_priority(&mut self.jobs) {
+            let id = job.id();
+            ran |= job.run_while_pending(now, from_clock, || on_run(id));
+        }
+        self.run_idle_job(ran);
+    }
+
+    /// How long until the next job is due to run, or `None` if no jobs will ever run again.
+    /// If a job is already overdue, this is zero.
+    pub fn time_until_next_run(&self) -> Option<Duration> {
+        let now = self.now();
+        self.jobs
+            .iter()
+            .filter_map(|job| job.schedule().time_until_due(&now, true))
+            .min()
+    }
+
+    /// Delay the next run of the job with ID `id` by `delay`, returning whether there's a job with that ID.
+    /// Only the next run is affected: later runs follow the job's usual schedule.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use std::time::Duration;
+    /// let mut scheduler = Scheduler::new();
+    /// let id = scheduler.every(1.hour()).run(|| println!("Hourly")).id();
+    /// // Give the database ten more minutes to come back up
+    /// scheduler.defer(id, Duration::from_secs(10 * 60));
+    /// ```
+    pub fn defer(&mut self, id: JobId, delay: Duration) -> bool {
+        match self.jobs.iter_mut().find(|job| job.id() == id) {
+            Some(job) => {
+                job.schedule_mut().defer(delay);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Run the job with ID `id` straight away, outside its schedule, returning whether it ran.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// let id = scheduler.every(1.hour()).max_per_day(3).run(|| println!("Syncing")).id();
+    /// // The user asked for a sync
+    /// scheduler.run_now(id);
+    /// ```
+    /// The run counts towards [`Job::max_per_day`], so the job doesn't run if it's already reached its limit
+    /// for the day. It doesn't run if there's no job with that ID, or if it won't run again, e.g. because it's
+    /// been disabled by [`Job::disable_after_failures`]. Paused jobs still run. The run doesn't count towards
+    /// [`Job::count`], and doesn't change when the job next runs, unless it reaches the daily limit.
+    pub fn run_now(&mut self, id: JobId) -> bool {
+        let now = self.now();
+        match self.get_by_id_mut(id) {
+            Some(job) => job.run_now(&now),
+            None => false,
+        }
+    }
+
+    /// Skip the next run of the job with ID `id`, returning whether there's a job with that ID.
+    /// The job runs as usual after that. Skipped runs don't count towards [`Job::count`].
+    pub fn skip_next(&mut self, id: JobId) -> bool {
+        match self.jobs.iter_mut().find(|job| job.id() == id) {
+            Some(job) => {
+                job.schedule_mut().skip_next();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Find the job with ID `id`, e.g. as returned by [`SyncJob::run_with_handle`].
+    pub fn get_by_id(&self, id: JobId) -> Option<&SyncJob<Tz, Tp>> {
+        self.jobs.iter().find(|job| job.id() == id)
+    }
+
+    /// Like [`Scheduler::get_by_id`], but allows the job to be changed.
+    pub fn get_by_id_mut(&mut self, id: JobId) -> Option<&mut SyncJob<Tz, Tp>> {
+        self.jobs.iter_mut().find(|job| job.id() == id)
+    }
+
+    /// When the job with ID `id` will next run, or `None` if there's no such job, or it's paused or won't run again.
+    pub fn next_run(&self, id: JobId) -> Option<DateTime<Tz>> {
+        self.get_by_id(id)
+            .and_then(|job| job.schedule().upcoming_run().cloned())
+    }
+
+    /// Pause the job with ID `id`, returning whether there's a job with that ID.
+    /// The job doesn't run until resumed with [`Scheduler::resume_job`].
+    pub fn pause_job(&mut self, id: JobId) -> bool {
+        match self.get_by_id_mut(id) {
+            Some(job) => {
+                job.schedule_mut().pause();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Resume the job with ID `id`, returning whether there's a job with that ID.
+    /// Runs that were missed while the job was paused are skipped, rather than run late.
+    pub fn resume_job(&mut self, id: JobId) -> bool {
+        match self.get_by_id_mut(id) {
+            Some(job) => {
+                job.schedule_mut().resume();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove the job with ID `id` from the scheduler, returning whether there was a job with that ID.
+    pub fn remove(&mut self, id: JobId) -> bool {
+        let before = self.jobs.len();
+        self.jobs.retain(|job| job.id() != id);
+        let removed = self.jobs.len() < before;
+        if removed {
+            self.jobs_changed();
+        }
+        removed
+    }
+
+    /// Remove every job from the scheduler.
+    pub fn clear(&mut self) {
+        if !self.jobs.is_empty() {
+            self.jobs.clear();
+            self.jobs_changed();
+        }
+    }
+
+    /// List the jobs that are due to run, with how long each has been due.
+    /// Jobs that are due for much longer than the time between calls to [`Scheduler::run_pending`]
+    /// suggest that other jobs are taking too long to run.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.minute()).run(|| println!("Periodic task"));
+    /// for (id, overdue) in scheduler.overdue() {
+    ///     println!("Job {:?} has been due for {:?}", id, overdue);
+    /// }
+    /// ```
+    pub fn overdue(&self) -> Vec<(JobId, Duration)> {
+        let now = self.now();
+        self.jobs
+            .iter()
+            .filter_map(|job| Some((job.id(), job.schedule().overdue_by(&now, true)?)))
+            .collect()
+    }
+
+    /// List the jobs that will run between now and `window` from now, with when they'll run, soonest first.
+    /// Jobs that are already overdue are listed by [`Scheduler::overdue`] instead.
+    /// Nothing is listed while the scheduler is paused.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use std::time::Duration;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.day()).at("09:00").run(|| println!("Morning report"));
+    /// for (id, next_run) in scheduler.due_within(Duration::from_secs(60 * 60)) {
+    ///     println!("Job {:?} will run at {}", id, next_run);
+    /// }
+    /// ```
+    pub fn due_within(&self, window: Duration) -> Vec<(JobId, DateTime<Tz>)> {
+        if self.is_paused() {
+            return vec![];
+        }
+        let now = self.now();
+        // A window too long to represent includes every scheduled run
+        let end = chrono::Duration::from_std(window)
+            .ok()
+            .and_then(|window| now.clone().checked_add_signed(window));
+        let mut due: Vec<_> = self
+            .jobs
+            .iter()
+            .filter_map(|job| Some((job.id(), job.schedule().upcoming_run()?.clone())))
+            .filter(|(_, next_run)| {
+                *next_run >= now && end.as_ref().map_or(true, |end| next_run <= end)
+            })
+            .collect();
+        due.sort_by(|(_, a), (_, b)| a.cmp(b));
+        due
+    }
+
+    /// List each job's runs over the next `horizon` as an iCalendar (`.ics`) file, e.g. to import into
+    /// a calendar app. Each run is an event named by the job's label. Available with the `ical` feature.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use std::time::Duration;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.day()).at("02:00").label("Nightly backup").run(|| println!("Backing up"));
+    /// let ics = scheduler.to_ics(Duration::from_secs(7 * 24 * 60 * 60));
+    /// assert_eq!(ics.matches("SUMMARY:Nightly backup").count(), 7);
+    /// ```
+    /// As with [`Job::fire_times`], this assumes that every run happens on time. Jobs without a label are
+    /// named by their ID. Paused jobs aren't included, and nothing is while the scheduler is paused.
+    /// At most 10,000 runs of each job are listed, however long the horizon.
+    #[cfg(feature = "ical")]
+    pub fn to_ics(&self, horizon: Duration) -> String {
+        use crate::ical::{calendar, Event};
+        let now = self.now();
+        let mut events = vec![];
+        if !self.is_paused() {
+            // A horizon too long to represent includes every scheduled run, up to the limit
+            let end = chrono::Duration::from_std(horizon)
+                .ok()
+                .and_then(|horizon| now.clone().checked_add_signed(horizon))
+                .map_or(chrono::NaiveDateTime::MAX, |end| end.naive_utc());
+            for job in &self.jobs {
+                let first = match job.schedule().upcoming_run() {
+                    Some(first) => first.clone(),
+                    None => continue,
+                };
+                let summary = match job.get_label() {
+                    Some(label) => label.to_owned(),
+                    None => format!("Job {}", job.id().as_u64()),
+                };
+                // The first run may have been moved from its usual time, e.g. by `Scheduler::defer`
+                let runs = std::iter::once(first.clone())
+                    .chain(job.fire_times(first))
+                    .take(
+                        job.runs_remaining()
+                            .unwrap_or(usize::MAX)
+                            .min(ICS_EVENT_LIMIT),
+                    );
+                for start in runs.take_while(|start| start.naive_utc() <= end) {
+                    let start = start.with_timezone(&chrono::Utc);
+                    events.push(Event {
+                        uid: format!("{}-{}@clokwerk", job.id().as_u64(), start.timestamp()),
+                        summary: summary.clone(),
+                        start,
+                    });
+                }
+            }
+        }
+        events.sort_by_key(|event| event.start);
+        calendar(&events, now.with_timezone(&chrono::Utc))
+    }
+
+    /// Test whether any job is due to run at this time. This is always false while the scheduler is paused.
+    pub fn has_pending(&self) -> bool {
+        if self.is_paused() {
+            return false;
+        }
+        let now = self.now();
+        self.jobs
+            .iter()
+            .any(|job| job.schedule().is_due(&now, true))
+    }
+
+    /// Repeatedly call [Scheduler::run_pending()] until no jobs are pending.
+    ///
+    /// This is useful on startup after downtime, to process any overdue jobs before returning.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.day()).at("3:00 am").once().run(|| println!("Catching up"));
+    /// scheduler.run_until_idle();
+    /// ```
+    ///
+    /// As a safeguard against jobs that are always pending, this method gives up after
+    /// 100 rounds of [Scheduler::run_pending()], even if jobs are still pending.
+    pub fn run_until_idle(&mut self) {
+        for _ in 0..RUN_UNTIL_IDLE_LIMIT {
+            if !self.has_pending() {
+                break;
+            }
+            self.run_pending();
+        }
+    }
+
+    /// Capture each job's label, next and last run times, and remaining runs, for comparing
+    /// against an expected state in tests. Available with the `testing` feature.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use clokwerk::timeprovider::MockClock;
+    /// use chrono::{TimeZone, Utc};
+    /// MockClock::set(Utc.with_ymd_and_hms(2021, 3, 14, 15, 9, 0).unwrap());
+    /// let mut scheduler = Scheduler::with_tz_and_provider::<Utc, MockClock>(Utc);
+    /// scheduler.every(1.hour()).label("hourly").count(2).run(|| {});
+    /// assert_eq!(
+    ///     scheduler.state_snapshot(),
+    ///     SchedulerSnapshot {
+    ///         jobs: vec![JobSnapshot {
+    ///             label: Some("hourly".to_string()),
+    ///             next_run: Some(Utc.with_ymd_and_hms(2021, 3, 14, 16, 0, 0).unwrap()),
+    ///             last_run: None,
+    ///             runs_remaining: Some(2),
+    ///         }]
+    ///     }
+    /// );
+    /// ```
+    #[cfg(any(test, feature = "testing"))]
+    pub fn state_snapshot(&self) -> SchedulerSnapshot<Tz> {
+        let jobs = self
+            .jobs
+            .iter()
+            .map(|job| JobSnapshot {
+                label: job.get_label().map(str::to_owned),
+                next_run: job
+                    .schedule()
+                    .next_run()
+                    .filter(|_| job.schedule().can_run_again())
+                    .cloned(),
+                last_run: job.schedule().last_run().cloned(),
+                runs_remaining: job.runs_remaining(),
+            })
+            .collect();
+        SchedulerSnapshot { jobs }
+    }
+}
+
+/// The state of a scheduler's jobs, from [`Scheduler::state_snapshot`]. Available with the `testing` feature.
+#[cfg(any(test, feature = "testing"))]
+#[derive(Debug, Clone)]
+pub struct SchedulerSnapshot<Tz: chrono::TimeZone> {
+    /// Each job's state, in the order the jobs were added
+    pub jobs: Vec<JobSnapshot<Tz>>,
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl<Tz: chrono::TimeZone> PartialEq for SchedulerSnapshot<Tz> {
+    fn eq(&self, other: &Self) -> bool {
+        self.jobs == other.jobs
+    }
+}
+
+/// The state of one job in a [`SchedulerSnapshot`].
+#[cfg(any(test, feature = "testing"))]
+#[derive(Debug, Clone)]
+pub struct JobSnapshot<Tz: chrono::TimeZone> {
+    /// The job's label, if it has one
+    pub label: Option<String>,
+    /// When the job will next run, or `None` if it won't run again
+    pub next_run: Option<DateTime<Tz>>,
+    /// When the job last ran, if it has
+    pub last_run: Option<DateTime<Tz>>,
+    /// How many more times the job will run, or `None` if it runs forever
+    pub runs_remaining: Option<usize>,
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl<Tz: chrono::TimeZone> PartialEq for JobSnapshot<Tz> {
+    fn eq(&self, other: &Self) -> bool {
+        self.label == other.label
+            && self.next_run == other.next_run
+            && self.last_run == other.last_run
+            && self.runs_remaining == other.runs_remaining
+    }
+}
+
+/// Lists each job, with its label and when it will next run.
+impl<Tz, Tp> fmt::Display for Scheduler<Tz, Tp>
+where
+    Tz: chrono::TimeZone + Sync + Send,
+    Tz::Offset: fmt::Display,
+    Tp: TimeProvider,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for job in &self.jobs {
+            let label = job.get_label().unwrap_or("(unlabelled)");
+            match job.schedule().next_run() {
+                Some(next_run) if job.schedule().can_run_again() => writeln!(
+                    f,
+                    "{}: next run at {}",
+                    label,
+                    next_run.format(&self.time_format)
+                )?,
+                _ => writeln!(f, "{}: not scheduled", label)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+const DEFAULT_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Maximum number of rounds [Scheduler::run_until_idle()] will run before giving up.
+const RUN_UNTIL_IDLE_LIMIT: usize = 100;
+
+/// Maximum number of runs of each job listed by [Scheduler::to_ics()].
+#[cfg(feature = "ical")]
+const ICS_EVENT_LIMIT: usize = 10_000;
+
+impl<Tz> Scheduler<Tz>
+where
+    Tz: chrono::TimeZone + Sync + Send + 'static,
+    <Tz as chrono::TimeZone>::Offset: Send,
 {
     /// Start a background thread to call [Scheduler::run_pending()] repeatedly.
     /// The frequency argument controls how long the thread will sleep between calls
@@ -195,33 +1589,116 @@ where
     ///
     /// Reasonable values for `frequency` would be between 100 ms and 10 seconds.
     /// If in doubt, choose a smaller value.
+    ///
+    /// The thread can be monitored using [ScheduleHandle::is_running()] and [ScheduleHandle::last_tick()],
+    /// e.g. to detect that it stopped because a job panicked.
     #[must_use = "The scheduler is halted when the returned handle is dropped"]
     pub fn watch_thread(self, frequency: Duration) -> ScheduleHandle {
+        self.spawn_thread(frequency, None)
+    }
+
+    /// Like [Scheduler::watch_thread()], but the thread stops by itself once `total` has elapsed, e.g. for a
+    /// short-lived tool. It stops promptly at the deadline, rather than finishing its sleep of `frequency` first.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use std::time::Duration;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.second()).run(|| println!("Still working"));
+    /// let handle = scheduler.run_for(Duration::from_millis(50), Duration::from_millis(10));
+    /// std::thread::sleep(Duration::from_millis(200));
+    /// assert!(!handle.is_running());
+    /// ```
+    /// The thread can still be stopped early by calling [ScheduleHandle::stop()] or dropping the handle.
+    /// A job that's running at the deadline isn't interrupted, so the thread stops once it has finished.
+    #[must_use = "The scheduler is halted when the returned handle is dropped"]
+    pub fn run_for(self, total: Duration, frequency: Duration) -> ScheduleHandle {
+        // A deadline too far away to represent is never reached
+        let deadline = Instant::now().checked_add(total);
+        self.spawn_thread(frequency, deadline)
+    }
+
+    /// Start the thread for [Scheduler::watch_thread()], stopping at `deadline` if there is one.
+    fn spawn_thread(self, frequency: Duration, deadline: Option<Instant>) -> ScheduleHandle {
         let stop = Arc::new(AtomicBool::new(false));
         let my_stop = stop.clone();
+        let paused = self.paused.clone();
+        let started = Instant::now();
+        let last_tick = Arc::new(AtomicU64::new(NO_TICK));
+        let my_last_tick = last_tick.clone();
         let mut me = self;
+        let time_left =
+            move || deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
         let handle = thread::spawn(move || {
-            while !stop.load(Ordering::SeqCst) {
+            while !stop.load(Ordering::SeqCst) && time_left() != Some(Duration::ZERO) {
                 me.run_pending();
-                thread::sleep(frequency);
+                let nanos = u64::try_from(started.elapsed().as_nanos()).unwrap_or(NO_TICK - 1);
+                last_tick.store(nanos, Ordering::SeqCst);
+                thread::sleep(time_left().map_or(frequency, |left| left.min(frequency)));
             }
         });
         ScheduleHandle {
             stop: my_stop,
+            paused,
+            started,
+            last_tick: my_last_tick,
             thread_handle: Some(handle),
         }
     }
 }
 
+/// Stored as the last tick of a [ScheduleHandle] before the first one
+const NO_TICK: u64 = u64::MAX;
+
 /// Guard object for the scheduler background thread. The thread is terminated if this object
 /// is dropped, or [ScheduleHandle::stop()] is called
 pub struct ScheduleHandle {
     stop: Arc<AtomicBool>,
+    paused: Arc<AtomicBool>,
+    started: Instant,
+    // When the thread last finished checking for pending jobs, in nanoseconds since `started`
+    last_tick: Arc<AtomicU64>,
     thread_handle: Option<thread::JoinHandle<()>>,
 }
 impl ScheduleHandle {
     /// Halt the scheduler background thread
     pub fn stop(self) {}
+
+    /// Whether the background thread is still running. This is `false` if a job panicked,
+    /// since the panic ends the thread.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # use std::time::Duration;
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.minute()).run(|| println!("Periodic task"));
+    /// let handle = scheduler.watch_thread(Duration::from_millis(100));
+    /// assert!(handle.is_running());
+    /// ```
+    pub fn is_running(&self) -> bool {
+        self.thread_handle
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished())
+    }
+
+    /// When the background thread last finished checking for pending jobs, or `None` if it hasn't yet.
+    /// If this is much longer ago than the `frequency` passed to [Scheduler::watch_thread()], the thread
+    /// is stuck running a slow job, or has stopped.
+    pub fn last_tick(&self) -> Option<Instant> {
+        match self.last_tick.load(Ordering::SeqCst) {
+            NO_TICK => None,
+            nanos => self.started.checked_add(Duration::from_nanos(nanos)),
+        }
+    }
+
+    /// Pause the scheduler, like [`Scheduler::pause`]. The thread keeps running, but no jobs run.
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::SeqCst);
+    }
+
+    /// Resume the scheduler, like [`Scheduler::resume`]. Missed runs are skipped the next time
+    /// the thread checks for pending jobs.
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::SeqCst);
+    }
 }
 
 impl Drop for ScheduleHandle {
@@ -234,38 +1711,26 @@ impl Drop for ScheduleHandle {
 
 #[cfg(test)]
 mod tests {
-    use super::{Job, Scheduler, TimeProvider};
+    use super::{Job, Scheduler};
     use crate::intervals::*;
     use std::sync::{atomic::AtomicU32, atomic::Ordering, Arc};
+    use std::time::Duration;
 
-    macro_rules! make_time_provider {
-        ($name:ident : $($time:literal),+) => {
-            #[derive(Debug)]
-            struct $name {}
-            static TIMES_TIME_REQUESTED: once_cell::sync::Lazy<AtomicU32> = once_cell::sync::Lazy::new(|| AtomicU32::new(0));
-            impl TimeProvider for $name {
-                fn now<Tz>(tz: &Tz) -> chrono::DateTime<Tz>
-                where
-                    Tz: chrono::TimeZone + Sync + Send,
-                    {
-                        let times = [$(chrono::DateTime::parse_from_rfc3339($time).unwrap()),+];
-                        let idx = TIMES_TIME_REQUESTED.fetch_add(1, Ordering::SeqCst) as usize;
-                        times[idx].with_timezone(&tz)
-                    }
-            }
-        };
+    fn utc(time: &str) -> chrono::DateTime<chrono::Utc> {
+        chrono::DateTime::parse_from_rfc3339(time)
+            .unwrap()
+            .with_timezone(&chrono::Utc)
+    }
+
+    /// Move a scheduler created with `frozen_at` forward to `time`
+    fn advance_to(scheduler: &mut Scheduler<chrono::Utc>, time: &str) {
+        let duration = (utc(time) - scheduler.now()).to_std().unwrap();
+        scheduler.advance(duration);
     }
 
     #[test]
     fn test_every_plus() {
-        make_time_provider!(FakeTimeProvider :
-            "2019-10-22T12:40:00Z",
-            "2019-10-22T12:40:00Z",
-            "2019-10-22T12:50:20Z",
-            "2019-10-22T12:50:30Z"
-        );
-        let mut scheduler =
-            Scheduler::with_tz_and_provider::<chrono::Utc, FakeTimeProvider>(chrono::Utc);
+        let mut scheduler = Scheduler::frozen_at(chrono::Utc, utc("2019-10-22T12:40:00Z"));
         let times_called = Arc::new(AtomicU32::new(0));
         {
             let times_called = times_called.clone();
@@ -276,29 +1741,19 @@ mod tests {
                     times_called.fetch_add(1, Ordering::SeqCst);
                 });
         }
-        assert_eq!(1, TIMES_TIME_REQUESTED.load(Ordering::SeqCst));
         scheduler.run_pending();
         assert_eq!(0, times_called.load(Ordering::SeqCst));
-        assert_eq!(2, TIMES_TIME_REQUESTED.load(Ordering::SeqCst));
+        advance_to(&mut scheduler, "2019-10-22T12:50:20Z");
         scheduler.run_pending();
         assert_eq!(1, times_called.load(Ordering::SeqCst));
-        assert_eq!(3, TIMES_TIME_REQUESTED.load(Ordering::SeqCst));
+        advance_to(&mut scheduler, "2019-10-22T12:50:30Z");
         scheduler.run_pending();
         assert_eq!(1, times_called.load(Ordering::SeqCst));
-        assert_eq!(4, TIMES_TIME_REQUESTED.load(Ordering::SeqCst));
     }
 
     #[test]
     fn test_every_at() {
-        make_time_provider!(FakeTimeProvider:
-            "2019-10-22T12:40:00Z",
-            "2019-10-22T12:40:10Z",
-            "2019-10-25T12:50:20Z",
-            "2019-10-25T15:23:30Z",
-            "2019-10-26T15:50:30Z"
-        );
-        let mut scheduler =
-            Scheduler::with_tz_and_provider::<chrono::Utc, FakeTimeProvider>(chrono::Utc);
+        let mut scheduler = Scheduler::frozen_at(chrono::Utc, utc("2019-10-22T12:40:00Z"));
         let times_called = Arc::new(AtomicU32::new(0));
         {
             let times_called = times_called.clone();
@@ -306,31 +1761,20 @@ mod tests {
                 times_called.fetch_add(1, Ordering::SeqCst);
             });
         }
-        assert_eq!(1, TIMES_TIME_REQUESTED.load(Ordering::SeqCst));
+        advance_to(&mut scheduler, "2019-10-22T12:40:10Z");
         scheduler.run_pending();
         assert_eq!(0, times_called.load(Ordering::SeqCst));
-        assert_eq!(2, TIMES_TIME_REQUESTED.load(Ordering::SeqCst));
+        advance_to(&mut scheduler, "2019-10-25T12:50:20Z");
         scheduler.run_pending();
         assert_eq!(1, times_called.load(Ordering::SeqCst));
-        assert_eq!(3, TIMES_TIME_REQUESTED.load(Ordering::SeqCst));
+        advance_to(&mut scheduler, "2019-10-25T15:23:30Z");
         scheduler.run_pending();
         assert_eq!(1, times_called.load(Ordering::SeqCst));
-        assert_eq!(4, TIMES_TIME_REQUESTED.load(Ordering::SeqCst));
     }
 
     #[test]
     fn test_every_and_every() {
-        make_time_provider!(FakeTimeProvider:
-            "2019-10-22T12:40:01Z",
-            "2019-10-22T12:40:01Z",
-            "2019-10-22T12:40:02Z",
-            "2019-10-22T12:40:03Z",
-            "2019-10-22T12:40:04Z",
-            "2019-10-22T12:40:05Z",
-            "2019-10-22T12:40:06Z"
-        );
-        let mut scheduler =
-            Scheduler::with_tz_and_provider::<chrono::Utc, FakeTimeProvider>(chrono::Utc);
+        let mut scheduler = Scheduler::frozen_at(chrono::Utc, utc("2019-10-22T12:40:01Z"));
         let times_called = Arc::new(AtomicU32::new(0));
         {
             let times_called = times_called.clone();
@@ -341,37 +1785,16 @@ mod tests {
                     times_called.fetch_add(1, Ordering::SeqCst);
                 });
         }
-        assert_eq!(1, TIMES_TIME_REQUESTED.load(Ordering::SeqCst));
-        scheduler.run_pending();
-        assert_eq!(2, TIMES_TIME_REQUESTED.load(Ordering::SeqCst));
-        assert_eq!(0, times_called.load(Ordering::SeqCst));
-        scheduler.run_pending();
-        assert_eq!(3, TIMES_TIME_REQUESTED.load(Ordering::SeqCst));
-        assert_eq!(1, times_called.load(Ordering::SeqCst));
-        scheduler.run_pending();
-        assert_eq!(4, TIMES_TIME_REQUESTED.load(Ordering::SeqCst));
-        assert_eq!(1, times_called.load(Ordering::SeqCst));
-        scheduler.run_pending();
-        assert_eq!(5, TIMES_TIME_REQUESTED.load(Ordering::SeqCst));
-        assert_eq!(2, times_called.load(Ordering::SeqCst));
-        scheduler.run_pending();
-        assert_eq!(6, TIMES_TIME_REQUESTED.load(Ordering::SeqCst));
-        assert_eq!(3, times_called.load(Ordering::SeqCst));
-        scheduler.run_pending();
-        assert_eq!(7, TIMES_TIME_REQUESTED.load(Ordering::SeqCst));
-        assert_eq!(4, times_called.load(Ordering::SeqCst));
+        for expected in [0, 1, 1, 2, 3, 4] {
+            scheduler.run_pending();
+            assert_eq!(expected, times_called.load(Ordering::SeqCst));
+            scheduler.advance(Duration::from_secs(1));
+        }
     }
 
     #[test]
     fn test_once() {
-        make_time_provider!(FakeTimeProvider:
-            "2019-10-22T12:40:01Z",
-            "2019-10-22T12:40:01Z",
-            "2019-10-22T12:40:02Z",
-            "2019-10-22T12:40:03Z"
-        );
-        let mut scheduler =
-            Scheduler::with_tz_and_provider::<chrono::Utc, FakeTimeProvider>(chrono::Utc);
+        let mut scheduler = Scheduler::frozen_at(chrono::Utc, utc("2019-10-22T12:40:01Z"));
         let times_called = Arc::new(AtomicU32::new(0));
         {
             let times_called = times_called.clone();
@@ -379,15 +1802,2505 @@ mod tests {
                 times_called.fetch_add(1, Ordering::SeqCst);
             });
         }
-        assert_eq!(1, TIMES_TIME_REQUESTED.load(Ordering::SeqCst));
+        for expected in [0, 1, 1] {
+            scheduler.run_pending();
+            assert_eq!(expected, times_called.load(Ordering::SeqCst));
+            scheduler.advance(Duration::from_secs(1));
+        }
+    }
+
+    #[test]
+    fn test_mock_clock() {
+        use crate::timeprovider::{lock_mock_clock, MockClock};
+        use chrono::TimeZone;
+        use std::time::Duration;
+        let _guard = lock_mock_clock();
+        MockClock::set(
+            chrono::Utc
+                .with_ymd_and_hms(2019, 10, 22, 12, 40, 0)
+                .unwrap(),
+        );
+        let mut scheduler = Scheduler::with_tz_and_provider::<chrono::Utc, MockClock>(chrono::Utc);
+        let times_called = Arc::new(AtomicU32::new(0));
+        {
+            let times_called = times_called.clone();
+            scheduler.every(10.minutes()).run(move || {
+                times_called.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        scheduler.run_pending();
+        assert_eq!(0, times_called.load(Ordering::SeqCst));
+        MockClock::advance(Duration::from_secs(9 * 60));
         scheduler.run_pending();
-        assert_eq!(2, TIMES_TIME_REQUESTED.load(Ordering::SeqCst));
         assert_eq!(0, times_called.load(Ordering::SeqCst));
+        MockClock::advance(Duration::from_secs(60));
         scheduler.run_pending();
-        assert_eq!(3, TIMES_TIME_REQUESTED.load(Ordering::SeqCst));
         assert_eq!(1, times_called.load(Ordering::SeqCst));
         scheduler.run_pending();
-        assert_eq!(4, TIMES_TIME_REQUESTED.load(Ordering::SeqCst));
         assert_eq!(1, times_called.load(Ordering::SeqCst));
     }
+
+    #[test]
+    fn test_dst_policy() {
+        use crate::DstPolicy;
+        let mut scheduler = Scheduler::new();
+        scheduler.every(1.day()).at("2:30").label("existing");
+        let mut scheduler = scheduler.with_dst_policy(DstPolicy::Latest);
+        scheduler
+            .every(Interval::Sunday)
+            .at("1:30")
+            .and_every(Interval::Wednesday)
+            .label("new");
+        for label in ["existing", "new"] {
+            let job = scheduler.get(label).unwrap();
+            for freq in job.frequencies() {
+                assert_eq!(freq.dst_policy(), DstPolicy::Latest, "{}", label);
+            }
+        }
+    }
+
+    #[test]
+    fn test_normalize_intervals() {
+        let mut scheduler = Scheduler::new();
+        scheduler.every(3600.seconds()).label("before");
+        scheduler.normalize_intervals(true);
+        scheduler.every(3600.seconds()).label("after");
+        scheduler.every(90.seconds()).label("uneven");
+        let base = |label| scheduler.get(label).unwrap().frequencies()[0].base();
+        assert_eq!(base("before"), Interval::Seconds(3600));
+        assert_eq!(base("after"), Interval::Hours(1));
+        assert_eq!(base("uneven"), Interval::Seconds(90));
+    }
+
+    #[test]
+    fn test_monotonic_intervals() {
+        use crate::timeprovider::{lock_mock_clock, MockClock};
+        use crate::Interval::Monday;
+        use chrono::TimeZone;
+        use std::time::Duration;
+        let _guard = lock_mock_clock();
+        MockClock::set(
+            chrono::Utc
+                .with_ymd_and_hms(2019, 10, 22, 12, 40, 0)
+                .unwrap(),
+        );
+        let mut scheduler = Scheduler::with_tz_and_provider::<chrono::Utc, MockClock>(chrono::Utc);
+        let interval_called = Arc::new(AtomicU32::new(0));
+        let weekly_called = Arc::new(AtomicU32::new(0));
+        {
+            let interval_called = interval_called.clone();
+            scheduler.every(1.second()).run(move || {
+                interval_called.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        scheduler.use_monotonic_for_intervals(true);
+        {
+            let weekly_called = weekly_called.clone();
+            scheduler.every(Monday).run(move || {
+                weekly_called.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        // The wall clock jumps back an hour, and forward a week, but only time passing matters to the interval job
+        MockClock::set(
+            chrono::Utc
+                .with_ymd_and_hms(2019, 10, 22, 11, 40, 0)
+                .unwrap(),
+        );
+        scheduler.run_pending();
+        assert_eq!(0, interval_called.load(Ordering::SeqCst));
+        MockClock::set(
+            chrono::Utc
+                .with_ymd_and_hms(2019, 10, 29, 11, 40, 0)
+                .unwrap(),
+        );
+        scheduler.run_pending();
+        assert_eq!(0, interval_called.load(Ordering::SeqCst));
+        assert_eq!(1, weekly_called.load(Ordering::SeqCst));
+        MockClock::advance(Duration::from_millis(1100));
+        scheduler.run_pending();
+        assert_eq!(1, interval_called.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_monotonic_intervals_with_given_time() {
+        let start = utc("2019-10-22T12:40:00Z");
+        let mut scheduler = Scheduler::frozen_at(chrono::Utc, start);
+        scheduler.use_monotonic_for_intervals(true);
+        let id = scheduler.every(10.minutes()).run(|| {}).id();
+        // The monotonic clock never moves, but the times given still count
+        let runs = scheduler.simulate(
+            start,
+            start + chrono::Duration::hours(1),
+            Duration::from_secs(60),
+        );
+        assert_eq!(6, runs.len());
+        assert!(runs.iter().all(|&(_, job)| job == id));
+        scheduler.run_pending_at(start + chrono::Duration::minutes(70));
+        assert_eq!(
+            scheduler.next_run(id),
+            Some(start + chrono::Duration::minutes(80))
+        );
+    }
+
+    #[test]
+    fn test_monotonic_intervals_frozen() {
+        let mut scheduler = Scheduler::frozen_at(chrono::Utc, utc("2019-10-22T12:40:00Z"));
+        scheduler.use_monotonic_for_intervals(true);
+        let times_called = Arc::new(AtomicU32::new(0));
+        {
+            let times_called = times_called.clone();
+            scheduler.every(10.minutes()).run(move || {
+                times_called.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        assert_eq!(
+            scheduler.run_pending_and_advise(),
+            (0, Some(Duration::from_secs(600)))
+        );
+        scheduler.advance(Duration::from_secs(599));
+        assert!(!scheduler.has_pending());
+        assert_eq!(
+            scheduler.time_until_next_run(),
+            Some(Duration::from_secs(1))
+        );
+        scheduler.advance(Duration::from_secs(1));
+        assert!(scheduler.has_pending());
+        assert_eq!(
+            scheduler.run_pending_and_advise(),
+            (1, Some(Duration::from_secs(600)))
+        );
+        scheduler.advance(Duration::from_secs(600));
+        scheduler.run_pending();
+        assert_eq!(2, times_called.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_run_immediately() {
+        let mut scheduler = Scheduler::frozen_at(chrono::Utc, utc("2019-10-22T12:40:00Z"));
+        let times_called = Arc::new(AtomicU32::new(0));
+        {
+            let times_called = times_called.clone();
+            scheduler
+                .every(1.hour())
+                .plus(5.minutes())
+                .run_immediately()
+                .run(move || {
+                    times_called.fetch_add(1, Ordering::SeqCst);
+                });
+        }
+        scheduler.run_pending();
+        assert_eq!(1, times_called.load(Ordering::SeqCst));
+        // Later runs are aligned to the interval, including the offset
+        advance_to(&mut scheduler, "2019-10-22T13:00:00Z");
+        scheduler.run_pending();
+        assert_eq!(1, times_called.load(Ordering::SeqCst));
+        advance_to(&mut scheduler, "2019-10-22T13:05:00Z");
+        scheduler.run_pending();
+        assert_eq!(2, times_called.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_skip_if_late_by() {
+        let mut scheduler = Scheduler::frozen_at(chrono::Utc, utc("2019-10-22T12:40:00Z"));
+        let times_called = Arc::new(AtomicU32::new(0));
+        {
+            let times_called = times_called.clone();
+            scheduler
+                .every(10.minutes())
+                .count(2)
+                .skip_if_late_by(std::time::Duration::from_secs(5 * 60))
+                .run(move || {
+                    times_called.fetch_add(1, Ordering::SeqCst);
+                });
+        }
+        let mut run_at = |time, expected| {
+            advance_to(&mut scheduler, time);
+            scheduler.run_pending();
+            assert_eq!(expected, times_called.load(Ordering::SeqCst), "{}", time);
+        };
+        // Over two hours late for 12:50, so skipped, and rescheduled for 15:10
+        run_at("2019-10-22T15:00:00Z", 0);
+        run_at("2019-10-22T15:00:30Z", 0);
+        // Late, but within the threshold
+        run_at("2019-10-22T15:10:30Z", 1);
+        // Six minutes late for 15:20
+        run_at("2019-10-22T15:26:00Z", 1);
+        // Skipped runs don't count towards the limit
+        run_at("2019-10-22T15:30:10Z", 2);
+    }
+
+    #[test]
+    fn test_run_pending_and_advise() {
+        let mut scheduler = Scheduler::frozen_at(chrono::Utc, utc("2019-10-22T12:40:00Z"));
+        scheduler.every(10.minutes()).count(2).run(|| {});
+        advance_to(&mut scheduler, "2019-10-22T12:45:00Z");
+        assert_eq!(
+            scheduler.run_pending_and_advise(),
+            (0, Some(Duration::from_secs(5 * 60)))
+        );
+        assert_eq!(
+            scheduler.time_until_next_run(),
+            Some(Duration::from_secs(5 * 60))
+        );
+        advance_to(&mut scheduler, "2019-10-22T12:50:10Z");
+        assert_eq!(
+            scheduler.run_pending_and_advise(),
+            (1, Some(Duration::from_secs(9 * 60 + 50)))
+        );
+        // The job has run out of runs
+        advance_to(&mut scheduler, "2019-10-22T13:00:00Z");
+        assert_eq!(scheduler.run_pending_and_advise(), (1, None));
+    }
+
+    #[test]
+    fn test_groups() {
+        use crate::timeprovider::{lock_mock_clock, MockClock};
+        use chrono::TimeZone;
+        let _guard = lock_mock_clock();
+        MockClock::set(
+            chrono::Utc
+                .with_ymd_and_hms(2019, 10, 22, 12, 40, 0)
+                .unwrap(),
+        );
+        let mut scheduler = Scheduler::with_tz_and_provider::<chrono::Utc, MockClock>(chrono::Utc);
+        let reports = Arc::new(AtomicU32::new(0));
+        let other = Arc::new(AtomicU32::new(0));
+        for _ in 0..2 {
+            let reports = reports.clone();
+            scheduler
+                .every(10.minutes())
+                .group("reporting")
+                .run(move || {
+                    reports.fetch_add(1, Ordering::SeqCst);
+                });
+        }
+        {
+            let other = other.clone();
+            scheduler.every(10.minutes()).run(move || {
+                other.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+
+        assert_eq!(scheduler.pause_group("reporting"), 2);
+        assert_eq!(scheduler.pause_group("no such group"), 0);
+        MockClock::advance(Duration::from_secs(10 * 60));
+        scheduler.run_pending();
+        assert_eq!(0, reports.load(Ordering::SeqCst));
+        assert_eq!(1, other.load(Ordering::SeqCst));
+
+        // The 12:50 run was missed while paused, so isn't run on resuming
+        MockClock::advance(Duration::from_secs(5 * 60));
+        assert_eq!(scheduler.resume_group("reporting"), 2);
+        scheduler.run_pending();
+        assert_eq!(0, reports.load(Ordering::SeqCst));
+        MockClock::advance(Duration::from_secs(5 * 60));
+        scheduler.run_pending();
+        assert_eq!(2, reports.load(Ordering::SeqCst));
+        assert_eq!(2, other.load(Ordering::SeqCst));
+
+        assert_eq!(scheduler.remove_group("reporting"), 2);
+        assert_eq!(scheduler.jobs.len(), 1);
+        MockClock::advance(Duration::from_secs(10 * 60));
+        scheduler.run_pending();
+        assert_eq!(2, reports.load(Ordering::SeqCst));
+        assert_eq!(3, other.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_run_pending_at() {
+        let mut scheduler = Scheduler::frozen_at(chrono::Utc, utc("2019-10-22T12:40:00Z"));
+        let times_called = Arc::new(AtomicU32::new(0));
+        {
+            let times_called = times_called.clone();
+            scheduler.every(10.minutes()).run(move || {
+                times_called.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        scheduler.run_pending_at(utc("2019-10-22T12:49:59Z"));
+        assert_eq!(0, times_called.load(Ordering::SeqCst));
+        scheduler.run_pending_at(utc("2019-10-22T12:50:00Z"));
+        assert_eq!(1, times_called.load(Ordering::SeqCst));
+        scheduler.run_pending_at(utc("2019-10-22T12:55:00Z"));
+        assert_eq!(1, times_called.load(Ordering::SeqCst));
+        scheduler.run_pending_at(utc("2019-10-22T13:00:00Z"));
+        assert_eq!(2, times_called.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_relative_to_start() {
+        let mut scheduler = Scheduler::frozen_at(chrono::Utc, utc("2019-10-22T12:40:10Z"));
+        let aligned = Arc::new(AtomicU32::new(0));
+        let relative = Arc::new(AtomicU32::new(0));
+        {
+            let aligned = aligned.clone();
+            scheduler.every(1.hour()).run(move || {
+                aligned.fetch_add(1, Ordering::SeqCst);
+            });
+            let relative = relative.clone();
+            scheduler
+                .every(1.hour())
+                .plus(10.seconds())
+                .relative_to_start()
+                .run(move || {
+                    relative.fetch_add(1, Ordering::SeqCst);
+                });
+        }
+        let check = |scheduler: &mut Scheduler<_, _>, time, expected_aligned, expected_relative| {
+            scheduler.run_pending_at(utc(time));
+            assert_eq!(expected_aligned, aligned.load(Ordering::SeqCst), "{}", time);
+            assert_eq!(
+                expected_relative,
+                relative.load(Ordering::SeqCst),
+                "{}",
+                time
+            );
+        };
+        check(&mut scheduler, "2019-10-22T13:00:00Z", 1, 0);
+        check(&mut scheduler, "2019-10-22T13:40:19Z", 1, 0);
+        check(&mut scheduler, "2019-10-22T13:40:20Z", 1, 1);
+        check(&mut scheduler, "2019-10-22T14:00:00Z", 2, 1);
+        // Counted from the last run, which was a little late
+        check(&mut scheduler, "2019-10-22T14:41:00Z", 2, 2);
+        check(&mut scheduler, "2019-10-22T15:41:09Z", 3, 2);
+        check(&mut scheduler, "2019-10-22T15:41:10Z", 3, 3);
+    }
+
+    #[test]
+    fn test_every_unique() {
+        use crate::SpecError;
+        let mut scheduler = Scheduler::new();
+        scheduler.every(1.hour()).run(|| {});
+        assert!(scheduler.every_unique(1.hour(), "a").is_ok());
+        assert!(scheduler.every_unique(1.hour(), "b").is_ok());
+        assert_eq!(
+            scheduler.every_unique(1.day(), "a").err(),
+            Some(SpecError::DuplicateLabel {
+                label: "a".to_string()
+            })
+        );
+        assert_eq!(scheduler.jobs.len(), 3);
+    }
+
+    #[test]
+    fn test_every_unit() {
+        let mut scheduler = Scheduler::new();
+        let pairs = vec![
+            (
+                scheduler.every_seconds(30).explain(),
+                scheduler.every(30.seconds()).explain(),
+            ),
+            (
+                scheduler.every_minutes(5).explain(),
+                scheduler.every(5.minutes()).explain(),
+            ),
+            (
+                scheduler.every_hours(2).explain(),
+                scheduler.every(2.hours()).explain(),
+            ),
+            (
+                scheduler.every_days(3).explain(),
+                scheduler.every(3.days()).explain(),
+            ),
+            (
+                scheduler.every_weeks(1).explain(),
+                scheduler.every(1.week()).explain(),
+            ),
+            (
+                scheduler.every_day_at("3:30 pm").explain(),
+                scheduler.every(1.day()).at("15:30").explain(),
+            ),
+        ];
+        for (made, expected) in pairs {
+            assert_eq!(made, expected);
+        }
+        assert_eq!(scheduler.jobs.len(), 12);
+
+        let noon = chrono::NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+        assert_eq!(
+            scheduler.every_at(Interval::Friday, noon).explain(),
+            scheduler.every(Interval::Friday).at("12:00").explain(),
+        );
+    }
+
+    #[test]
+    fn test_from_specs() {
+        use crate::SpecError;
+        use chrono::NaiveTime;
+        use std::collections::HashMap;
+        type Closures = HashMap<String, Box<dyn FnMut() + Send>>;
+        fn closures(labels: &[&str]) -> Closures {
+            labels
+                .iter()
+                .map(|label| {
+                    (
+                        label.to_string(),
+                        Box::new(|| {}) as Box<dyn FnMut() + Send>,
+                    )
+                })
+                .collect()
+        }
+        let specs = vec![
+            ("a".to_string(), 1.day(), NaiveTime::from_hms_opt(2, 0, 0)),
+            ("b".to_string(), 30.seconds(), None),
+        ];
+
+        let scheduler =
+            Scheduler::from_specs(specs.clone(), closures(&["b", "a", "unused"])).unwrap();
+        let labels: Vec<_> = scheduler.jobs.iter().map(|job| job.get_label()).collect();
+        assert_eq!(labels, vec![Some("a"), Some("b")]);
+
+        let err = Scheduler::from_specs(specs.clone(), closures(&["a"])).err();
+        assert_eq!(
+            err,
+            Some(SpecError::MissingClosure {
+                label: "b".to_string()
+            })
+        );
+
+        let mut duplicated = specs;
+        duplicated.push(("a".to_string(), 1.hour(), None));
+        let err = Scheduler::from_specs(duplicated, closures(&["a", "b"])).err();
+        assert_eq!(
+            err,
+            Some(SpecError::DuplicateLabel {
+                label: "a".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_run_until_idle() {
+        let mut scheduler = Scheduler::frozen_at(chrono::Utc, utc("2019-10-22T12:40:01Z"));
+        let times_called = Arc::new(AtomicU32::new(0));
+        {
+            let times_called = times_called.clone();
+            scheduler.every(1.hour()).once().run(move || {
+                times_called.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        {
+            let times_called = times_called.clone();
+            scheduler.every(1.day()).count(2).run(move || {
+                times_called.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        assert!(!scheduler.has_pending());
+        scheduler.run_until_idle();
+        assert_eq!(0, times_called.load(Ordering::SeqCst));
+        // Time jumps ahead: one round runs the hourly job, after which nothing is pending
+        advance_to(&mut scheduler, "2019-10-22T15:00:00Z");
+        scheduler.run_until_idle();
+        assert_eq!(1, times_called.load(Ordering::SeqCst));
+        assert!(!scheduler.has_pending());
+    }
+
+    #[test]
+    fn test_zero_interval() {
+        use crate::timeprovider::{lock_mock_clock, MockClock};
+        use chrono::TimeZone;
+        let _guard = lock_mock_clock();
+        MockClock::set(
+            chrono::Utc
+                .with_ymd_and_hms(2019, 10, 22, 12, 40, 1)
+                .unwrap(),
+        );
+        let mut scheduler = Scheduler::with_tz_and_provider::<chrono::Utc, MockClock>(chrono::Utc);
+        let times_called = Arc::new(AtomicU32::new(0));
+        {
+            let times_called = times_called.clone();
+            scheduler
+                .every(0.seconds())
+                .repeating_every(0.seconds())
+                .times(2)
+                .run(move || {
+                    times_called.fetch_add(1, Ordering::SeqCst);
+                });
+        }
+        scheduler.run_until_idle();
+        assert_eq!(0, times_called.load(Ordering::SeqCst));
+        // Runs at most once a second, rather than every time the scheduler checks
+        MockClock::advance(Duration::from_secs(1));
+        scheduler.run_until_idle();
+        assert_eq!(1, times_called.load(Ordering::SeqCst));
+        for _ in 0..5 {
+            scheduler.run_pending();
+        }
+        assert_eq!(1, times_called.load(Ordering::SeqCst));
+        MockClock::advance(Duration::from_secs(1));
+        scheduler.run_until_idle();
+        assert_eq!(2, times_called.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_minimum_interval() {
+        use crate::timeprovider::{lock_mock_clock, MockClock};
+        use chrono::TimeZone;
+        let _guard = lock_mock_clock();
+        MockClock::set(
+            chrono::Utc
+                .with_ymd_and_hms(2019, 10, 22, 12, 40, 0)
+                .unwrap(),
+        );
+        let mut scheduler = Scheduler::with_tz_and_provider::<chrono::Utc, MockClock>(chrono::Utc);
+        let times_called = Arc::new(AtomicU32::new(0));
+        {
+            let times_called = times_called.clone();
+            scheduler.every(1.second()).run(move || {
+                times_called.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        scheduler.set_minimum_interval(Duration::from_secs(10));
+        MockClock::advance(Duration::from_secs(1));
+        scheduler.run_pending();
+        assert_eq!(1, times_called.load(Ordering::SeqCst));
+        for _ in 0..9 {
+            MockClock::advance(Duration::from_secs(1));
+            scheduler.run_pending();
+        }
+        assert_eq!(1, times_called.load(Ordering::SeqCst));
+        MockClock::advance(Duration::from_secs(1));
+        scheduler.run_pending();
+        assert_eq!(2, times_called.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_time_format() {
+        use crate::timeprovider::{lock_mock_clock, MockClock};
+        use chrono::TimeZone;
+        let _guard = lock_mock_clock();
+        MockClock::set(
+            chrono::Utc
+                .with_ymd_and_hms(2019, 10, 22, 12, 40, 0)
+                .unwrap(),
+        );
+        let mut scheduler = Scheduler::with_tz_and_provider::<chrono::Utc, MockClock>(chrono::Utc);
+        scheduler.every(1.day()).at("14:30").label("tea").run(|| {});
+        scheduler.every(1.hour()).once().run(|| {});
+        assert_eq!(
+            "tea: next run at 2019-10-22 14:30:00\n(unlabelled): next run at 2019-10-22 13:00:00\n",
+            scheduler.to_string()
+        );
+
+        scheduler.with_time_format("%a %-I:%M %p");
+        MockClock::advance(Duration::from_secs(20 * 60));
+        scheduler.run_pending();
+        assert_eq!(
+            "tea: next run at Tue 2:30 PM\n(unlabelled): not scheduled\n",
+            scheduler.to_string()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid time format")]
+    fn test_invalid_time_format() {
+        Scheduler::new().with_time_format("%Q");
+    }
+
+    #[test]
+    fn test_pause_scheduler() {
+        use crate::timeprovider::{lock_mock_clock, MockClock};
+        use chrono::TimeZone;
+        let _guard = lock_mock_clock();
+        MockClock::set(
+            chrono::Utc
+                .with_ymd_and_hms(2019, 10, 22, 12, 40, 0)
+                .unwrap(),
+        );
+        let mut scheduler = Scheduler::with_tz_and_provider::<chrono::Utc, MockClock>(chrono::Utc);
+        let times_called = Arc::new(AtomicU32::new(0));
+        {
+            let times_called = times_called.clone();
+            scheduler.every(10.minutes()).run(move || {
+                times_called.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        MockClock::advance(Duration::from_secs(10 * 60));
+        scheduler.run_pending();
+        assert_eq!(1, times_called.load(Ordering::SeqCst));
+
+        scheduler.pause();
+        assert!(scheduler.is_paused());
+        // Several runs are missed while paused
+        for _ in 0..5 {
+            MockClock::advance(Duration::from_secs(10 * 60));
+            assert!(!scheduler.has_pending());
+            scheduler.run_pending();
+        }
+        assert_eq!(1, times_called.load(Ordering::SeqCst));
+
+        // 13:45; the 13:40 run was missed, so the next is at 13:50
+        MockClock::advance(Duration::from_secs(5 * 60));
+        scheduler.resume();
+        assert!(!scheduler.is_paused());
+        scheduler.run_until_idle();
+        assert_eq!(1, times_called.load(Ordering::SeqCst));
+        MockClock::advance(Duration::from_secs(5 * 60));
+        scheduler.run_until_idle();
+        assert_eq!(2, times_called.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_resume_without_polling() {
+        use chrono::TimeZone;
+        let mut scheduler = Scheduler::frozen_at(
+            chrono::Utc,
+            chrono::Utc
+                .with_ymd_and_hms(2019, 10, 22, 12, 40, 0)
+                .unwrap(),
+        );
+        let times_called = Arc::new(AtomicU32::new(0));
+        {
+            let times_called = times_called.clone();
+            scheduler.every(10.minutes()).run(move || {
+                times_called.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        // The 12:50 run is missed while paused, though the scheduler is never polled
+        scheduler.pause();
+        scheduler.advance(Duration::from_secs(15 * 60));
+        scheduler.resume();
+        assert!(!scheduler.has_pending());
+        scheduler.run_pending();
+        assert_eq!(0, times_called.load(Ordering::SeqCst));
+        scheduler.advance(Duration::from_secs(5 * 60));
+        scheduler.run_pending();
+        assert_eq!(1, times_called.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_run_pending_parallel() {
+        use std::sync::Barrier;
+        let mut scheduler = Scheduler::new();
+        // Each job waits for the other, so this only finishes if they run at the same time
+        let barrier = Arc::new(Barrier::new(2));
+        let times_called = Arc::new(AtomicU32::new(0));
+        for _ in 0..2 {
+            let barrier = barrier.clone();
+            let times_called = times_called.clone();
+            scheduler.every(1.hour()).run_immediately().run(move || {
+                barrier.wait();
+                times_called.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        scheduler.run_pending_parallel();
+        assert_eq!(2, times_called.load(Ordering::SeqCst));
+        scheduler.run_pending_parallel();
+        assert_eq!(2, times_called.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_run_pending_parallel_thread_pool() {
+        use chrono::{TimeZone, Utc};
+        use std::collections::HashSet;
+        use std::panic::{self, AssertUnwindSafe};
+        use std::sync::Mutex;
+        use std::thread;
+        let start = Utc.with_ymd_and_hms(2021, 3, 14, 15, 9, 0).unwrap();
+        let mut scheduler = Scheduler::frozen_at(Utc, start);
+        scheduler.with_thread_pool(2);
+        let threads = Arc::new(Mutex::new(HashSet::new()));
+        let times_called = Arc::new(AtomicU32::new(0));
+        for _ in 0..5 {
+            let threads = threads.clone();
+            let times_called = times_called.clone();
+            scheduler.every(1.minute()).run(move || {
+                threads.lock().unwrap().insert(thread::current().id());
+                times_called.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        let panicky = scheduler.every(1.minute()).run(|| panic!("Oops")).id();
+        for tick in 1..=3 {
+            scheduler.advance(Duration::from_secs(60));
+            // Every job runs, and the panic is passed on once they're done
+            let result = panic::catch_unwind(AssertUnwindSafe(|| scheduler.run_pending_parallel()));
+            assert!(result.is_err());
+            assert_eq!(tick * 5, times_called.load(Ordering::SeqCst));
+        }
+        // The same workers are reused, and the jobs are kept in order
+        let threads = threads.lock().unwrap();
+        assert!(threads.len() <= 2);
+        assert!(!threads.contains(&thread::current().id()));
+        assert_eq!(scheduler.jobs.len(), 6);
+        assert_eq!(scheduler.jobs[5].id(), panicky);
+    }
+
+    #[test]
+    fn test_every_like() {
+        use chrono::TimeZone;
+        let mut scheduler = Scheduler::with_tz(chrono::Utc);
+        let config = scheduler
+            .every(1.day())
+            .at("9:00")
+            .repeating_every(30.minutes())
+            .times(2)
+            .count(3)
+            .label("first")
+            .run(|| {})
+            .schedule_config();
+        let job = scheduler.every_like(&config, || {});
+        assert_eq!(job.get_label(), None);
+        assert_eq!(job.runs_remaining(), Some(3));
+        let start = chrono::Utc
+            .with_ymd_and_hms(2019, 10, 22, 12, 0, 0)
+            .unwrap();
+        let times: Vec<_> = job.fire_times(start).collect();
+        let expected: Vec<_> = scheduler.jobs[0].fire_times(start).collect();
+        assert_eq!(3, times.len());
+        assert_eq!(expected, times);
+    }
+
+    #[test]
+    fn test_on_nth() {
+        use crate::timeprovider::{lock_mock_clock, MockClock};
+        use chrono::TimeZone;
+        let _guard = lock_mock_clock();
+        MockClock::set(
+            chrono::Utc
+                .with_ymd_and_hms(2019, 10, 22, 12, 40, 0)
+                .unwrap(),
+        );
+        let mut scheduler = Scheduler::with_tz_and_provider::<chrono::Utc, MockClock>(chrono::Utc);
+        let times_called = Arc::new(AtomicU32::new(0));
+        let extra_called = Arc::new(AtomicU32::new(0));
+        {
+            let times_called = times_called.clone();
+            let extra_called = extra_called.clone();
+            scheduler
+                .every(1.minute())
+                .run(move || {
+                    times_called.fetch_add(1, Ordering::SeqCst);
+                })
+                .on_nth(3, move || {
+                    extra_called.fetch_add(1, Ordering::SeqCst);
+                });
+        }
+        for i in 1..=7 {
+            MockClock::advance(Duration::from_secs(60));
+            scheduler.run_pending();
+            assert_eq!(i, times_called.load(Ordering::SeqCst));
+            assert_eq!(i / 3, extra_called.load(Ordering::SeqCst));
+        }
+    }
+
+    #[test]
+    fn test_tick_warning() {
+        use chrono::TimeZone;
+        use std::sync::Mutex;
+        let mut scheduler = Scheduler::with_tz(chrono::Utc);
+        let gaps = Arc::new(Mutex::new(vec![]));
+        {
+            let gaps = gaps.clone();
+            scheduler.set_tick_warning(Duration::from_secs(5), move |gap| {
+                gaps.lock().unwrap().push(gap)
+            });
+        }
+        let start = chrono::Utc
+            .with_ymd_and_hms(2019, 10, 22, 12, 0, 0)
+            .unwrap();
+        for secs in [0, 1, 6, 12, 30, 30, 31, 20] {
+            scheduler.run_pending_at(start + chrono::Duration::seconds(secs));
+        }
+        assert_eq!(
+            *gaps.lock().unwrap(),
+            vec![Duration::from_secs(6), Duration::from_secs(18)]
+        );
+    }
+
+    #[test]
+    fn test_at_random_between() {
+        use crate::job_schedule::WithSchedule;
+        use crate::timeprovider::{lock_mock_clock, MockClock};
+        use chrono::{DateTime, NaiveTime, TimeZone, Timelike, Utc};
+        let _guard = lock_mock_clock();
+
+        fn run_times(seed: u64, start: NaiveTime, end: NaiveTime) -> Vec<DateTime<Utc>> {
+            MockClock::set(Utc.with_ymd_and_hms(2019, 10, 22, 12, 0, 0).unwrap());
+            let mut scheduler = Scheduler::with_tz_and_provider::<Utc, MockClock>(Utc);
+            scheduler
+                .every(1.day())
+                .at_random_between(start, end)
+                .random_seed(seed)
+                .run(|| {});
+            let mut times = vec![];
+            for _ in 0..10 {
+                let next_run = *scheduler.jobs[0].schedule().next_run().unwrap();
+                times.push(next_run);
+                MockClock::set(next_run);
+                scheduler.run_pending();
+            }
+            times
+        }
+
+        let two_am = NaiveTime::from_hms_opt(2, 0, 0).unwrap();
+        let four_am = NaiveTime::from_hms_opt(4, 0, 0).unwrap();
+        let times = run_times(7, two_am, four_am);
+        assert_eq!(times, run_times(7, two_am, four_am));
+        assert_ne!(times, run_times(8, two_am, four_am));
+        let mut date = Utc
+            .with_ymd_and_hms(2019, 10, 23, 0, 0, 0)
+            .unwrap()
+            .date_naive();
+        for time in &times {
+            assert_eq!(date, time.date_naive());
+            assert!((two_am..=four_am).contains(&time.time()));
+            date = date.succ_opt().unwrap();
+        }
+        // The times really are random, not all the same
+        assert!(times.iter().any(|time| time.time() != times[0].time()));
+
+        // Windows can cross midnight
+        let eleven_pm = NaiveTime::from_hms_opt(23, 0, 0).unwrap();
+        let one_am = NaiveTime::from_hms_opt(1, 0, 0).unwrap();
+        let times = run_times(7, eleven_pm, one_am);
+        for pair in times.windows(2) {
+            let gap = pair[1] - pair[0];
+            assert!(gap > chrono::Duration::hours(22) && gap < chrono::Duration::hours(26));
+        }
+        for time in &times {
+            assert!(time.hour() == 23 || time.time() <= one_am);
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_tracing() {
+        use crate::timeprovider::{lock_mock_clock, MockClock};
+        use crate::tracing_support::Recorder;
+        use chrono::TimeZone;
+
+        let _guard = lock_mock_clock();
+        MockClock::set(
+            chrono::Utc
+                .with_ymd_and_hms(2019, 10, 22, 12, 40, 0)
+                .unwrap(),
+        );
+        let recorder = Arc::new(Recorder::default());
+        let mut scheduler = Scheduler::with_tz_and_provider::<chrono::Utc, MockClock>(chrono::Utc);
+        scheduler.every(1.minute()).label("tick").run(|| {});
+        scheduler
+            .every(1.minute())
+            .skip_if_late_by(Duration::from_secs(10))
+            .run(|| {});
+        tracing::subscriber::with_default(recorder.clone(), || {
+            MockClock::advance(Duration::from_secs(90));
+            scheduler.run_pending();
+        });
+        let spans = recorder.spans.lock().unwrap();
+        assert_eq!(1, spans.len());
+        assert!(spans[0].starts_with("job "));
+        assert!(spans[0].contains("tick"));
+        assert_eq!(*recorder.events.lock().unwrap(), vec!["WARN".to_string()]);
+        drop(spans);
+
+        // Runs that still go ahead after missing a run are warned about too
+        tracing::subscriber::with_default(recorder.clone(), || {
+            MockClock::advance(Duration::from_secs(90));
+            scheduler.run_pending();
+        });
+        assert_eq!(recorder.spans.lock().unwrap().len(), 2);
+        assert_eq!(recorder.events.lock().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_overdue() {
+        use crate::timeprovider::{lock_mock_clock, MockClock};
+        use chrono::TimeZone;
+        let _guard = lock_mock_clock();
+        MockClock::set(
+            chrono::Utc
+                .with_ymd_and_hms(2019, 10, 22, 12, 40, 0)
+                .unwrap(),
+        );
+        let mut scheduler = Scheduler::with_tz_and_provider::<chrono::Utc, MockClock>(chrono::Utc);
+        let minutely = scheduler.every(1.minute()).run(|| {}).id();
+        let hourly = scheduler.every(1.hour()).run(|| {}).id();
+        assert_ne!(minutely, hourly);
+        assert_eq!(scheduler.overdue(), vec![]);
+
+        MockClock::advance(Duration::from_secs(90));
+        assert_eq!(
+            scheduler.overdue(),
+            vec![(minutely, Duration::from_secs(30))]
+        );
+        MockClock::advance(Duration::from_secs(20 * 60));
+        assert_eq!(
+            scheduler.overdue(),
+            vec![
+                (minutely, Duration::from_secs(20 * 60 + 30)),
+                (hourly, Duration::from_secs(90))
+            ]
+        );
+        scheduler.run_pending();
+        assert_eq!(scheduler.overdue(), vec![]);
+    }
+
+    #[test]
+    fn test_run_once_now() {
+        use crate::timeprovider::{lock_mock_clock, MockClock};
+        use chrono::TimeZone;
+        let _guard = lock_mock_clock();
+        MockClock::set(
+            chrono::Utc
+                .with_ymd_and_hms(2019, 10, 22, 12, 40, 0)
+                .unwrap(),
+        );
+        let mut scheduler = Scheduler::with_tz_and_provider::<chrono::Utc, MockClock>(chrono::Utc);
+        let times_called = Arc::new(AtomicU32::new(0));
+        {
+            let times_called = times_called.clone();
+            scheduler.run_once_now(move || {
+                times_called.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        scheduler.run_pending();
+        assert_eq!(1, times_called.load(Ordering::SeqCst));
+        for _ in 0..3 {
+            MockClock::advance(Duration::from_secs(86400));
+            scheduler.run_pending();
+        }
+        assert_eq!(1, times_called.load(Ordering::SeqCst));
+        assert_eq!(Some(0), scheduler.jobs[0].runs_remaining());
+    }
+
+    #[test]
+    fn test_every_step_in_window() {
+        use crate::job_schedule::WithSchedule;
+        use crate::timeprovider::{lock_mock_clock, MockClock};
+        use chrono::{Datelike, NaiveTime, TimeZone, Timelike, Utc};
+        let _guard = lock_mock_clock();
+
+        fn run_hours(step: Interval, start: u32, end: u32) -> Vec<(u32, u32)> {
+            MockClock::set(Utc.with_ymd_and_hms(2019, 10, 22, 12, 30, 0).unwrap());
+            let mut scheduler = Scheduler::with_tz_and_provider::<Utc, MockClock>(Utc);
+            scheduler
+                .every(1.day())
+                .every_step_in_window(
+                    step,
+                    NaiveTime::from_hms_opt(start, 0, 0).unwrap(),
+                    NaiveTime::from_hms_opt(end, 0, 0).unwrap(),
+                )
+                .run(|| {});
+            let mut hours = vec![];
+            for _ in 0..8 {
+                let next_run = *scheduler.jobs[0].schedule().next_run().unwrap();
+                hours.push((next_run.day(), next_run.hour()));
+                MockClock::set(next_run);
+                scheduler.run_pending();
+            }
+            hours
+        }
+
+        // Starts partway through the window, and stops at the end of it
+        assert_eq!(
+            run_hours(2.hours(), 8, 20),
+            vec![
+                (22, 14),
+                (22, 16),
+                (22, 18),
+                (22, 20),
+                (23, 8),
+                (23, 10),
+                (23, 12),
+                (23, 14)
+            ]
+        );
+        // Steps that don't divide the window evenly
+        assert_eq!(
+            run_hours(5.hours(), 8, 20),
+            vec![
+                (22, 13),
+                (22, 18),
+                (23, 8),
+                (23, 13),
+                (23, 18),
+                (24, 8),
+                (24, 13),
+                (24, 18)
+            ]
+        );
+        // Windows can cross midnight
+        assert_eq!(
+            run_hours(1.hour(), 23, 1),
+            vec![
+                (22, 23),
+                (23, 0),
+                (23, 1),
+                (23, 23),
+                (24, 0),
+                (24, 1),
+                (24, 23),
+                (25, 0)
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "non-zero fixed-length interval")]
+    fn test_every_step_in_window_weekday() {
+        use chrono::NaiveTime;
+        Scheduler::new().every(1.day()).every_step_in_window(
+            Interval::Monday,
+            NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(20, 0, 0).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_defer_and_skip_next() {
+        use crate::timeprovider::{lock_mock_clock, MockClock};
+        use chrono::TimeZone;
+        let _guard = lock_mock_clock();
+        MockClock::set(
+            chrono::Utc
+                .with_ymd_and_hms(2019, 10, 22, 12, 40, 0)
+                .unwrap(),
+        );
+        let mut scheduler = Scheduler::with_tz_and_provider::<chrono::Utc, MockClock>(chrono::Utc);
+        let times_called = Arc::new(AtomicU32::new(0));
+        let id = {
+            let times_called = times_called.clone();
+            scheduler
+                .every(1.hour())
+                .run(move || {
+                    times_called.fetch_add(1, Ordering::SeqCst);
+                })
+                .id()
+        };
+        let run_at = |scheduler: &mut Scheduler<chrono::Utc, MockClock>, h, m| {
+            MockClock::set(chrono::Utc.with_ymd_and_hms(2019, 10, 22, h, m, 0).unwrap());
+            scheduler.run_pending();
+        };
+
+        // 13:00 is deferred to 13:10, and 14:00 runs as usual
+        assert!(scheduler.defer(id, Duration::from_secs(10 * 60)));
+        run_at(&mut scheduler, 13, 0);
+        assert_eq!(0, times_called.load(Ordering::SeqCst));
+        run_at(&mut scheduler, 13, 10);
+        assert_eq!(1, times_called.load(Ordering::SeqCst));
+        run_at(&mut scheduler, 14, 0);
+        assert_eq!(2, times_called.load(Ordering::SeqCst));
+
+        // 15:00 is skipped, and 16:00 runs as usual
+        assert!(scheduler.skip_next(id));
+        run_at(&mut scheduler, 15, 0);
+        assert_eq!(2, times_called.load(Ordering::SeqCst));
+        run_at(&mut scheduler, 16, 0);
+        assert_eq!(3, times_called.load(Ordering::SeqCst));
+        run_at(&mut scheduler, 17, 0);
+        assert_eq!(4, times_called.load(Ordering::SeqCst));
+
+        let other = Scheduler::new().every(1.hour()).id();
+        assert!(!scheduler.defer(other, Duration::from_secs(60)));
+        assert!(!scheduler.skip_next(other));
+    }
+
+    #[test]
+    fn test_after_uptime() {
+        use crate::job_schedule::WithSchedule;
+        use std::time::Instant;
+        let at = |h, m| utc(&format!("2019-10-22T{:02}:{:02}:00Z", h, m));
+
+        let mut scheduler = Scheduler::frozen_at(chrono::Utc, at(12, 40));
+        scheduler
+            .every(10.minutes())
+            .after_uptime(Duration::from_secs(30 * 60))
+            .run(|| {});
+        scheduler
+            .every(1.hour())
+            .after_uptime(Duration::from_secs(5 * 60))
+            .run_immediately()
+            .run(|| {});
+        scheduler.every(10.minutes()).run(|| {});
+        // Too long to ever pass
+        scheduler
+            .every(10.minutes())
+            .after_uptime(Duration::MAX)
+            .run(|| {});
+        let next_runs: Vec<_> = scheduler
+            .jobs
+            .iter()
+            .map(|job| job.schedule().next_run().cloned())
+            .collect();
+        assert_eq!(
+            next_runs,
+            vec![Some(at(13, 10)), Some(at(12, 45)), Some(at(12, 50)), None]
+        );
+
+        // Time passed on the scheduler's clock counts as uptime
+        scheduler.advance(Duration::from_secs(20 * 60));
+        scheduler
+            .every(10.minutes())
+            .after_uptime(Duration::from_secs(30 * 60))
+            .run_immediately()
+            .run(|| {});
+        assert_eq!(scheduler.jobs[4].schedule().next_run(), Some(&at(13, 10)));
+
+        // The uptime has already passed
+        if let Some(start) = Instant::now().checked_sub(Duration::from_secs(10 * 60)) {
+            let mut scheduler = Scheduler::frozen_at(chrono::Utc, at(12, 40));
+            scheduler.with_start_instant(start);
+            scheduler
+                .every(10.minutes())
+                .after_uptime(Duration::from_secs(5 * 60))
+                .run(|| {});
+            assert_eq!(scheduler.jobs[0].schedule().next_run(), Some(&at(12, 50)));
+        }
+    }
+
+    #[test]
+    fn test_after_uptime_mock_clock() {
+        use crate::job_schedule::WithSchedule;
+        use crate::timeprovider::{lock_mock_clock, MockClock};
+        let _guard = lock_mock_clock();
+        MockClock::set(utc("2019-10-22T12:40:00Z"));
+        let mut scheduler = Scheduler::with_tz_and_provider::<chrono::Utc, MockClock>(chrono::Utc);
+        MockClock::advance(Duration::from_secs(20 * 60));
+        scheduler
+            .every(10.minutes())
+            .after_uptime(Duration::from_secs(30 * 60))
+            .run_immediately()
+            .run(|| {});
+        assert_eq!(
+            scheduler.jobs[0].schedule().next_run(),
+            Some(&utc("2019-10-22T13:10:00Z"))
+        );
+    }
+
+    #[test]
+    fn test_catch_up() {
+        use crate::timeprovider::{lock_mock_clock, MockClock};
+        use crate::CatchUp;
+        use chrono::{TimeZone, Utc};
+        let _guard = lock_mock_clock();
+        MockClock::set(Utc.with_ymd_and_hms(2019, 10, 22, 12, 40, 0).unwrap());
+        let run_at = |scheduler: &mut Scheduler<Utc, MockClock>, h, m| {
+            MockClock::set(Utc.with_ymd_and_hms(2019, 10, 22, h, m, 0).unwrap());
+            scheduler.run_pending();
+        };
+
+        let mut scheduler = Scheduler::with_tz_and_provider::<Utc, MockClock>(Utc);
+        let counters: Vec<_> = (0..4).map(|_| Arc::new(AtomicU32::new(0))).collect();
+        let policies = [
+            (CatchUp::RunOnce, Duration::ZERO),
+            (CatchUp::Skip, Duration::ZERO),
+            (CatchUp::RunAll, Duration::ZERO),
+            (CatchUp::RunAll, Duration::from_secs(5 * 60)),
+        ];
+        for (counter, (policy, spacing)) in counters.iter().zip(policies) {
+            let counter = counter.clone();
+            scheduler
+                .every(1.hour())
+                .catch_up(policy)
+                .catch_up_spacing(spacing)
+                .run(move || {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                });
+        }
+        let counts = || -> Vec<u32> {
+            counters
+                .iter()
+                .map(|counter| counter.load(Ordering::SeqCst))
+                .collect()
+        };
+
+        // 13:00, 14:00, 15:00 and 16:00 were all missed
+        run_at(&mut scheduler, 16, 10);
+        assert_eq!(counts(), vec![1, 0, 4, 1]);
+        // Spaced out replays run at most once per call
+        run_at(&mut scheduler, 16, 12);
+        assert_eq!(counts(), vec![1, 0, 4, 1]);
+        run_at(&mut scheduler, 16, 15);
+        run_at(&mut scheduler, 16, 15);
+        assert_eq!(counts(), vec![1, 0, 4, 2]);
+        run_at(&mut scheduler, 16, 20);
+        run_at(&mut scheduler, 16, 25);
+        assert_eq!(counts(), vec![1, 0, 4, 4]);
+        run_at(&mut scheduler, 16, 30);
+        assert_eq!(counts(), vec![1, 0, 4, 4]);
+
+        // Back on schedule; a single late run isn't skipped
+        run_at(&mut scheduler, 17, 30);
+        assert_eq!(counts(), vec![2, 1, 5, 5]);
+    }
+
+    #[test]
+    fn test_with_probability() {
+        use chrono::{TimeZone, Utc};
+        let start = Utc.with_ymd_and_hms(2019, 10, 22, 12, 0, 0).unwrap();
+        let counter = |scheduler: &mut Scheduler<Utc>, p: f64, seed: u64| {
+            let times_called = Arc::new(AtomicU32::new(0));
+            let called = times_called.clone();
+            scheduler
+                .every(1.minute())
+                .random_seed(seed)
+                .with_probability(p)
+                .run(move || {
+                    called.fetch_add(1, Ordering::SeqCst);
+                });
+            times_called
+        };
+
+        let mut scheduler = Scheduler::frozen_at(Utc, start);
+        let quarter = counter(&mut scheduler, 0.25, 42);
+        let always = counter(&mut scheduler, 1.0, 42);
+        let never = counter(&mut scheduler, 0.0, 42);
+        let mut again = Scheduler::frozen_at(Utc, start);
+        let same_seed = counter(&mut again, 0.25, 42);
+        for _ in 0..1000 {
+            scheduler.advance(Duration::from_secs(60));
+            scheduler.run_pending();
+            again.advance(Duration::from_secs(60));
+            again.run_pending();
+        }
+        let quarter = quarter.load(Ordering::SeqCst);
+        assert!((200..300).contains(&quarter), "{} runs", quarter);
+        assert_eq!(quarter, same_seed.load(Ordering::SeqCst));
+        assert_eq!(1000, always.load(Ordering::SeqCst));
+        assert_eq!(0, never.load(Ordering::SeqCst));
+        // Skipped runs still advance the schedule
+        let next = start + chrono::Duration::minutes(1001);
+        let ids: Vec<_> = scheduler.jobs.iter().map(|job| job.id()).collect();
+        for id in ids {
+            assert_eq!(scheduler.next_run(id), Some(next));
+        }
+
+        // Skipped runs don't use up the job's count or call its callbacks
+        let mut scheduler = Scheduler::frozen_at(Utc, start);
+        let rescheduled = Arc::new(AtomicU32::new(0));
+        let id = {
+            let rescheduled = rescheduled.clone();
+            scheduler
+                .every(1.minute())
+                .with_probability(0.0)
+                .count(2)
+                .on_reschedule(move |_| {
+                    rescheduled.fetch_add(1, Ordering::SeqCst);
+                })
+                .run(|| panic!("Ran despite a probability of 0"))
+                .id()
+        };
+        for _ in 0..5 {
+            scheduler.advance(Duration::from_secs(60));
+            scheduler.run_pending();
+        }
+        assert_eq!(0, rescheduled.load(Ordering::SeqCst));
+        assert_eq!(scheduler.get_by_id(id).unwrap().runs_remaining(), Some(2));
+        assert_eq!(
+            scheduler.next_run(id),
+            Some(start + chrono::Duration::minutes(6))
+        );
+    }
+
+    #[test]
+    fn test_dry_run_with_probability() {
+        use chrono::{TimeZone, Utc};
+        let at = |h, m| Utc.with_ymd_and_hms(2019, 10, 22, h, m, 0).unwrap();
+        let mut scheduler = Scheduler::frozen_at(Utc, at(12, 0));
+        let rescheduled = Arc::new(AtomicU32::new(0));
+        let id = {
+            let rescheduled = rescheduled.clone();
+            scheduler
+                .every(1.hour())
+                .with_probability(0.0)
+                .count(3)
+                .on_reschedule(move |_| {
+                    rescheduled.fetch_add(1, Ordering::SeqCst);
+                })
+                .run(|| panic!("Ran in a dry run"))
+                .id()
+        };
+        // Every run is assumed to go ahead
+        let runs = scheduler.dry_run(at(12, 0), at(18, 0));
+        assert_eq!(
+            runs,
+            vec![(id, at(13, 0)), (id, at(14, 0)), (id, at(15, 0))]
+        );
+        assert_eq!(0, rescheduled.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_run_if() {
+        use chrono::{TimeZone, Utc};
+        use std::sync::atomic::AtomicBool;
+        let start = Utc.with_ymd_and_hms(2019, 10, 22, 12, 0, 0).unwrap();
+        let mut scheduler = Scheduler::frozen_at(Utc, start);
+        let enabled = Arc::new(AtomicBool::new(true));
+        let times_called = Arc::new(AtomicU32::new(0));
+        let id = {
+            let enabled = enabled.clone();
+            let times_called = times_called.clone();
+            scheduler
+                .every(1.hour())
+                .run_if(move || enabled.load(Ordering::SeqCst))
+                .run(move || {
+                    times_called.fetch_add(1, Ordering::SeqCst);
+                })
+                .id()
+        };
+        let run_hour = |scheduler: &mut Scheduler<Utc>, on| {
+            enabled.store(on, Ordering::SeqCst);
+            scheduler.advance(Duration::from_secs(3600));
+            scheduler.run_pending();
+        };
+        run_hour(&mut scheduler, true);
+        assert_eq!(1, times_called.load(Ordering::SeqCst));
+        run_hour(&mut scheduler, false);
+        run_hour(&mut scheduler, false);
+        assert_eq!(1, times_called.load(Ordering::SeqCst));
+        // Skipped runs don't pile up for when the condition holds again
+        assert_eq!(
+            scheduler.next_run(id),
+            Some(start + chrono::Duration::hours(4))
+        );
+        run_hour(&mut scheduler, true);
+        assert_eq!(2, times_called.load(Ordering::SeqCst));
+        run_hour(&mut scheduler, true);
+        assert_eq!(3, times_called.load(Ordering::SeqCst));
+
+        // Skipped runs don't use up the job's count
+        let mut scheduler = Scheduler::frozen_at(Utc, start);
+        let times_called = Arc::new(AtomicU32::new(0));
+        {
+            let enabled = enabled.clone();
+            let times_called = times_called.clone();
+            scheduler
+                .every(1.hour())
+                .count(2)
+                .run_if(move || enabled.load(Ordering::SeqCst))
+                .run(move || {
+                    times_called.fetch_add(1, Ordering::SeqCst);
+                });
+        }
+        let run_hour = |scheduler: &mut Scheduler<Utc>, on| {
+            enabled.store(on, Ordering::SeqCst);
+            scheduler.advance(Duration::from_secs(3600));
+            scheduler.run_pending();
+        };
+        run_hour(&mut scheduler, false);
+        run_hour(&mut scheduler, false);
+        run_hour(&mut scheduler, true);
+        run_hour(&mut scheduler, false);
+        run_hour(&mut scheduler, true);
+        run_hour(&mut scheduler, true);
+        assert_eq!(2, times_called.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_dry_run_with_condition() {
+        use chrono::{TimeZone, Utc};
+        let at = |h, m| Utc.with_ymd_and_hms(2019, 10, 22, h, m, 0).unwrap();
+        let mut scheduler = Scheduler::frozen_at(Utc, at(12, 0));
+        let checked = Arc::new(AtomicU32::new(0));
+        let id = {
+            let checked = checked.clone();
+            scheduler
+                .every(1.hour())
+                .count(2)
+                .run_if(move || {
+                    checked.fetch_add(1, Ordering::SeqCst);
+                    false
+                })
+                .run(|| panic!("Ran in a dry run"))
+                .id()
+        };
+        // The condition isn't checked, and every run is assumed to go ahead
+        let runs = scheduler.dry_run(at(12, 0), at(18, 0));
+        assert_eq!(runs, vec![(id, at(13, 0)), (id, at(14, 0))]);
+        assert_eq!(0, checked.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_max_per_day() {
+        use crate::timeprovider::{lock_mock_clock, MockClock};
+        use chrono::{TimeZone, Utc};
+        let _guard = lock_mock_clock();
+        MockClock::set(Utc.with_ymd_and_hms(2019, 10, 22, 20, 40, 0).unwrap());
+        let run_at = |scheduler: &mut Scheduler<Utc, MockClock>, d, h| {
+            MockClock::set(Utc.with_ymd_and_hms(2019, 10, d, h, 0, 0).unwrap());
+            scheduler.run_pending();
+        };
+
+        let mut scheduler = Scheduler::with_tz_and_provider::<Utc, MockClock>(Utc);
+        let times_called = Arc::new(AtomicU32::new(0));
+        {
+            let times_called = times_called.clone();
+            scheduler.every(1.hour()).max_per_day(2).run(move || {
+                times_called.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        run_at(&mut scheduler, 22, 21);
+        run_at(&mut scheduler, 22, 22);
+        assert_eq!(2, times_called.load(Ordering::SeqCst));
+        run_at(&mut scheduler, 22, 23);
+        assert_eq!(2, times_called.load(Ordering::SeqCst));
+
+        // The count resets at midnight
+        run_at(&mut scheduler, 23, 0);
+        run_at(&mut scheduler, 23, 1);
+        assert_eq!(4, times_called.load(Ordering::SeqCst));
+        run_at(&mut scheduler, 23, 2);
+        run_at(&mut scheduler, 23, 3);
+        assert_eq!(4, times_called.load(Ordering::SeqCst));
+        run_at(&mut scheduler, 24, 0);
+        assert_eq!(5, times_called.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_max_per_day_not_pending() {
+        use chrono::{TimeZone, Utc};
+        let at = |d, h| Utc.with_ymd_and_hms(2019, 10, d, h, 0, 0).unwrap();
+        let mut scheduler = Scheduler::frozen_at(Utc, at(22, 20));
+        let id = scheduler.every(1.hour()).max_per_day(2).run(|| {}).id();
+        scheduler.advance(Duration::from_secs(60 * 60));
+        scheduler.run_pending();
+        scheduler.advance(Duration::from_secs(60 * 60));
+        assert!(scheduler.has_pending());
+        scheduler.run_pending();
+
+        // Once the limit is reached, the job isn't due again until the next day
+        assert_eq!(scheduler.next_run(id), Some(at(23, 0)));
+        scheduler.advance(Duration::from_secs(60 * 60));
+        assert!(!scheduler.has_pending());
+        assert_eq!(scheduler.overdue(), vec![]);
+        assert_eq!(scheduler.due_within(Duration::from_secs(30 * 60)), vec![]);
+        scheduler.advance(Duration::from_secs(60 * 60));
+        assert!(scheduler.has_pending());
+    }
+
+    #[test]
+    fn test_run_now() {
+        use chrono::{TimeZone, Utc};
+        let at = |d, h, m| Utc.with_ymd_and_hms(2019, 10, d, h, m, 0).unwrap();
+        let mut scheduler = Scheduler::frozen_at(Utc, at(22, 20, 30));
+        let times_called = Arc::new(AtomicU32::new(0));
+        let id = {
+            let times_called = times_called.clone();
+            scheduler
+                .every(1.hour())
+                .max_per_day(2)
+                .count(5)
+                .run(move || {
+                    times_called.fetch_add(1, Ordering::SeqCst);
+                })
+                .id()
+        };
+        // Manual runs don't change the schedule
+        assert!(scheduler.run_now(id));
+        assert_eq!(1, times_called.load(Ordering::SeqCst));
+        assert_eq!(scheduler.next_run(id), Some(at(22, 21, 0)));
+        assert_eq!(scheduler.get_by_id(id).unwrap().runs_remaining(), Some(5));
+
+        // They count towards the daily limit, along with scheduled runs
+        scheduler.advance(Duration::from_secs(30 * 60));
+        scheduler.run_pending();
+        assert_eq!(2, times_called.load(Ordering::SeqCst));
+        assert_eq!(scheduler.next_run(id), Some(at(23, 0, 0)));
+        assert!(!scheduler.run_now(id));
+        assert_eq!(2, times_called.load(Ordering::SeqCst));
+
+        // Reaching the limit with a manual run moves the next run to the following day
+        scheduler.advance(Duration::from_secs(4 * 60 * 60));
+        assert!(scheduler.run_now(id));
+        assert_eq!(scheduler.next_run(id), Some(at(23, 0, 0)));
+        assert!(scheduler.run_now(id));
+        assert_eq!(scheduler.next_run(id), Some(at(24, 0, 0)));
+        assert!(!scheduler.run_now(id));
+        assert_eq!(4, times_called.load(Ordering::SeqCst));
+
+        assert!(!scheduler.run_now(Scheduler::new().every(1.hour()).id()));
+    }
+
+    #[test]
+    fn test_run_pending_with() {
+        use crate::timeprovider::{lock_mock_clock, MockClock};
+        use chrono::{TimeZone, Utc};
+        let _guard = lock_mock_clock();
+        MockClock::set(Utc.with_ymd_and_hms(2019, 10, 22, 12, 40, 0).unwrap());
+
+        let mut scheduler = Scheduler::with_tz_and_provider::<Utc, MockClock>(Utc);
+        let failing = scheduler.every(1.hour()).run(|| panic!("Oops")).id();
+        let times_called = Arc::new(AtomicU32::new(0));
+        {
+            let times_called = times_called.clone();
+            scheduler.every(1.hour()).run(move || {
+                times_called.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+
+        let mut panics = vec![];
+        MockClock::set(Utc.with_ymd_and_hms(2019, 10, 22, 13, 0, 0).unwrap());
+        scheduler.run_pending_with(|id, panic| {
+            panics.push((id, panic.downcast_ref::<&str>().copied()));
+        });
+        assert_eq!(panics, vec![(failing, Some("Oops"))]);
+        assert_eq!(1, times_called.load(Ordering::SeqCst));
+
+        // The failing job was rescheduled as usual
+        scheduler.run_pending_with(|_, _| panic!("Already ran"));
+        MockClock::set(Utc.with_ymd_and_hms(2019, 10, 22, 14, 0, 0).unwrap());
+        let mut panicked = 0;
+        scheduler.run_pending_with(|_, _| panicked += 1);
+        assert_eq!(1, panicked);
+        assert_eq!(2, times_called.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_run_pending_with_panicking_callback() {
+        use chrono::{TimeZone, Utc};
+        let at = |h, m| Utc.with_ymd_and_hms(2019, 10, 22, h, m, 0).unwrap();
+        let mut scheduler = Scheduler::frozen_at(Utc, at(12, 40));
+        let times_called = Arc::new(AtomicU32::new(0));
+        let id = {
+            let times_called = times_called.clone();
+            scheduler
+                .every(1.hour())
+                .on_reschedule(|_| panic!("Oops"))
+                .run(move || {
+                    times_called.fetch_add(1, Ordering::SeqCst);
+                })
+                .id()
+        };
+        let mut panics = vec![];
+        scheduler.advance(Duration::from_secs(20 * 60));
+        scheduler.run_pending_with(|id, panic| {
+            panics.push((id, panic.downcast_ref::<&str>().copied()));
+        });
+        assert_eq!(panics, vec![(id, Some("Oops"))]);
+        assert_eq!(1, times_called.load(Ordering::SeqCst));
+        // The job was only rescheduled once, so the next run isn't skipped
+        assert_eq!(scheduler.next_run(id), Some(at(14, 0)));
+        scheduler.advance(Duration::from_secs(3600));
+        scheduler.run_pending_with(|_, _| ());
+        assert_eq!(2, times_called.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_run_pending_with_counts_failures() {
+        use chrono::{TimeZone, Utc};
+        let start = Utc.with_ymd_and_hms(2019, 10, 22, 12, 0, 0).unwrap();
+        let mut scheduler = Scheduler::frozen_at(Utc, start);
+        let times_called = Arc::new(AtomicU32::new(0));
+        let disabled = Arc::new(AtomicU32::new(0));
+        {
+            let times_called = times_called.clone();
+            let disabled = disabled.clone();
+            scheduler
+                .every(1.minute())
+                .disable_after_failures(2)
+                .on_disabled(move || {
+                    disabled.fetch_add(1, Ordering::SeqCst);
+                })
+                .run(move || {
+                    times_called.fetch_add(1, Ordering::SeqCst);
+                    panic!("Oops");
+                });
+        }
+        let mut panicked = 0;
+        for _ in 0..5 {
+            scheduler.advance(Duration::from_secs(60));
+            scheduler.run_pending_with(|_, _| panicked += 1);
+        }
+        assert_eq!(2, times_called.load(Ordering::SeqCst));
+        assert_eq!(2, panicked);
+        assert_eq!(1, disabled.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_state_snapshot() {
+        use super::{JobSnapshot, SchedulerSnapshot};
+        use crate::timeprovider::{lock_mock_clock, MockClock};
+        use chrono::{TimeZone, Utc};
+        let _guard = lock_mock_clock();
+        let at = |h, m| Utc.with_ymd_and_hms(2019, 10, 22, h, m, 0).unwrap();
+        MockClock::set(at(12, 40));
+
+        let mut scheduler = Scheduler::with_tz_and_provider::<Utc, MockClock>(Utc);
+        scheduler.every(1.hour()).label("hourly").once().run(|| {});
+        scheduler.every(30.minutes()).run(|| {});
+        let job = |label: Option<&str>, next_run, last_run, runs_remaining| JobSnapshot {
+            label: label.map(str::to_owned),
+            next_run,
+            last_run,
+            runs_remaining,
+        };
+        assert_eq!(
+            scheduler.state_snapshot(),
+            SchedulerSnapshot {
+                jobs: vec![
+                    job(Some("hourly"), Some(at(13, 0)), None, Some(1)),
+                    job(None, Some(at(13, 0)), None, None),
+                ]
+            }
+        );
+
+        MockClock::set(at(13, 0));
+        scheduler.run_pending();
+        assert_eq!(
+            scheduler.state_snapshot(),
+            SchedulerSnapshot {
+                jobs: vec![
+                    job(Some("hourly"), None, Some(at(13, 0)), Some(0)),
+                    job(None, Some(at(13, 30)), Some(at(13, 0)), None),
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn test_simulate() {
+        use crate::timeprovider::{lock_mock_clock, MockClock};
+        use chrono::{TimeZone, Utc};
+        let _guard = lock_mock_clock();
+        let at = |h, m| Utc.with_ymd_and_hms(2019, 10, 22, h, m, 0).unwrap();
+        MockClock::set(at(12, 40));
+
+        let mut scheduler = Scheduler::with_tz_and_provider::<Utc, MockClock>(Utc);
+        let hourly = scheduler.every(1.hour()).run(|| {}).id();
+        let twice = scheduler.every(25.minutes()).count(2).run(|| {}).id();
+        let runs = scheduler.simulate(at(12, 40), at(14, 0), Duration::from_secs(5 * 60));
+        assert_eq!(
+            runs,
+            vec![
+                (at(12, 55), twice),
+                (at(13, 0), hourly),
+                (at(13, 20), twice),
+                (at(14, 0), hourly),
+            ]
+        );
+
+        // Runs happen at the first step after they're due
+        let runs = scheduler.simulate(at(14, 5), at(16, 0), Duration::from_secs(45 * 60));
+        assert_eq!(runs, vec![(at(15, 35), hourly)]);
+    }
+
+    #[test]
+    fn test_disable_after_failures() {
+        use crate::timeprovider::{lock_mock_clock, MockClock};
+        use chrono::{TimeZone, Utc};
+        let _guard = lock_mock_clock();
+        MockClock::set(Utc.with_ymd_and_hms(2019, 10, 22, 12, 40, 0).unwrap());
+
+        let mut scheduler = Scheduler::with_tz_and_provider::<Utc, MockClock>(Utc);
+        let times_called = Arc::new(AtomicU32::new(0));
+        let disabled = Arc::new(AtomicU32::new(0));
+        {
+            let times_called = times_called.clone();
+            let disabled = disabled.clone();
+            // Fails on every run except the third
+            scheduler
+                .every(1.minute())
+                .disable_after_failures(3)
+                .on_disabled(move || {
+                    disabled.fetch_add(1, Ordering::SeqCst);
+                })
+                .run_fallible(move || match times_called.fetch_add(1, Ordering::SeqCst) {
+                    2 => Ok(()),
+                    _ => Err("Oops"),
+                });
+        }
+        for _ in 0..5 {
+            MockClock::advance(Duration::from_secs(60));
+            scheduler.run_pending();
+        }
+        assert_eq!(5, times_called.load(Ordering::SeqCst));
+        assert_eq!(0, disabled.load(Ordering::SeqCst));
+
+        // The third failure in a row since the success disables the job
+        MockClock::advance(Duration::from_secs(60));
+        scheduler.run_pending();
+        assert_eq!(6, times_called.load(Ordering::SeqCst));
+        assert_eq!(1, disabled.load(Ordering::SeqCst));
+        for _ in 0..3 {
+            MockClock::advance(Duration::from_secs(60));
+            scheduler.run_pending();
+        }
+        assert_eq!(6, times_called.load(Ordering::SeqCst));
+        assert_eq!(1, disabled.load(Ordering::SeqCst));
+        assert_eq!(scheduler.time_until_next_run(), None);
+    }
+
+    #[test]
+    fn test_cadence_from_success() {
+        use chrono::{TimeZone, Utc};
+        use std::sync::atomic::AtomicBool;
+        let at = |h, m| Utc.with_ymd_and_hms(2019, 10, 22, h, m, 0).unwrap();
+        let mut scheduler = Scheduler::frozen_at(Utc, at(12, 0));
+        let succeed = Arc::new(AtomicBool::new(true));
+        let add_job = |scheduler: &mut Scheduler<Utc>, from_success| {
+            let succeed = succeed.clone();
+            let job = scheduler.every(1.hour()).relative_to_start();
+            if from_success {
+                job.cadence_from_success();
+            }
+            job.run_fallible(move || match succeed.load(Ordering::SeqCst) {
+                true => Ok(()),
+                false => Err("Oops"),
+            })
+            .id()
+        };
+        let relative = add_job(&mut scheduler, false);
+        let id = add_job(&mut scheduler, true);
+        let run_at = |scheduler: &mut Scheduler<Utc>, h, m, ok| {
+            let now = scheduler.now();
+            scheduler.advance((at(h, m) - now).to_std().unwrap());
+            succeed.store(ok, Ordering::SeqCst);
+            scheduler.run_pending();
+        };
+        assert_eq!(scheduler.get_by_id(id).unwrap().last_success(), None);
+
+        run_at(&mut scheduler, 13, 0, true);
+        assert_eq!(
+            scheduler.get_by_id(id).unwrap().last_success(),
+            Some(at(13, 0))
+        );
+        assert_eq!(scheduler.next_run(id), Some(at(14, 0)));
+        // A late failure doesn't shift the cadence
+        run_at(&mut scheduler, 14, 20, false);
+        assert_eq!(
+            scheduler.get_by_id(id).unwrap().last_success(),
+            Some(at(13, 0))
+        );
+        assert_eq!(scheduler.next_run(id), Some(at(15, 0)));
+        assert_eq!(scheduler.next_run(relative), Some(at(15, 20)));
+        run_at(&mut scheduler, 15, 0, false);
+        assert_eq!(scheduler.next_run(id), Some(at(16, 0)));
+        // A late success does
+        run_at(&mut scheduler, 16, 10, true);
+        assert_eq!(
+            scheduler.get_by_id(id).unwrap().last_success(),
+            Some(at(16, 10))
+        );
+        assert_eq!(scheduler.next_run(id), Some(at(17, 10)));
+        assert_eq!(
+            scheduler.get_by_id(id).unwrap().explain(),
+            "Every 1 hour, counted from the start of the last successful run."
+        );
+    }
+
+    #[test]
+    fn test_sub_hour_alignment() {
+        use chrono::{TimeZone, Utc};
+        use chrono_tz::Asia::Kathmandu;
+        let at = |h, m, s| Utc.with_ymd_and_hms(2019, 10, 22, h, m, s).unwrap();
+        let mut scheduler = Scheduler::frozen_at(Utc, at(12, 17, 23));
+        let id = scheduler.every(30.minutes()).run(|| {}).id();
+        assert_eq!(scheduler.next_run(id), Some(at(12, 30, 0)));
+        scheduler.advance(Duration::from_secs(13 * 60 - 23));
+        scheduler.run_pending();
+        assert_eq!(scheduler.next_run(id), Some(at(13, 0, 0)));
+        // A job added exactly on a boundary first runs at the next one
+        let on_boundary = scheduler.every(30.minutes()).run(|| {}).id();
+        assert_eq!(scheduler.next_run(on_boundary), Some(at(13, 0, 0)));
+        scheduler.advance(Duration::from_secs(30 * 60));
+        scheduler.run_pending();
+        assert_eq!(scheduler.next_run(id), Some(at(13, 30, 0)));
+        assert_eq!(scheduler.next_run(on_boundary), Some(at(13, 30, 0)));
+
+        let local = |h, m, s| Kathmandu.with_ymd_and_hms(2019, 10, 22, h, m, s).unwrap();
+        let mut scheduler = Scheduler::frozen_at(Kathmandu, local(9, 17, 0));
+        let id = scheduler.every(15.minutes()).run(|| {}).id();
+        assert_eq!(scheduler.next_run(id), Some(local(9, 30, 0)));
+    }
+
+    #[test]
+    fn test_run_next_pending() {
+        use chrono::{TimeZone, Utc};
+        use std::sync::Mutex;
+        let start = Utc.with_ymd_and_hms(2019, 10, 22, 12, 0, 30).unwrap();
+        let mut scheduler = Scheduler::frozen_at(Utc, start);
+        let order = Arc::new(Mutex::new(vec![]));
+        let mut add_job = |ival, name: &'static str, priority| {
+            let order = order.clone();
+            scheduler
+                .every(ival)
+                .priority(priority)
+                .run(move || order.lock().unwrap().push(name));
+        };
+        add_job(10.minutes(), "ten", 0);
+        add_job(1.minute(), "one", 0);
+        add_job(10.minutes(), "urgent ten", 1);
+        add_job(5.minutes(), "five", 0);
+        assert!(!scheduler.run_next_pending());
+
+        // All four are due, for different lengths of time
+        scheduler.advance(Duration::from_secs(10 * 60));
+        for ran in 1..=4 {
+            assert!(scheduler.run_next_pending());
+            assert_eq!(order.lock().unwrap().len(), ran);
+        }
+        assert!(!scheduler.run_next_pending());
+        assert_eq!(
+            *order.lock().unwrap(),
+            vec!["one", "five", "urgent ten", "ten"]
+        );
+    }
+
+    #[test]
+    fn test_on_jobs_changed() {
+        use std::sync::Mutex;
+        let mut scheduler = Scheduler::new();
+        scheduler.every(1.hour()).run(|| {});
+        let counts = Arc::new(Mutex::new(vec![]));
+        {
+            let counts = counts.clone();
+            scheduler.on_jobs_changed(move |count| counts.lock().unwrap().push(count));
+        }
+        let id = scheduler.every(1.minute()).run(|| {}).id();
+        scheduler.every(1.day()).group("daily").run(|| {});
+        scheduler.every(2.days()).group("daily").run(|| {});
+        assert_eq!(*counts.lock().unwrap(), vec![2, 3, 4]);
+
+        assert_eq!(scheduler.remove_group("daily"), 2);
+        assert!(scheduler.remove(id));
+        // Removing nothing isn't a change
+        assert!(!scheduler.remove(id));
+        assert_eq!(scheduler.remove_group("daily"), 0);
+        assert_eq!(*counts.lock().unwrap(), vec![2, 3, 4, 2, 1]);
+
+        scheduler.clear();
+        scheduler.clear();
+        assert_eq!(*counts.lock().unwrap(), vec![2, 3, 4, 2, 1, 0]);
+        assert!(scheduler.jobs.is_empty());
+    }
+
+    #[test]
+    fn test_run_with_handle() {
+        use chrono::{TimeZone, Utc};
+        let at = |h, m| Utc.with_ymd_and_hms(2019, 10, 22, h, m, 0).unwrap();
+        let mut scheduler = Scheduler::frozen_at(Utc, at(12, 10));
+        let runs = Arc::new(AtomicU32::new(0));
+        let (hourly, _) = {
+            let runs = runs.clone();
+            scheduler.every(1.hour()).run_with_handle(move || {
+                runs.fetch_add(1, Ordering::SeqCst);
+            })
+        };
+        let (_, quarterly) = scheduler.every(15.minutes()).run_with_handle(|| {});
+        quarterly.label("quarterly");
+        let quarterly = scheduler.get("quarterly").unwrap().id();
+
+        assert_eq!(scheduler.next_run(hourly), Some(at(13, 0)));
+        assert_eq!(scheduler.next_run(quarterly), Some(at(12, 15)));
+        assert_eq!(
+            scheduler.get_by_id(quarterly).unwrap().get_label(),
+            Some("quarterly")
+        );
+
+        assert!(scheduler.pause_job(hourly));
+        assert_eq!(scheduler.next_run(hourly), None);
+        scheduler.advance(Duration::from_secs(60 * 60));
+        scheduler.run_pending();
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+        assert!(scheduler.resume_job(hourly));
+        // The run missed while paused is skipped
+        assert_eq!(scheduler.next_run(hourly), Some(at(14, 0)));
+
+        assert!(scheduler.remove(hourly));
+        assert!(!scheduler.remove(hourly));
+        assert!(scheduler.get_by_id(hourly).is_none());
+        assert_eq!(scheduler.next_run(hourly), None);
+        assert!(!scheduler.pause_job(hourly));
+        assert_eq!(scheduler.next_run(quarterly), Some(at(13, 15)));
+    }
+
+    #[test]
+    fn test_run_for() {
+        use std::time::Instant;
+        let mut scheduler = Scheduler::new();
+        let runs = Arc::new(AtomicU32::new(0));
+        {
+            let runs = runs.clone();
+            scheduler.every(0.seconds()).run(move || {
+                runs.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        let started = Instant::now();
+        // The thread sleeps for much longer than it runs, so must cut its sleep short
+        let handle = scheduler.run_for(Duration::from_millis(100), Duration::from_secs(10));
+        assert!(handle.is_running());
+        while handle.is_running() {
+            assert!(started.elapsed() < Duration::from_secs(5), "Didn't stop");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(started.elapsed() >= Duration::from_millis(100));
+        assert!(handle.last_tick().is_some());
+        let stopped_at = runs.load(Ordering::SeqCst);
+        std::thread::sleep(Duration::from_millis(50));
+        assert_eq!(runs.load(Ordering::SeqCst), stopped_at);
+        handle.stop();
+    }
+
+    #[test]
+    fn test_reset() {
+        use chrono::{TimeZone, Utc};
+        let start = Utc.with_ymd_and_hms(2019, 10, 22, 12, 0, 0).unwrap();
+        let mut scheduler = Scheduler::frozen_at(Utc, start);
+        let runs = Arc::new(AtomicU32::new(0));
+        {
+            let runs = runs.clone();
+            scheduler
+                .every(1.minute())
+                .count(2)
+                .label("limited")
+                .run(move || {
+                    runs.fetch_add(1, Ordering::SeqCst);
+                });
+        }
+        let run_for = |scheduler: &mut Scheduler<Utc>, minutes| {
+            for _ in 0..minutes {
+                scheduler.advance(Duration::from_secs(60));
+                scheduler.run_pending();
+            }
+        };
+        run_for(&mut scheduler, 5);
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        assert_eq!(scheduler.get("limited").unwrap().runs_remaining(), Some(0));
+
+        let job = scheduler.get_mut("limited").unwrap();
+        job.reset();
+        assert_eq!(job.runs_remaining(), Some(2));
+        assert_eq!(job.get_label(), Some("limited"));
+        run_for(&mut scheduler, 5);
+        assert_eq!(runs.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_due_within() {
+        use crate::timeprovider::{lock_mock_clock, MockClock};
+        use chrono::{TimeZone, Utc};
+        let _guard = lock_mock_clock();
+        let at = |h, m| Utc.with_ymd_and_hms(2019, 10, 22, h, m, 0).unwrap();
+        MockClock::set(at(12, 40));
+
+        let mut scheduler = Scheduler::with_tz_and_provider::<Utc, MockClock>(Utc);
+        let hourly = scheduler.every(1.hour()).run(|| {}).id();
+        let quarterly = scheduler.every(15.minutes()).run(|| {}).id();
+        let daily = scheduler.every(1.day()).at("13:40").run(|| {}).id();
+        scheduler.every(10.minutes()).once().run(|| {});
+        scheduler.run_pending_at(at(12, 50));
+
+        // Soonest first, leaving out finished jobs
+        assert_eq!(
+            scheduler.due_within(Duration::from_secs(60 * 60)),
+            vec![
+                (hourly, at(13, 0)),
+                (quarterly, at(13, 0)),
+                (daily, at(13, 40))
+            ]
+        );
+        assert_eq!(scheduler.due_within(Duration::from_secs(10 * 60)), vec![]);
+
+        // Overdue jobs aren't included, and the window includes its end
+        MockClock::set(at(13, 5));
+        assert_eq!(
+            scheduler.due_within(Duration::from_secs(35 * 60)),
+            vec![(daily, at(13, 40))]
+        );
+        scheduler.pause();
+        assert_eq!(scheduler.due_within(Duration::MAX), vec![]);
+    }
+
+    #[cfg(feature = "ical")]
+    #[test]
+    fn test_to_ics() {
+        use chrono::{TimeZone, Utc};
+        let at = |h, m| Utc.with_ymd_and_hms(2019, 10, 22, h, m, 0).unwrap();
+
+        let mut scheduler = Scheduler::frozen_at(Utc, at(12, 40));
+        scheduler.every(1.hour()).label("hourly").run(|| {});
+        let once = scheduler.every(10.minutes()).once().run(|| {}).id();
+        scheduler.every(1.minute()).group("paused").run(|| {});
+        scheduler.pause_group("paused");
+
+        let ics = scheduler.to_ics(Duration::from_secs(2 * 60 * 60));
+        let starts: Vec<_> = ics
+            .lines()
+            .filter(|line| line.starts_with("DTSTART:") || line.starts_with("SUMMARY:"))
+            .collect();
+        assert_eq!(
+            starts,
+            vec![
+                "DTSTART:20191022T125000Z",
+                &*format!("SUMMARY:Job {}", once.as_u64()),
+                "DTSTART:20191022T130000Z",
+                "SUMMARY:hourly",
+                "DTSTART:20191022T140000Z",
+                "SUMMARY:hourly",
+            ]
+        );
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+
+        scheduler.pause();
+        assert_eq!(
+            scheduler
+                .to_ics(Duration::MAX)
+                .matches("BEGIN:VEVENT")
+                .count(),
+            0
+        );
+    }
+
+    #[cfg(feature = "ical")]
+    #[test]
+    fn test_to_ics_forever() {
+        use chrono::{TimeZone, Utc};
+        let mut scheduler =
+            Scheduler::frozen_at(Utc, Utc.with_ymd_and_hms(2019, 10, 22, 12, 40, 0).unwrap());
+        scheduler.every(1.day()).run(|| {});
+        scheduler.every(1.hour()).count(3).run(|| {});
+        // Jobs that run forever are cut off, rather than listed forever
+        let ics = scheduler.to_ics(Duration::MAX);
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 10_000 + 3);
+    }
+
+    #[test]
+    fn test_provider_instance() {
+        use crate::timeprovider::Clock;
+        use chrono::{DateTime, TimeZone, Utc};
+        use std::sync::Mutex;
+
+        #[derive(Clone)]
+        struct ManualClock(Arc<Mutex<DateTime<Utc>>>);
+        impl Clock for ManualClock {
+            fn now(&self) -> DateTime<Utc> {
+                *self.0.lock().unwrap()
+            }
+        }
+
+        let clock = ManualClock(Arc::new(Mutex::new(
+            Utc.with_ymd_and_hms(2019, 10, 22, 12, 0, 0).unwrap(),
+        )));
+        let advance = |minutes| *clock.0.lock().unwrap() += chrono::Duration::minutes(minutes);
+        let counter = Arc::new(AtomicU32::new(0));
+        let mut scheduler = Scheduler::with_tz_and_provider_instance(Utc, clock.clone());
+        {
+            let counter = counter.clone();
+            scheduler.every(10.minutes()).run(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        assert_eq!(
+            scheduler.time_until_next_run(),
+            Some(Duration::from_secs(10 * 60))
+        );
+        scheduler.run_pending();
+        assert_eq!(0, counter.load(Ordering::SeqCst));
+        advance(10);
+        scheduler.run_pending();
+        assert_eq!(1, counter.load(Ordering::SeqCst));
+        advance(5);
+        scheduler.run_pending();
+        assert_eq!(1, counter.load(Ordering::SeqCst));
+        advance(5);
+        scheduler.run_pending();
+        assert_eq!(2, counter.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_idle_job() {
+        use crate::timeprovider::{lock_mock_clock, MockClock};
+        use chrono::{TimeZone, Utc};
+        let _guard = lock_mock_clock();
+        MockClock::set(Utc.with_ymd_and_hms(2019, 10, 22, 12, 0, 0).unwrap());
+
+        let busy = Arc::new(AtomicU32::new(0));
+        let idle = Arc::new(AtomicU32::new(0));
+        let mut scheduler = Scheduler::with_tz_and_provider::<Utc, MockClock>(Utc);
+        {
+            let busy = busy.clone();
+            scheduler.every(2.minutes()).run(move || {
+                busy.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        {
+            let idle = idle.clone();
+            scheduler.set_idle_job(move || {
+                idle.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        // The job runs every other minute, and the idle job in between
+        for minute in 1..=6 {
+            MockClock::advance(Duration::from_secs(60));
+            scheduler.run_pending();
+            assert_eq!(minute / 2, busy.load(Ordering::SeqCst));
+            assert_eq!((minute + 1) / 2, idle.load(Ordering::SeqCst));
+        }
+
+        // Busy ticks from the other ways of running jobs
+        MockClock::advance(Duration::from_secs(120));
+        scheduler.run_pending_parallel();
+        MockClock::advance(Duration::from_secs(120));
+        scheduler.run_pending_with(|_, _| {});
+        assert_eq!(5, busy.load(Ordering::SeqCst));
+        assert_eq!(3, idle.load(Ordering::SeqCst));
+        scheduler.run_pending_parallel();
+        scheduler.run_pending_with(|_, _| {});
+        assert_eq!(5, idle.load(Ordering::SeqCst));
+
+        // Nothing runs while paused
+        scheduler.pause();
+        scheduler.run_pending();
+        assert_eq!(5, idle.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_get_by_label() {
+        let mut scheduler = Scheduler::new();
+        let first = scheduler.every(1.hour()).label("report").run(|| {}).id();
+        let second = scheduler.every(1.day()).label("report").run(|| {}).id();
+        let other = scheduler.every(1.minute()).label("other").run(|| {}).id();
+        scheduler.every(1.minute()).run(|| {});
+
+        // The first job added wins
+        assert_eq!(scheduler.get("report").map(|job| job.id()), Some(first));
+        assert_eq!(scheduler.get("other").map(|job| job.id()), Some(other));
+        assert!(scheduler.get("missing").is_none());
+
+        scheduler.get_mut("report").unwrap().once();
+        assert_eq!(scheduler.get("report").unwrap().runs_remaining(), Some(1));
+        scheduler.get_mut("report").unwrap().label("done");
+        assert_eq!(scheduler.get("report").map(|job| job.id()), Some(second));
+        assert!(scheduler.get_mut("missing").is_none());
+    }
+
+    #[test]
+    fn test_watch_thread_heartbeat() {
+        use std::time::Instant;
+        fn wait_for(mut done: impl FnMut() -> bool) {
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while !done() {
+                assert!(Instant::now() < deadline, "Timed out");
+                std::thread::sleep(Duration::from_millis(5));
+            }
+        }
+
+        let mut scheduler = Scheduler::new();
+        scheduler.every(1.hour()).run(|| {});
+        let before = Instant::now();
+        let handle = scheduler.watch_thread(Duration::from_millis(10));
+        assert!(handle.is_running());
+        wait_for(|| handle.last_tick().is_some());
+        let first = handle.last_tick().unwrap();
+        assert!(first >= before);
+        wait_for(|| handle.last_tick() > Some(first));
+        assert!(handle.is_running());
+        handle.stop();
+
+        // A panicking job ends the thread before it finishes its first tick
+        let mut scheduler = Scheduler::new();
+        scheduler.run_once_now(|| panic!("Job failed"));
+        let handle = scheduler.watch_thread(Duration::from_millis(10));
+        wait_for(|| !handle.is_running());
+        assert_eq!(handle.last_tick(), None);
+    }
+
+    #[test]
+    fn test_run_with_context() {
+        use crate::timeprovider::{lock_mock_clock, MockClock};
+        use chrono::{TimeZone, Utc};
+        let _guard = lock_mock_clock();
+        let at = |h, m| Utc.with_ymd_and_hms(2019, 10, 22, h, m, 0).unwrap();
+        MockClock::set(at(12, 0));
+
+        // Doubles its interval after each run
+        let mut scheduler = Scheduler::with_tz_and_provider::<Utc, MockClock>(Utc);
+        let mut minutes = 1;
+        scheduler
+            .every(1.minute())
+            .relative_to_start()
+            .count(6)
+            .run_with_context(move |context| {
+                minutes *= 2;
+                context.set_interval(minutes.minutes());
+            });
+        let runs: Vec<_> = scheduler
+            .simulate(at(12, 0), at(14, 0), Duration::from_secs(60))
+            .into_iter()
+            .map(|(time, _)| time)
+            .collect();
+        assert_eq!(
+            runs,
+            vec![
+                at(12, 1),
+                at(12, 3),
+                at(12, 7),
+                at(12, 15),
+                at(12, 31),
+                at(13, 3)
+            ]
+        );
+
+        // Delays its next run once
+        let mut scheduler = Scheduler::with_tz_and_provider::<Utc, MockClock>(Utc);
+        let mut first = true;
+        scheduler.every(1.hour()).run_with_context(move |context| {
+            if first {
+                first = false;
+                context.set_next_run(at(13, 5));
+            }
+        });
+        let runs: Vec<_> = scheduler
+            .simulate(at(12, 0), at(15, 0), Duration::from_secs(60))
+            .into_iter()
+            .map(|(time, _)| time)
+            .collect();
+        assert_eq!(runs, vec![at(13, 0), at(13, 5), at(14, 0), at(15, 0)]);
+    }
+
+    #[test]
+    fn test_set_next_run_in_past() {
+        use chrono::{TimeZone, Utc};
+        let at = |h, m, s| Utc.with_ymd_and_hms(2019, 10, 22, h, m, s).unwrap();
+        let mut scheduler = Scheduler::frozen_at(Utc, at(12, 30, 0));
+        let times_called = Arc::new(AtomicU32::new(0));
+        let id = {
+            let times_called = times_called.clone();
+            scheduler
+                .every(1.hour())
+                .run_with_context(move |context| {
+                    times_called.fetch_add(1, Ordering::SeqCst);
+                    context.set_next_run(at(11, 0, 0));
+                })
+                .id()
+        };
+        // A next run in the past doesn't make the job run over and over
+        scheduler.advance(Duration::from_secs(30 * 60));
+        scheduler.run_pending();
+        assert_eq!(1, times_called.load(Ordering::SeqCst));
+        assert_eq!(scheduler.next_run(id), Some(at(13, 0, 1)));
+
+        // Or more often than the minimum interval allows
+        scheduler.set_minimum_interval(Duration::from_secs(5 * 60));
+        scheduler.advance(Duration::from_secs(1));
+        scheduler.run_pending();
+        assert_eq!(2, times_called.load(Ordering::SeqCst));
+        assert_eq!(scheduler.next_run(id), Some(at(13, 5, 1)));
+    }
+
+    #[test]
+    fn test_frozen_at() {
+        use chrono::{TimeZone, Utc};
+        let at = |h, m| Utc.with_ymd_and_hms(2019, 10, 22, h, m, 0).unwrap();
+        // Each scheduler has its own clock
+        let mut early = Scheduler::frozen_at(Utc, at(12, 0));
+        let mut late = Scheduler::frozen_at(Utc, at(12, 30));
+        early.every(1.hour()).run(|| {});
+        late.every(1.hour()).run(|| {});
+        assert_eq!(
+            early.time_until_next_run(),
+            Some(Duration::from_secs(60 * 60))
+        );
+        assert_eq!(
+            late.time_until_next_run(),
+            Some(Duration::from_secs(30 * 60))
+        );
+        early.advance(Duration::from_secs(15 * 60));
+        assert_eq!(
+            early.time_until_next_run(),
+            Some(Duration::from_secs(45 * 60))
+        );
+        assert_eq!(early.due_within(Duration::from_secs(45 * 60)).len(), 1);
+        assert_eq!(late.due_within(Duration::from_secs(15 * 60)), vec![]);
+        assert_eq!(
+            late.time_until_next_run(),
+            Some(Duration::from_secs(30 * 60))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "frozen_at")]
+    fn test_advance_unfrozen() {
+        Scheduler::new().advance(Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_priority() {
+        use chrono::{TimeZone, Utc};
+        use std::sync::Mutex;
+        let order = Arc::new(Mutex::new(vec![]));
+        let mut scheduler =
+            Scheduler::frozen_at(Utc, Utc.with_ymd_and_hms(2019, 10, 22, 12, 0, 0).unwrap());
+        for (name, priority) in [("a", 0), ("b", -1), ("c", 5), ("d", 0), ("e", 5)] {
+            let order = order.clone();
+            scheduler
+                .every(1.minute())
+                .priority(priority)
+                .run(move || order.lock().unwrap().push(name));
+        }
+        {
+            let order = order.clone();
+            scheduler
+                .every(1.hour())
+                .priority(10)
+                .run(move || order.lock().unwrap().push("hourly"));
+        }
+        scheduler.advance(Duration::from_secs(60));
+        scheduler.run_pending();
+        // Highest first, and in the order they were added among equals
+        assert_eq!(*order.lock().unwrap(), vec!["c", "e", "a", "d", "b"]);
+
+        order.lock().unwrap().clear();
+        scheduler.advance(Duration::from_secs(59 * 60));
+        scheduler.run_pending_with(|_, _| {});
+        assert_eq!(
+            *order.lock().unwrap(),
+            vec!["hourly", "c", "e", "a", "d", "b"]
+        );
+    }
+
+    #[test]
+    fn test_dry_run() {
+        use chrono::{TimeZone, Utc};
+        let at = |d, h, m| Utc.with_ymd_and_hms(2019, 10, d, h, m, 0).unwrap();
+        let mut scheduler = Scheduler::frozen_at(Utc, at(22, 12, 0));
+        let rescheduled = Arc::new(AtomicU32::new(0));
+        let hourly = {
+            let rescheduled = rescheduled.clone();
+            scheduler
+                .every(1.hour())
+                .label("hourly")
+                .count(3)
+                .on_reschedule(move |_| {
+                    rescheduled.fetch_add(1, Ordering::SeqCst);
+                })
+                .run(|| panic!("Ran in a dry run"))
+                .id()
+        };
+        let daily = scheduler
+            .every(1.day())
+            .at("13:30")
+            .priority(1)
+            .run(|| panic!("Ran in a dry run"))
+            .id();
+        let twice = scheduler
+            .every(15.minutes())
+            .max_per_day(2)
+            .run(|| panic!("Ran in a dry run"))
+            .id();
+        scheduler
+            .every(1.minute())
+            .group("paused")
+            .run(|| panic!("Ran in a dry run"));
+        scheduler.pause_group("paused");
+        let before: Vec<_> = [hourly, daily, twice]
+            .iter()
+            .map(|&id| {
+                (
+                    scheduler.next_run(id),
+                    scheduler.get_by_id(id).unwrap().runs_remaining(),
+                )
+            })
+            .collect();
+
+        let runs = scheduler.dry_run(at(22, 12, 0), at(23, 13, 30));
+        assert_eq!(
+            runs,
+            vec![
+                (twice, at(22, 12, 15)),
+                (twice, at(22, 12, 30)),
+                (hourly, at(22, 13, 0)),
+                (daily, at(22, 13, 30)),
+                (hourly, at(22, 14, 0)),
+                (hourly, at(22, 15, 0)),
+                (twice, at(23, 0, 0)),
+                (twice, at(23, 0, 15)),
+                (daily, at(23, 13, 30)),
+            ]
+        );
+        assert_eq!(0, rescheduled.load(Ordering::SeqCst));
+        // The jobs themselves haven't changed
+        let after: Vec<_> = [hourly, daily, twice]
+            .iter()
+            .map(|&id| {
+                (
+                    scheduler.next_run(id),
+                    scheduler.get_by_id(id).unwrap().runs_remaining(),
+                )
+            })
+            .collect();
+        assert_eq!(before, after);
+        assert_eq!(scheduler.get("hourly").unwrap().runs_remaining(), Some(3));
+        // So the same dry run gives the same runs
+        assert_eq!(scheduler.dry_run(at(22, 12, 0), at(23, 13, 30)), runs);
+    }
+
+    #[test]
+    fn test_from_registration() {
+        use chrono::{TimeZone, Utc};
+        let at = |h, m| Utc.with_ymd_and_hms(2019, 10, 22, h, m, 0).unwrap();
+        let mut scheduler = Scheduler::frozen_at(Utc, at(12, 20));
+        let aligned = scheduler.every(1.hour()).run(|| {}).id();
+        let anchored = scheduler
+            .every(1.hour())
+            .from_registration()
+            .run(|| {})
+            .id();
+        let relative = scheduler
+            .every(1.hour())
+            .relative_to_start()
+            .run(|| {})
+            .id();
+        assert_eq!(
+            scheduler.due_within(Duration::from_secs(60 * 60)),
+            vec![
+                (aligned, at(13, 0)),
+                (anchored, at(13, 20)),
+                (relative, at(13, 20))
+            ]
+        );
+
+        // Running late doesn't move the anchored job's later runs, unlike the relative one's
+        scheduler.advance(Duration::from_secs(65 * 60));
+        assert_eq!(scheduler.run_pending_and_advise().0, 3);
+        assert_eq!(
+            scheduler.due_within(Duration::from_secs(60 * 60)),
+            vec![
+                (aligned, at(14, 0)),
+                (anchored, at(14, 20)),
+                (relative, at(14, 25))
+            ]
+        );
+        // Registered at 13:25
+        let job = scheduler.every(90.minutes()).from_registration();
+        job.run(|| {});
+        assert_eq!(
+            job.fire_times(at(15, 0)).take(2).collect::<Vec<_>>(),
+            vec![at(16, 25), at(17, 55)]
+        );
+
+        // Intervals without a fixed length step from the anchor
+        let job = scheduler.every(Interval::Monday).from_registration();
+        job.run(|| {});
+        assert_eq!(
+            job.fire_times(at(12, 0)).next(),
+            Some(Utc.with_ymd_and_hms(2019, 10, 28, 0, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_min_gap_with_clock_going_backwards() {
+        use chrono::{TimeZone, Utc};
+        let at = |h, m| Utc.with_ymd_and_hms(2019, 10, 22, h, m, 0).unwrap();
+        let times_called = Arc::new(AtomicU32::new(0));
+        let mut scheduler = Scheduler::frozen_at(Utc, at(12, 0));
+        {
+            let times_called = times_called.clone();
+            scheduler
+                .every(1.minute())
+                .min_gap(Duration::from_secs(10 * 60))
+                .run(move || {
+                    times_called.fetch_add(1, Ordering::SeqCst);
+                });
+        }
+        scheduler.run_pending_at(at(12, 1));
+        assert_eq!(1, times_called.load(Ordering::SeqCst));
+
+        // The clock is set back, and runs forward again through the last run
+        for minute in 0..=10 {
+            scheduler.run_pending_at(at(12, minute));
+            assert_eq!(1, times_called.load(Ordering::SeqCst), "at 12:{}", minute);
+        }
+        scheduler.run_pending_at(at(12, 11));
+        assert_eq!(2, times_called.load(Ordering::SeqCst));
+    }
 }
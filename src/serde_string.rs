@@ -0,0 +1,171 @@
+//! Serialize an [`Interval`] as a string, rather than as an enum, for hand-written configuration files.
+//! Available with the `serde` feature.
+//!
+//! Fixed-length intervals are written as a count and the first letter of the unit, e.g. `"5s"` or `"2h"`,
+//! and the others as in their [`Display`](core::fmt::Display) form without the leading "every", e.g. `"monday"`,
+//! `"weekday"` or `"monday, wednesday and friday"`. Anything accepted by [`Interval::from_str`](core::str::FromStr)
+//! can be deserialized.
+//!
+//! Use it on a field with `#[serde(with = "clokwerk::serde_string")]`, or on an `Option<Interval>`
+//! with `#[serde(with = "clokwerk::serde_string::option")]`. Without it, intervals use the enum
+//! representation, e.g. `{"Seconds":5}`.
+//! ```rust
+//! # use clokwerk::{Interval, TimeUnits};
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Config {
+//!     #[serde(with = "clokwerk::serde_string")]
+//!     poll: Interval,
+//!     #[serde(with = "clokwerk::serde_string")]
+//!     report: Interval,
+//! }
+//!
+//! let config: Config = serde_json::from_str(r#"{"poll": "30s", "report": "friday"}"#).unwrap();
+//! assert_eq!(config.poll, 30.seconds());
+//! assert_eq!(config.report, Interval::Friday);
+//! ```
+use alloc::string::ToString;
+use core::fmt;
+
+use serde::{de, Deserializer, Serializer};
+
+use crate::Interval;
+
+/// Serialize `interval` as a string
+pub fn serialize<S: Serializer>(interval: &Interval, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&Abbreviated(*interval))
+}
+
+/// Deserialize an interval from a string
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Interval, D::Error> {
+    deserializer.deserialize_str(IntervalVisitor)
+}
+
+/// The same, for an optional interval, e.g. `#[serde(with = "clokwerk::serde_string::option")]`.
+/// `None` is serialized as `null`.
+pub mod option {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::Abbreviated;
+    use crate::Interval;
+
+    /// Serialize `interval` as a string, if there is one
+    pub fn serialize<S: Serializer>(
+        interval: &Option<Interval>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match interval {
+            Some(interval) => serializer.collect_str(&Abbreviated(*interval)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserialize an interval from a string, if there is one
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Interval>, D::Error> {
+        #[derive(Deserialize)]
+        struct Wrapper(#[serde(with = "super")] Interval);
+        Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|Wrapper(interval)| interval))
+    }
+}
+
+struct Abbreviated(Interval);
+
+impl fmt::Display for Abbreviated {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Interval::Seconds(n) => write!(f, "{}s", n),
+            Interval::Minutes(n) => write!(f, "{}m", n),
+            Interval::Hours(n) => write!(f, "{}h", n),
+            Interval::Days(n) => write!(f, "{}d", n),
+            Interval::Weeks(n) => write!(f, "{}w", n),
+            other => {
+                let long = other.to_string().to_ascii_lowercase();
+                f.write_str(long.strip_prefix("every ").unwrap_or(&long))
+            }
+        }
+    }
+}
+
+struct IntervalVisitor;
+
+impl de::Visitor<'_> for IntervalVisitor {
+    type Value = Interval;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an interval, like \"5s\" or \"monday\"")
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Interval, E> {
+        s.parse().map_err(E::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Interval, TimeUnits, WeekdaySet};
+    use chrono::Weekday;
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Config {
+        #[serde(with = "crate::serde_string")]
+        every: Interval,
+        #[serde(with = "crate::serde_string::option", default)]
+        retry: Option<Interval>,
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let cases = [
+            (5.seconds(), r#""5s""#),
+            (10.minutes(), r#""10m""#),
+            (2.hours(), r#""2h""#),
+            (1.day(), r#""1d""#),
+            (3.weeks(), r#""3w""#),
+            (Interval::Monday, r#""monday""#),
+            (Interval::Weekday, r#""weekday""#),
+            (
+                Interval::days_of_week(&[Weekday::Mon, Weekday::Wed, Weekday::Fri]),
+                r#""monday, wednesday and friday""#,
+            ),
+            (Interval::DaysOfWeek(WeekdaySet::empty()), r#""never""#),
+            (
+                Interval::MonthDayOrNextWeekday(15),
+                r#""month on day 15, or the next weekday""#,
+            ),
+            (Interval::IsoWeeks(2), r#""2 iso weeks""#),
+        ];
+        for (interval, expected) in cases {
+            let config = Config {
+                every: interval,
+                retry: Some(interval),
+            };
+            let serialized = serde_json::to_string(&config).unwrap();
+            assert_eq!(
+                serialized,
+                format!(r#"{{"every":{},"retry":{}}}"#, expected, expected)
+            );
+            assert_eq!(serde_json::from_str::<Config>(&serialized).unwrap(), config);
+        }
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let config: Config = serde_json::from_str(r#"{"every": "every 15 Minutes"}"#).unwrap();
+        assert_eq!(
+            config,
+            Config {
+                every: 15.minutes(),
+                retry: None
+            }
+        );
+        let config: Config =
+            serde_json::from_str(r#"{"every": "Tuesday", "retry": null}"#).unwrap();
+        assert_eq!(config.every, Interval::Tuesday);
+        assert_eq!(config.retry, None);
+
+        let err = serde_json::from_str::<Config>(r#"{"every": "fortnightly"}"#).unwrap_err();
+        assert!(err.to_string().contains("fortnightly"), "{}", err);
+        assert!(serde_json::from_str::<Config>(r#"{"every": {"Seconds": 5}}"#).is_err());
+    }
+}
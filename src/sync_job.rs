@@ -1,12 +1,18 @@
 use crate::Interval;
 use crate::{
+    error::JobError,
     job::Job,
-    job_schedule::{JobSchedule, WithSchedule},
+    job_schedule::{record_outcome, record_success, JobContext, JobId, JobSchedule, WithSchedule},
 };
 
 use crate::timeprovider::{ChronoTimeProvider, TimeProvider};
 use chrono::prelude::*;
+use std::any::Any;
 use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+/// The task run by a [`SyncJob`], wrapped to take a [`JobContext`] and return a result
+type Task<Tz> = Box<dyn FnMut(&mut JobContext<Tz>) -> Result<(), JobError> + Send>;
 
 /// A job to run on the scheduler.
 /// Create these by calling [`Scheduler::every()`](crate::Scheduler::every).
@@ -18,7 +24,9 @@ where
     Tp: TimeProvider,
 {
     schedule: JobSchedule<Tz, Tp>,
-    job: Option<Box<dyn FnMut() + Send>>,
+    job: Option<Task<Tz>>,
+    // Set from the start of a run until the job has been rescheduled, so that a run that panics can be recovered
+    running: bool,
 }
 
 impl<Tz, Tp> WithSchedule<Tz, Tp> for SyncJob<Tz, Tp>
@@ -61,27 +69,257 @@ where
         SyncJob {
             schedule: JobSchedule::new(ival, tz),
             job: None,
+            running: false,
         }
     }
 
     /// Specify a task to run, and schedule its next run
-    pub fn run<F>(&mut self, f: F) -> &mut Self
+    ///
+    /// # Panics
+    /// Panics if a time given to [`Job::at`](crate::Job::at) couldn't be parsed. So do the other methods
+    /// that specify a task, such as [`SyncJob::run_fallible`].
+    pub fn run<F>(&mut self, mut f: F) -> &mut Self
+    where
+        F: 'static + FnMut() + Send,
+    {
+        self.job = Some(Box::new(move |_: &mut JobContext<Tz>| {
+            f();
+            Ok(())
+        }));
+        self.schedule.start_schedule();
+        self
+    }
+
+    /// Like [`SyncJob::run`], but also returns the job's ID, which can be kept to manage the job through the
+    /// scheduler later, after the borrow of the scheduler has ended.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// let mut scheduler = Scheduler::new();
+    /// let (id, job) = scheduler.every(1.hour()).run_with_handle(|| println!("Polling"));
+    /// job.label("poll");
+    /// // ...later
+    /// println!("Next poll at {:?}", scheduler.next_run(id));
+    /// scheduler.remove(id);
+    /// ```
+    /// See [`Scheduler::get_by_id`](crate::Scheduler::get_by_id), [`Scheduler::next_run`](crate::Scheduler::next_run),
+    /// [`Scheduler::pause_job`](crate::Scheduler::pause_job) and [`Scheduler::remove`](crate::Scheduler::remove).
+    pub fn run_with_handle<F>(&mut self, f: F) -> (JobId, &mut Self)
     where
         F: 'static + FnMut() + Send,
     {
-        self.job = Some(Box::new(f));
+        self.run(f);
+        (self.schedule.id(), self)
+    }
+
+    /// Specify a task that can fail, and schedule its next run. Failed runs are counted towards
+    /// [`Job::disable_after_failures`], and logged if the `tracing` feature is enabled.
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # fn sync_files() -> Result<(), std::io::Error> { Ok(()) }
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler
+    ///     .every(10.minutes())
+    ///     .disable_after_failures(5)
+    ///     .run_fallible(sync_files);
+    /// ```
+    pub fn run_fallible<F, E>(&mut self, mut f: F) -> &mut Self
+    where
+        F: 'static + FnMut() -> Result<(), E> + Send,
+        E: Into<JobError>,
+    {
+        self.job = Some(Box::new(move |_: &mut JobContext<Tz>| {
+            f().map_err(Into::into)
+        }));
+        self.schedule.start_schedule();
+        self
+    }
+
+    /// Specify a task that can change its own schedule through a [`JobContext`], and schedule its next run.
+    /// For example, to poll more often while there's work to do:
+    /// ```rust
+    /// # use clokwerk::*;
+    /// # fn poll() -> bool { true }
+    /// let mut scheduler = Scheduler::new();
+    /// scheduler.every(1.minute()).run_with_context(|context| {
+    ///     if poll() {
+    ///         context.set_interval(10.seconds());
+    ///     } else {
+    ///         context.set_interval(1.minute());
+    ///     }
+    /// });
+    /// ```
+    pub fn run_with_context<F>(&mut self, mut f: F) -> &mut Self
+    where
+        F: 'static + FnMut(&mut JobContext<Tz>) + Send,
+    {
+        self.job = Some(Box::new(move |context: &mut JobContext<Tz>| {
+            f(context);
+            Ok(())
+        }));
         self.schedule.start_schedule();
         self
     }
 
     /// Run a task and re-schedule it. This is usually only called by
     /// [Scheduler::run_pending()](crate::Scheduler::run_pending).
-    pub fn execute(&mut self, now: &DateTime<Tz>) {
+    ///
+    /// Returns whether the task was run: it won't be if the job has run out of runs,
+    /// or the run was skipped because of [`Job::skip_if_late_by`](crate::Job::skip_if_late_by)
+    /// or [`Job::max_per_day`](crate::Job::max_per_day).
+    pub fn execute(&mut self, now: &DateTime<Tz>) -> bool {
+        // Runs that panicked since the last time are only known about now
+        self.schedule.disable_if_failing();
+        self.running = true;
+        if !self.ready_to_run(now) || self.skipped_by_condition_or_chance(now) {
+            self.running = false;
+            return false;
+        }
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("job", label = self.schedule.get_label()).entered();
+        #[cfg(feature = "tracing")]
+        if self.schedule.is_overtaken(now) {
+            tracing::warn!("late run");
+        }
+        #[cfg(feature = "tracing")]
+        let _panic_warning = crate::tracing_support::PanicWarning;
+        self.run_task(now);
+        self.schedule.call_on_nth();
+        self.schedule.schedule_next_quietly(now);
+        self.running = false;
+        self.schedule.notify_rescheduled();
+        self.schedule.disable_if_failing();
+        true
+    }
+
+    /// Run the task straight away, outside the job's schedule, returning whether it ran. It doesn't run if the job
+    /// won't run again, or has reached its [`Job::max_per_day`](crate::Job::max_per_day) limit. The run counts
+    /// towards that limit and [`Job::disable_after_failures`](crate::Job::disable_after_failures), but not
+    /// [`Job::count`](crate::Job::count), and the job's scheduled runs are otherwise unchanged.
+    /// Used by [`Scheduler::run_now`](crate::Scheduler::run_now).
+    pub(crate) fn run_now(&mut self, now: &DateTime<Tz>) -> bool {
+        self.schedule.disable_if_failing();
+        if !self.schedule.can_run_again() || !self.schedule.count_manual_run(now) {
+            return false;
+        }
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("job", label = self.schedule.get_label()).entered();
+        #[cfg(feature = "tracing")]
+        let _panic_warning = crate::tracing_support::PanicWarning;
+        self.run_task(now);
+        self.schedule.disable_if_failing();
+        true
+    }
+
+    /// Call the task, recording whether it succeeded
+    fn run_task(&mut self, now: &DateTime<Tz>) {
+        if let Some(f) = self.job.as_mut() {
+            let mut context = JobContext::new();
+            let result = f(&mut context);
+            self.schedule.set_context(context);
+            #[cfg(feature = "tracing")]
+            if let Err(e) = &result {
+                tracing::warn!(error = %e, "job failed");
+            }
+            record_outcome(&self.schedule.failures(), result.is_ok());
+            if result.is_ok() {
+                record_success(&self.schedule.last_success_millis(), now.timestamp_millis());
+            }
+        }
+    }
+
+    /// Run the job for as long as it's pending at `now`, calling `on_run` after each run, and return
+    /// whether it ran. Jobs replaying missed runs with `CatchUp::RunAll` can be due again straight away.
+    /// `from_clock` is as for [`JobSchedule::is_due`].
+    pub(crate) fn run_while_pending<F: FnMut()>(
+        &mut self,
+        now: &DateTime<Tz>,
+        from_clock: bool,
+        mut on_run: F,
+    ) -> bool {
+        let mut ran = false;
+        while self.schedule.is_due(now, from_clock) && self.execute(now) {
+            ran = true;
+            on_run();
+        }
+        ran
+    }
+
+    /// Like [`SyncJob::run_while_pending`], but catch any run that panics and pass the panic to `on_panic`.
+    /// A run that panics counts as a failure, and the job is rescheduled without calling its callbacks.
+    pub(crate) fn run_while_pending_catching<F, P>(
+        &mut self,
+        now: &DateTime<Tz>,
+        from_clock: bool,
+        mut on_run: F,
+        mut on_panic: P,
+    ) -> bool
+    where
+        F: FnMut(),
+        P: FnMut(Box<dyn Any + Send>),
+    {
+        let mut ran = false;
+        loop {
+            match panic::catch_unwind(AssertUnwindSafe(|| {
+                self.run_while_pending(now, from_clock, &mut on_run)
+            })) {
+                Ok(job_ran) => return ran || job_ran,
+                Err(payload) => {
+                    ran = true;
+                    self.recover_from_panic(now);
+                    on_panic(payload);
+                }
+            }
+        }
+    }
+
+    /// After a panic, count the run as failed and reschedule the job, unless the panic came after
+    /// it was rescheduled (e.g. from [`Job::on_reschedule`]).
+    fn recover_from_panic(&mut self, now: &DateTime<Tz>) {
+        if !std::mem::take(&mut self.running) {
+            return;
+        }
+        record_outcome(&self.schedule.failures(), false);
+        self.schedule.schedule_next_quietly(now);
+    }
+
+    /// Check whether the job should run at `now`, skipping the run if it's late or over its daily limit.
+    fn ready_to_run(&mut self, now: &DateTime<Tz>) -> bool {
         // Don't do anything if we're run out of runs
         if !self.schedule.can_run_again() {
-            return;
+            return false;
+        }
+        if self.schedule.skip_if_late(now) {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(label = self.schedule.get_label(), "skipped late run");
+            return false;
+        }
+        if self.schedule.skip_if_over_daily_limit(now) {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                label = self.schedule.get_label(),
+                "skipped run over daily limit"
+            );
+            return false;
+        }
+        true
+    }
+
+    /// For a run that's ready to go ahead, skip it if its [`Job::run_if`] condition doesn't hold, or it
+    /// loses the roll for [`Job::with_probability`]. Returns whether the run was skipped.
+    fn skipped_by_condition_or_chance(&mut self, now: &DateTime<Tz>) -> bool {
+        if self.schedule.skip_if_condition_unmet(now) {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                label = self.schedule.get_label(),
+                "skipped run as its condition didn't hold"
+            );
+            return true;
+        }
+        let skip = self.schedule.skip_by_chance(now);
+        #[cfg(feature = "tracing")]
+        if skip {
+            tracing::debug!(label = self.schedule.get_label(), "skipped run by chance");
         }
-        self.job.as_mut().map(|f| f());
-        self.schedule.schedule_next(now);
+        skip
     }
 }
@@ -0,0 +1,76 @@
+//! Worker threads for [`Scheduler::with_thread_pool`](crate::Scheduler::with_thread_pool).
+use std::fmt;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+type Task = Box<dyn FnOnce() + Send>;
+
+/// A fixed number of threads, which take tasks from a shared queue in the order they were added
+pub(crate) struct ThreadPool {
+    sender: Option<Sender<Task>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl ThreadPool {
+    /// Start `size` worker threads
+    ///
+    /// # Panics
+    /// Panics if `size` is zero, or if a thread can't be started.
+    pub(crate) fn new(size: usize) -> Self {
+        assert!(size > 0, "A thread pool needs at least one thread");
+        let (sender, receiver) = mpsc::channel::<Task>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers = (0..size)
+            .map(|i| {
+                let receiver = receiver.clone();
+                thread::Builder::new()
+                    .name(format!("clokwerk-worker-{}", i))
+                    .spawn(move || loop {
+                        // The lock is released before running the task, so other workers can take the next one
+                        let task = receiver.lock().unwrap().recv();
+                        match task {
+                            Ok(task) => task(),
+                            // The pool has been dropped
+                            Err(_) => break,
+                        }
+                    })
+                    .expect("Couldn't start a worker thread")
+            })
+            .collect();
+        ThreadPool {
+            sender: Some(sender),
+            workers,
+        }
+    }
+
+    /// Queue `task` to run on the next free worker
+    pub(crate) fn execute<F>(&self, task: F)
+    where
+        F: 'static + FnOnce() + Send,
+    {
+        self.sender
+            .as_ref()
+            .expect("Thread pool is shutting down")
+            .send(Box::new(task))
+            .expect("Worker threads have stopped");
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        // Closing the queue stops each worker once it's finished its current task
+        self.sender.take();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl fmt::Debug for ThreadPool {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ThreadPool")
+            .field("size", &self.workers.len())
+            .finish()
+    }
+}
@@ -20,3 +20,270 @@ impl TimeProvider for ChronoTimeProvider {
         chrono::Local::now().with_timezone(tz)
     }
 }
+
+/// A source of the current time that's an instance, rather than a type like [`TimeProvider`].
+/// This allows the clock to carry its own state, e.g. a test clock owned by a single test,
+/// or a clock synchronized with some external source.
+/// Use it with [`Scheduler::with_tz_and_provider_instance`](crate::Scheduler::with_tz_and_provider_instance).
+///
+/// ```rust
+/// # use clokwerk::timeprovider::Clock;
+/// use chrono::{DateTime, Utc};
+/// use std::sync::{Arc, Mutex};
+///
+/// #[derive(Clone)]
+/// struct ManualClock(Arc<Mutex<DateTime<Utc>>>);
+///
+/// impl Clock for ManualClock {
+///     fn now(&self) -> DateTime<Utc> {
+///         *self.0.lock().unwrap()
+///     }
+/// }
+/// ```
+pub trait Clock: Send + Sync {
+    /// Returns the current time, according to the clock
+    fn now(&self) -> chrono::DateTime<chrono::Utc>;
+}
+
+impl Clock for ChronoTimeProvider {
+    /// Returns the current time, according to `chrono`
+    fn now(&self) -> chrono::DateTime<chrono::Utc> {
+        chrono::Utc::now()
+    }
+}
+
+/// A [`Clock`] shared by a scheduler and its jobs
+#[derive(Clone)]
+pub(crate) struct SharedClock {
+    clock: std::sync::Arc<dyn Clock>,
+    /// Set if `clock` is a [`FrozenClock`], whose monotonic time also only moves when told to
+    frozen: Option<FrozenClock>,
+}
+
+impl SharedClock {
+    pub(crate) fn new<C: Clock + 'static>(clock: C) -> Self {
+        let frozen = (&clock as &dyn std::any::Any)
+            .downcast_ref::<FrozenClock>()
+            .cloned();
+        SharedClock {
+            clock: std::sync::Arc::new(clock),
+            frozen,
+        }
+    }
+
+    pub(crate) fn now<Tz: chrono::TimeZone>(&self, tz: &Tz) -> chrono::DateTime<Tz> {
+        self.clock.now().with_timezone(tz)
+    }
+
+    /// The monotonic time that goes with this clock
+    pub(crate) fn monotonic_now(&self) -> std::time::Instant {
+        match &self.frozen {
+            Some(frozen) => frozen.monotonic_now(),
+            None => monotonic_now(),
+        }
+    }
+}
+
+impl std::fmt::Debug for SharedClock {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("SharedClock")
+    }
+}
+
+/// A [`Clock`] whose time only moves when told to, for use in tests.
+/// Unlike [`MockClock`], each one has its own time, so tests that use their own clocks can run at the same time.
+/// Clones share the same time.
+///
+/// [`Scheduler::frozen_at`](crate::Scheduler::frozen_at) creates a scheduler with one of these. To keep hold of
+/// the clock, e.g. to share it between schedulers, pass it to
+/// [`Scheduler::with_tz_and_provider_instance`](crate::Scheduler::with_tz_and_provider_instance) instead.
+/// ```rust
+/// # use clokwerk::*;
+/// # use clokwerk::timeprovider::FrozenClock;
+/// use chrono::{TimeZone, Utc};
+/// use std::time::Duration;
+///
+/// let clock = FrozenClock::new(Utc.with_ymd_and_hms(2021, 3, 14, 15, 9, 0).unwrap());
+/// let mut scheduler = Scheduler::with_tz_and_provider_instance(Utc, clock.clone());
+/// scheduler.every(1.minute()).run(|| println!("Tick"));
+/// scheduler.run_pending(); // Nothing happens
+/// clock.advance(Duration::from_secs(60));
+/// scheduler.run_pending(); // Prints "Tick"
+/// ```
+///
+/// The monotonic time used by [`Scheduler::use_monotonic_for_intervals`](crate::Scheduler::use_monotonic_for_intervals)
+/// is frozen too, and [`FrozenClock::advance`] moves it along with the wall clock.
+#[derive(Debug, Clone)]
+pub struct FrozenClock(std::sync::Arc<FrozenTime>);
+
+#[derive(Debug)]
+struct FrozenTime {
+    now_nanos: std::sync::atomic::AtomicI64,
+    elapsed_nanos: std::sync::atomic::AtomicI64,
+    started: std::time::Instant,
+}
+
+impl FrozenClock {
+    /// Create a clock stopped at `time`
+    pub fn new<Tz: chrono::TimeZone>(time: chrono::DateTime<Tz>) -> Self {
+        FrozenClock(std::sync::Arc::new(FrozenTime {
+            now_nanos: std::sync::atomic::AtomicI64::new(to_nanos(time)),
+            elapsed_nanos: std::sync::atomic::AtomicI64::new(0),
+            started: std::time::Instant::now(),
+        }))
+    }
+
+    /// Move the clock's time forward by `duration`
+    pub fn advance(&self, duration: std::time::Duration) {
+        let nanos = duration_nanos(duration);
+        self.0
+            .now_nanos
+            .fetch_add(nanos, std::sync::atomic::Ordering::SeqCst);
+        self.0
+            .elapsed_nanos
+            .fetch_add(nanos, std::sync::atomic::Ordering::SeqCst);
+    }
+
+    /// The clock's monotonic time, which only moves with [`FrozenClock::advance`]
+    pub(crate) fn monotonic_now(&self) -> std::time::Instant {
+        self.0.started
+            + std::time::Duration::from_nanos(
+                self.0
+                    .elapsed_nanos
+                    .load(std::sync::atomic::Ordering::SeqCst) as u64,
+            )
+    }
+}
+
+impl Clock for FrozenClock {
+    fn now(&self) -> chrono::DateTime<chrono::Utc> {
+        from_nanos(self.0.now_nanos.load(std::sync::atomic::Ordering::SeqCst))
+    }
+}
+
+fn to_nanos<Tz: chrono::TimeZone>(time: chrono::DateTime<Tz>) -> i64 {
+    time.timestamp()
+        .checked_mul(1_000_000_000)
+        .and_then(|n| n.checked_add(i64::from(time.timestamp_subsec_nanos())))
+        .expect("Clock time out of range")
+}
+
+fn duration_nanos(duration: std::time::Duration) -> i64 {
+    std::convert::TryFrom::try_from(duration.as_nanos()).expect("Clock duration out of range")
+}
+
+fn from_nanos(nanos: i64) -> chrono::DateTime<chrono::Utc> {
+    use chrono::TimeZone;
+    chrono::Utc.timestamp_nanos(nanos)
+}
+
+#[cfg(any(test, feature = "testing"))]
+static MOCK_NOW_NANOS: std::sync::atomic::AtomicI64 = std::sync::atomic::AtomicI64::new(0);
+
+/// Time passed with [`MockClock::advance`], which also moves the monotonic clock
+#[cfg(any(test, feature = "testing"))]
+static MOCK_ELAPSED_NANOS: std::sync::atomic::AtomicI64 = std::sync::atomic::AtomicI64::new(0);
+
+/// The monotonic time used by [`Scheduler::use_monotonic_for_intervals`](crate::Scheduler::use_monotonic_for_intervals).
+/// With the `testing` feature, this includes any time passed with [`MockClock::advance`].
+pub(crate) fn monotonic_now() -> std::time::Instant {
+    let now = std::time::Instant::now();
+    #[cfg(any(test, feature = "testing"))]
+    let now = now
+        + std::time::Duration::from_nanos(
+            MOCK_ELAPSED_NANOS.load(std::sync::atomic::Ordering::SeqCst) as u64,
+        );
+    now
+}
+
+/// A TimeProvider whose time only changes when told to, for use in tests.
+/// Available with the `testing` feature.
+///
+/// ```rust
+/// # use clokwerk::*;
+/// # use clokwerk::timeprovider::MockClock;
+/// use chrono::{TimeZone, Utc};
+/// use std::time::Duration;
+///
+/// MockClock::set(Utc.with_ymd_and_hms(2021, 3, 14, 15, 9, 0).unwrap());
+/// let mut scheduler = Scheduler::with_tz_and_provider::<Utc, MockClock>(Utc);
+/// scheduler.every(1.minute()).run(|| println!("Tick"));
+/// scheduler.run_pending(); // Nothing happens
+/// MockClock::advance(Duration::from_secs(60));
+/// scheduler.run_pending(); // Prints "Tick"
+/// ```
+///
+/// The time is stored in a single atomic shared by the whole process, so every scheduler using
+/// `MockClock` sees the same time, and tests that set or advance it interfere with each other when
+/// they run at the same time. Prefer [`FrozenClock`] (or [`Scheduler::frozen_at`](crate::Scheduler::frozen_at)),
+/// which gives each test its own clock.
+/// Until [MockClock::set] is first called, the time is the start of the Unix epoch.
+///
+/// [MockClock::advance] also moves the monotonic clock used by
+/// [`Scheduler::use_monotonic_for_intervals`](crate::Scheduler::use_monotonic_for_intervals), while
+/// [MockClock::set] only changes the wall clock, as when the system time is changed.
+#[cfg(any(test, feature = "testing"))]
+pub struct MockClock {}
+
+#[cfg(any(test, feature = "testing"))]
+impl MockClock {
+    /// Set the current time
+    pub fn set<Tz: chrono::TimeZone>(time: chrono::DateTime<Tz>) {
+        MOCK_NOW_NANOS.store(to_nanos(time), std::sync::atomic::Ordering::SeqCst);
+    }
+
+    /// Move the current time forward by `duration`
+    pub fn advance(duration: std::time::Duration) {
+        let nanos = duration_nanos(duration);
+        MOCK_NOW_NANOS.fetch_add(nanos, std::sync::atomic::Ordering::SeqCst);
+        MOCK_ELAPSED_NANOS.fetch_add(nanos, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl TimeProvider for MockClock {
+    /// Returns the time last set on the `MockClock`
+    fn now<Tz>(tz: &Tz) -> chrono::DateTime<Tz>
+    where
+        Tz: chrono::TimeZone + Sync + Send,
+    {
+        from_nanos(MOCK_NOW_NANOS.load(std::sync::atomic::Ordering::SeqCst)).with_timezone(tz)
+    }
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl Clock for MockClock {
+    /// Returns the time last set on the `MockClock`
+    fn now(&self) -> chrono::DateTime<chrono::Utc> {
+        <MockClock as TimeProvider>::now(&chrono::Utc)
+    }
+}
+
+/// Serializes tests that use the process-wide [MockClock].
+#[cfg(test)]
+pub(crate) fn lock_mock_clock() -> std::sync::MutexGuard<'static, ()> {
+    static LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lock_mock_clock, MockClock, TimeProvider};
+    use chrono::{TimeZone, Utc};
+    use std::time::Duration;
+
+    #[test]
+    fn test_mock_clock() {
+        let _guard = lock_mock_clock();
+        let start = Utc.with_ymd_and_hms(2021, 3, 14, 15, 9, 26).unwrap();
+        MockClock::set(start);
+        assert_eq!(MockClock::now(&Utc), start);
+        MockClock::advance(Duration::from_millis(1500));
+        assert_eq!(
+            MockClock::now(&Utc),
+            start + chrono::Duration::milliseconds(1500)
+        );
+        MockClock::set(start - chrono::Duration::days(1));
+        assert_eq!(MockClock::now(&Utc), start - chrono::Duration::days(1));
+    }
+}
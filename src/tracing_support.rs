@@ -0,0 +1,65 @@
+//! Helpers for the `tracing` feature.
+use std::{
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// Logs a warning if dropped while a job is panicking.
+pub(crate) struct PanicWarning;
+
+impl Drop for PanicWarning {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            tracing::warn!("job panicked");
+        }
+    }
+}
+
+/// Wraps a job's future, logging a warning if it panics while being polled.
+/// [`PanicWarning`] doesn't work for futures, which aren't dropped until after the panic has been caught.
+pub(crate) struct WarnOnPanic<F>(pub(crate) F);
+
+impl<F: Future + Unpin> Future for WarnOnPanic<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        let future = &mut self.0;
+        match panic::catch_unwind(AssertUnwindSafe(|| Pin::new(future).poll(cx))) {
+            Ok(poll) => poll,
+            Err(payload) => {
+                tracing::warn!("job panicked");
+                panic::resume_unwind(payload)
+            }
+        }
+    }
+}
+
+/// Records the spans and the levels of the events it sees, for tests.
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct Recorder {
+    pub(crate) spans: std::sync::Mutex<Vec<String>>,
+    pub(crate) events: std::sync::Mutex<Vec<String>>,
+}
+
+#[cfg(test)]
+impl tracing::Subscriber for Recorder {
+    fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+        true
+    }
+    fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+        let mut spans = self.spans.lock().unwrap();
+        spans.push(format!("{} {:?}", span.metadata().name(), span.values()));
+        tracing::span::Id::from_u64(spans.len() as u64)
+    }
+    fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record<'_>) {}
+    fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+    fn event(&self, event: &tracing::Event<'_>) {
+        let level = event.metadata().level().to_string();
+        self.events.lock().unwrap().push(level);
+    }
+    fn enter(&self, _: &tracing::span::Id) {}
+    fn exit(&self, _: &tracing::span::Id) {}
+}